@@ -0,0 +1,271 @@
+#![allow(dead_code)]
+use std::fmt::Debug;
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+// a^k % q
+pub fn fast_pow_modulo(a: i64, k: i64, q: i64) -> i64 {
+    if k == 1 {
+        return a % q;
+    }
+    if k == 0 {
+        return 1;
+    }
+    let half = fast_pow_modulo(a, k / 2, q);
+    let mut res = (half * half) % q;
+    if k % 2 == 1 {
+        res *= a;
+        res %= q;
+    }
+    res
+}
+
+pub fn lcm(a: i64, b: i64) -> i64 {
+    (a / gcd(a, b)) * b
+}
+
+pub fn gcd(a: i64, b: i64) -> i64 {
+    use std::cmp::{max, min};
+
+    let mut big = max(a, b);
+    let mut sml = min(a, b);
+
+    while sml > 0 {
+        let t = big;
+        big = sml;
+        sml = t % sml;
+    }
+
+    big
+}
+
+/// Signed integers the modular helpers below work with.
+pub trait Integer: Copy + Ord + Debug
+    + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+    + Div<Output = Self> + Rem<Output = Self> + Neg<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_mul(self, other: Self) -> Option<Self>;
+    fn rem_euclid(self, m: Self) -> Self;
+    /// a * b % m for a, b in [0, m), without overflowing.
+    fn mul_mod(self, other: Self, m: Self) -> Self;
+}
+
+impl Integer for i64 {
+    const ZERO: i64 = 0;
+    const ONE: i64 = 1;
+
+    fn checked_mul(self, other: i64) -> Option<i64> { i64::checked_mul(self, other) }
+    fn rem_euclid(self, m: i64) -> i64 { i64::rem_euclid(self, m) }
+
+    fn mul_mod(self, other: i64, m: i64) -> i64 {
+        (self as i128 * other as i128 % m as i128) as i64
+    }
+}
+
+impl Integer for i128 {
+    const ZERO: i128 = 0;
+    const ONE: i128 = 1;
+
+    fn checked_mul(self, other: i128) -> Option<i128> { i128::checked_mul(self, other) }
+    fn rem_euclid(self, m: i128) -> i128 { i128::rem_euclid(self, m) }
+
+    fn mul_mod(self, other: i128, m: i128) -> i128 {
+        // There is no wider type, so multiply by doubling.
+        let add_mod = |a: i128, b: i128| if a >= m - b { a - (m - b) } else { a + b };
+        let mut result = 0;
+        let mut a = self;
+        let mut b = other;
+        while b > 0 {
+            if b % 2 == 1 {
+                result = add_mod(result, a);
+            }
+            a = add_mod(a, a);
+            b /= 2;
+        }
+        result
+    }
+}
+
+/// (g, x, y) such that a * x + b * y = g = gcd(a, b), with g >= 0.
+pub fn extended_gcd<T: Integer>(a: T, b: T) -> (T, T, T) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (T::ONE, T::ZERO);
+    let (mut old_y, mut y) = (T::ZERO, T::ONE);
+
+    while r != T::ZERO {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+
+    if old_r < T::ZERO {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// x in [0, m) such that a * x = 1 (mod m), if a and m are coprime.
+pub fn mod_inverse<T: Integer>(a: T, m: T) -> Option<T> {
+    let (g, x, _) = extended_gcd(a.rem_euclid(m), m);
+    if g != T::ONE {
+        return None;
+    }
+    Some(x.rem_euclid(m))
+}
+
+/// Solves x = r (mod m) for all (r, m) in `congruences`, moduli don't have to
+/// be coprime.
+///
+/// Returns (x, lcm of the moduli) with x in [0, lcm), or None if the
+/// congruences contradict each other. Panics if the lcm doesn't fit in `T`.
+pub fn crt<T: Integer>(congruences: &[(T, T)]) -> Option<(T, T)> {
+    let mut result = (T::ZERO, T::ONE);
+
+    for &(r, m) in congruences {
+        if m <= T::ZERO {
+            panic!("Modulus has to be positive, got: {:?}", m);
+        }
+        let (r1, m1) = result;
+        let r2 = r.rem_euclid(m);
+
+        let (g, _, _) = extended_gcd(m1, m);
+        let diff = r2 - r1.rem_euclid(m);
+        if diff.rem_euclid(g) != T::ZERO {
+            return None;
+        }
+
+        // x = r1 + m1 * t, where m1 * t = diff (mod m), so t = diff / g * (m1 / g)^-1 (mod m / g).
+        let m_g = m / g;
+        let inverse = mod_inverse(m1 / g, m_g).unwrap();
+        let t = (diff / g).rem_euclid(m_g).mul_mod(inverse, m_g);
+        let lcm = m1.checked_mul(m_g)
+            .unwrap_or_else(|| panic!("lcm of {:?} and {:?} overflows", m1, m));
+
+        // r1 < m1 and t < m / g, so this stays below the lcm.
+        result = (r1 + m1 * t, lcm);
+    }
+
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fast_pow_modulo() {
+        let high_q = 1000000007;
+        let small_q = 1021;
+        assert_eq!(fast_pow_modulo(2, 1, high_q), 2);
+        assert_eq!(fast_pow_modulo(2, 10, high_q), 1024);
+        assert_eq!(fast_pow_modulo(5, 10, small_q), 781);
+    }
+
+    #[test]
+    fn test_lcm() {
+        assert_eq!(lcm(2, 5), 10);
+        assert_eq!(lcm(2, 2), 2);
+        assert_eq!(lcm(13, 2), 26);
+    }
+
+    #[test]
+    fn test_gcd() {
+        assert_eq!(gcd(2, 5), 1);
+        assert_eq!(gcd(2, 2), 2);
+        assert_eq!(gcd(13, 2), 1);
+        assert_eq!(gcd(42, 28), 14);
+        assert_eq!(gcd(13, 53), 1);
+    }
+
+    #[test]
+    fn test_extended_gcd() {
+        assert_eq!(extended_gcd(240i64, 46), (2, -9, 47));
+        assert_eq!(extended_gcd(0i64, 7), (7, 0, 1));
+        for a in -30i64..30 {
+            for b in -30i64..30 {
+                let (g, x, y) = extended_gcd(a, b);
+                assert_eq!(a * x + b * y, g);
+                assert_eq!(g, gcd(a.abs(), b.abs()));
+            }
+        }
+
+        let a: i128 = 1 << 100;
+        let b: i128 = (1 << 90) * 3 + 1;
+        let (g, x, y) = extended_gcd(a, b);
+        assert_eq!(g, 1);
+        assert_eq!((a % b).mul_mod(x.rem_euclid(b), b), 1);
+        assert_eq!(b.mul_mod(y.rem_euclid(a), a), 1);
+    }
+
+    #[test]
+    fn test_mod_inverse() {
+        assert_eq!(mod_inverse(3i64, 11), Some(4));
+        assert_eq!(mod_inverse(-3i64, 11), Some(7));
+        assert_eq!(mod_inverse(6i64, 9), None);
+
+        let p: i64 = (1 << 61) - 1;
+        let inverse = mod_inverse(123456789, p).unwrap();
+        assert_eq!(123456789.mul_mod(inverse, p), 1);
+
+        let p: i128 = i128::MAX;
+        let inverse = mod_inverse(p - 2, p).unwrap();
+        assert_eq!((p - 2).mul_mod(inverse, p), 1);
+    }
+
+    #[test]
+    fn test_mul_mod() {
+        let m: i128 = i128::MAX;
+        assert_eq!((m - 1).mul_mod(m - 1, m), 1);
+        assert_eq!((m - 1).mul_mod(2, m), m - 2);
+        assert_eq!(i64::MAX.mul_mod(i64::MAX - 1, i64::MAX), 0);
+        assert_eq!((i64::MAX - 1).mul_mod(i64::MAX - 1, i64::MAX), 1);
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt(&[(2i64, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[(1i64, 4), (3, 6)]), Some((9, 12)));
+        assert_eq!(crt(&[(1i64, 4), (2, 6)]), None);
+        assert_eq!(crt::<i64>(&[]), Some((0, 1)));
+        assert_eq!(crt(&[(-1i64, 10)]), Some((9, 10)));
+
+        for m1 in 1i64..12 {
+            for m2 in 1i64..12 {
+                for r1 in 0..m1 {
+                    for r2 in 0..m2 {
+                        let lcm = lcm(m1, m2);
+                        let brute = (0..lcm).find(|x| x % m1 == r1 && x % m2 == r2);
+                        assert_eq!(crt(&[(r1, m1), (r2, m2)]), brute.map(|x| (x, lcm)));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_crt_large() {
+        let p: i64 = (1 << 61) - 1;
+        let (x, m) = crt(&[(p - 1, p), (2, 3)]).unwrap();
+        assert_eq!(m, p * 3);
+        assert_eq!(x % p, p - 1);
+        assert_eq!(x % 3, 2);
+
+        let p = p as i128;
+        let q: i128 = 1_000_000_007;
+        let (x, m) = crt(&[(p - 1, p), (5, q), (3, (1 << 20) * 3), (3, 1 << 10)]).unwrap();
+        assert_eq!(m, p * q * (1 << 20) * 3);
+        assert_eq!(x % p, p - 1);
+        assert_eq!(x % q, 5);
+        assert_eq!(x % ((1 << 20) * 3), 3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_crt_overflow() {
+        crt(&[(0i64, (1 << 61) - 1), (0, 1_000_000_007)]);
+    }
+}
@@ -0,0 +1,183 @@
+//! Tests generated from sample files, for use in a day's `build.rs`.
+//!
+//! Every `examples/<module>.<case>.in` with a matching `<module>.<case>.out`
+//! becomes a test `example_<case>` in `module`, which runs `solve` on the input
+//! exactly as it is in the file and compares with the output, ignoring trailing
+//! whitespace. Outputs named `<module>.<case>.part1.out` and `.part2.out` are
+//! answers of the day's `Puzzle` instead, checked by `example_<case>_part1` and
+//! `example_<case>_part2`. The module opts in with, in its `mod tests`:
+//!
+//! ```text
+//! include!(concat!(env!("OUT_DIR"), "/examples/crucible.rs"));
+//! ```
+//!
+//! and the day with a `build.rs` calling `aoc::examples::generate_tests()`.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The extensions of outputs before `out`, the suffixes of their tests and
+/// what they run.
+const SOLVES: [(&str, &str, &str); 3] = [
+    ("", "", "solve"),
+    ("part1.", "_part1", "aoc::solution::solve_part1::<crate::Puzzle, _, _>"),
+    ("part2.", "_part2", "aoc::solution::solve_part2::<crate::Puzzle, _, _>"),
+];
+
+/// Writes the tests of the crate being built. Panics on errors, as build
+/// scripts do.
+pub fn generate_tests() {
+    let manifest_dir = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap());
+    let out_dir = PathBuf::from(std::env::var("OUT_DIR").unwrap());
+    if let Err(e) = generate(&manifest_dir, &out_dir) {
+        panic!("Cannot generate the example tests: {}", e);
+    }
+
+    // Without any of these cargo reruns the script when anything in the crate
+    // changes, which is what we want until there is an `examples` directory.
+    if manifest_dir.join("examples").is_dir() {
+        println!("cargo:rerun-if-changed=examples");
+        println!("cargo:rerun-if-changed=src");
+        println!("cargo:rerun-if-changed=build.rs");
+    }
+}
+
+/// Writes `out_dir/examples/<module>.rs` for every module in `manifest_dir/src`.
+pub fn generate(manifest_dir: &Path, out_dir: &Path) -> Result<(), String> {
+    let mut tests: BTreeMap<String, String> = BTreeMap::new();
+    for path in files(&manifest_dir.join("src"))? {
+        let module = path.file_stem().unwrap().to_string_lossy().to_string();
+        if path.extension().is_some_and(|e| e == "rs") && module != "lib" && module != "main" {
+            tests.insert(module, String::new());
+        }
+    }
+
+    let examples = manifest_dir.join("examples");
+    let examples = if examples.is_dir() { files(&examples)? } else { vec![] };
+    for input in examples.iter().filter(|p| p.extension().is_some_and(|e| e == "in")) {
+        let name = input.file_stem().unwrap().to_string_lossy().to_string();
+        let (module, case) = name.split_once('.')
+            .ok_or(format!("{} is not named <module>.<case>.in", input.display()))?;
+        let module_tests = tests.get_mut(module)
+            .ok_or(format!("{}: there is no module {}", input.display(), module))?;
+        let mut outputs = 0;
+        for (suffix, test_suffix, solve) in SOLVES {
+            let output = input.with_extension(format!("{}out", suffix));
+            if !output.is_file() {
+                continue;
+            }
+            outputs += 1;
+            write!(module_tests, "
+#[test]
+fn example_{}{}() {{
+    aoc::testing::test_example(|i, o| {}(i, o), include_str!({:?}), include_str!({:?}));
+}}
+", identifier(case), test_suffix, solve, input, output).unwrap();
+        }
+        if outputs == 0 {
+            return Err(format!("{} has no {}", input.display(), input.with_extension("out").display()));
+        }
+    }
+    for output in examples.iter().filter(|p| p.extension().is_some_and(|e| e == "out")) {
+        let name = output.file_name().unwrap().to_string_lossy();
+        let stem = SOLVES.iter()
+            .filter_map(|(suffix, _, _)| name.strip_suffix(&format!(".{}out", suffix)))
+            .min_by_key(|stem| stem.len())
+            .unwrap();
+        let input = output.with_file_name(format!("{}.in", stem));
+        if !input.is_file() {
+            return Err(format!("{} has no {}", output.display(), input.display()));
+        }
+    }
+
+    let dir = out_dir.join("examples");
+    fs::create_dir_all(&dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+    for (module, module_tests) in tests {
+        let path = dir.join(format!("{}.rs", module));
+        fs::write(&path, module_tests).map_err(|e| format!("{}: {}", path.display(), e))?;
+    }
+    Ok(())
+}
+
+fn files(dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let mut files = fs::read_dir(dir)
+        .and_then(|entries| entries.map(|e| e.map(|e| e.path())).collect::<std::io::Result<Vec<_>>>())
+        .map_err(|e| format!("{}: {}", dir.display(), e))?;
+    files.sort();
+    Ok(files)
+}
+
+/// `case` with everything that can't be in a function name replaced by `_`.
+fn identifier(case: &str) -> String {
+    case.chars().map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '_' }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_day(name: &str, files: &[(&str, &str)]) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc_examples_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        for (path, contents) in files {
+            let path = dir.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn test_generate() {
+        let day = temp_day("generate", &[
+            ("src/lib.rs", ""),
+            ("src/main.rs", ""),
+            ("src/crucible.rs", ""),
+            ("src/crucible2.rs", ""),
+            ("examples/crucible.sample.in", "2413\n"),
+            ("examples/crucible.sample.out", "102\n"),
+            ("examples/crucible.Long-Path.in", "1111\n"),
+            ("examples/crucible.Long-Path.out", "71\n"),
+            ("examples/crucible2.sample.in", "2413\n"),
+            ("examples/crucible2.sample.part1.out", "102\n"),
+            ("examples/crucible2.sample.part2.out", "94\n"),
+        ]);
+        generate(&day, &day.join("out")).unwrap();
+
+        let crucible = fs::read_to_string(day.join("out/examples/crucible.rs")).unwrap();
+        assert!(crucible.contains("fn example_sample()"));
+        assert!(crucible.contains("fn example_long_path()"));
+        assert!(crucible.contains("crucible.sample.in\"), include_str!("));
+        assert!(!crucible.contains("_part"));
+        let crucible2 = fs::read_to_string(day.join("out/examples/crucible2.rs")).unwrap();
+        assert!(crucible2.contains("fn example_sample_part1()"));
+        assert!(crucible2.contains("fn example_sample_part2()"));
+        assert!(crucible2.contains("solve_part2::<crate::Puzzle, _, _>(i, o), include_str!("));
+        assert!(crucible2.contains("crucible2.sample.part2.out\"))"));
+        assert!(!crucible2.contains("fn example_sample()"));
+        assert!(!day.join("out/examples/lib.rs").exists());
+        fs::remove_dir_all(&day).unwrap();
+    }
+
+    #[test]
+    fn test_generate_errors() {
+        let error = |name: &str, files: &[(&str, &str)]| {
+            let day = temp_day(name, files);
+            let error = generate(&day, &day.join("out")).unwrap_err();
+            fs::remove_dir_all(&day).unwrap();
+            error
+        };
+        assert!(error("no_out", &[("src/walk.rs", ""), ("examples/walk.a.in", "")])
+                .ends_with("walk.a.out"));
+        assert!(error("no_in", &[("src/walk.rs", ""), ("examples/walk.a.out", "")])
+                .ends_with("walk.a.in"));
+        assert!(error("no_in_part", &[("src/walk.rs", ""), ("examples/walk.a.part2.out", "")])
+                .ends_with("/examples/walk.a.in"));
+        assert!(error("no_case", &[("src/walk.rs", ""), ("examples/walk.in", ""), ("examples/walk.out", "")])
+                .ends_with("is not named <module>.<case>.in"));
+        assert!(error("no_module", &[("src/walk.rs", ""), ("examples/run.a.in", ""), ("examples/run.a.out", "")])
+                .ends_with("there is no module run"));
+    }
+}
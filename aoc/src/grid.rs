@@ -0,0 +1,266 @@
+//! Rectangular grid of nodes, addressed with `XY` (x is the column, y the row).
+
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::XY;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    nodes: Vec<Vec<T>>,
+}
+
+impl<T> Grid<T> {
+    /// Panics if the rows are not all of the same length.
+    pub fn new(nodes: Vec<Vec<T>>) -> Grid<T> {
+        if let Some(first) = nodes.first() {
+            if nodes.iter().any(|row| row.len() != first.len()) {
+                panic!("Rows of a grid must have equal lengths");
+            }
+        }
+        Grid { nodes }
+    }
+
+    /// Parses one row per line, ignoring surrounding whitespace and empty lines.
+    pub fn from_lines<I, S, F>(lines: I, mut from_char: F) -> Grid<T>
+        where I: IntoIterator<Item = S>,
+              S: AsRef<str>,
+              F: FnMut(char) -> T,
+    {
+        let nodes = lines.into_iter()
+            .map(|l| l.as_ref().trim().chars().map(&mut from_char).collect::<Vec<_>>())
+            .filter(|row| !row.is_empty())
+            .collect();
+        Grid::new(nodes)
+    }
+
+    pub fn from_fn<F>(width: usize, height: usize, mut f: F) -> Grid<T>
+        where F: FnMut(XY) -> T
+    {
+        let nodes = (0..height)
+            .map(|y| (0..width).map(|x| f(XY::newu(x, y))).collect())
+            .collect();
+        Grid { nodes }
+    }
+
+    pub fn width(&self) -> usize { self.nodes.first().map_or(0, |row| row.len()) }
+    pub fn height(&self) -> usize { self.nodes.len() }
+
+    pub fn is_valid(&self, pos: &XY) -> bool {
+        pos.x >= 0 && pos.y >= 0 &&
+                pos.x < self.width() as i64 && pos.y < self.height() as i64
+    }
+
+    pub fn get(&self, pos: &XY) -> Option<&T> {
+        if !self.is_valid(pos) {
+            return None;
+        }
+        Some(&self.nodes[pos.uy()][pos.ux()])
+    }
+
+    pub fn get_mut(&mut self, pos: &XY) -> Option<&mut T> {
+        if !self.is_valid(pos) {
+            return None;
+        }
+        Some(&mut self.nodes[pos.uy()][pos.ux()])
+    }
+
+    /// Treats the grid as repeating infinitely in every direction.
+    pub fn wrap(&self, pos: &XY) -> XY {
+        XY::new(pos.x.rem_euclid(self.width() as i64), pos.y.rem_euclid(self.height() as i64))
+    }
+
+    pub fn wrapping_at(&self, pos: &XY) -> &T {
+        &self[self.wrap(pos)]
+    }
+
+    /// Valid positions up, right, down and left of `pos`.
+    pub fn neighbours(&self, pos: &XY) -> Vec<XY> {
+        XY::AROUND.iter()
+            .map(|diff| pos.add(diff))
+            .filter(|n| self.is_valid(n))
+            .collect()
+    }
+
+    /// Like `neighbours`, but includes the diagonals.
+    pub fn neighbours8(&self, pos: &XY) -> Vec<XY> {
+        let mut neighs = Vec::new();
+        for dy in -1..=1 {
+            for dx in -1..=1 {
+                let n = pos.add(&XY::new(dx, dy));
+                if n != *pos && self.is_valid(&n) {
+                    neighs.push(n);
+                }
+            }
+        }
+        neighs
+    }
+
+    /// All positions, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = XY> {
+        let width = self.width();
+        (0..self.height()).flat_map(move |y| (0..width).map(move |x| XY::newu(x, y)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (XY, &T)> {
+        self.positions().zip(self.nodes.iter().flatten())
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (XY, &mut T)> {
+        self.positions().zip(self.nodes.iter_mut().flatten())
+    }
+
+    pub fn find<P>(&self, mut predicate: P) -> Option<XY>
+        where P: FnMut(&T) -> bool
+    {
+        self.iter().find(|(_, node)| predicate(node)).map(|(pos, _)| pos)
+    }
+
+    pub fn row(&self, y: usize) -> &[T] { &self.nodes[y] }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.nodes.iter().map(|row| row.as_slice())
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        self.nodes.iter().map(move |row| &row[x])
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width()).map(|x| self.column(x))
+    }
+
+    pub fn map<U, F>(&self, mut f: F) -> Grid<U>
+        where F: FnMut(&T) -> U
+    {
+        Grid { nodes: self.nodes.iter().map(|row| row.iter().map(&mut f).collect()).collect() }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn filled(width: usize, height: usize, value: T) -> Grid<T> {
+        Grid { nodes: vec![vec![value; width]; height] }
+    }
+
+    /// Mirrors the grid along the main diagonal, so rows become columns.
+    pub fn transpose(&self) -> Grid<T> {
+        Grid { nodes: self.columns().map(|column| column.cloned().collect()).collect() }
+    }
+
+    pub fn rotate_clockwise(&self) -> Grid<T> {
+        let mut rotated = self.transpose();
+        for row in rotated.nodes.iter_mut() {
+            row.reverse();
+        }
+        rotated
+    }
+
+    pub fn rotate_counterclockwise(&self) -> Grid<T> {
+        let mut rotated = self.transpose();
+        rotated.nodes.reverse();
+        rotated
+    }
+}
+
+impl<T> Index<XY> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: XY) -> &T {
+        match self.get(&pos) {
+            Some(node) => node,
+            None => panic!("Getting node out of bounds: {:?}", pos),
+        }
+    }
+}
+
+impl<T> IndexMut<XY> for Grid<T> {
+    fn index_mut(&mut self, pos: XY) -> &mut T {
+        if !self.is_valid(&pos) {
+            panic!("Getting node out of bounds: {:?}", pos);
+        }
+        &mut self.nodes[pos.uy()][pos.ux()]
+    }
+}
+
+/// One row per line, with the nodes printed next to each other.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in &self.nodes {
+            for node in row {
+                write!(f, "{}", node)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Grid<char> {
+        Grid::from_lines(
+            "abc
+             def".lines(),
+            |c| c,
+        )
+    }
+
+    #[test]
+    fn test_parse_and_access() {
+        let grid = sample();
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 2);
+        assert_eq!(grid[XY::new(2, 1)], 'f');
+        assert_eq!(grid.get(&XY::new(3, 0)), None);
+        assert_eq!(grid.get(&XY::new(0, -1)), None);
+        assert_eq!(*grid.wrapping_at(&XY::new(-1, 2)), 'c');
+        assert_eq!(*grid.wrapping_at(&XY::new(7, -3)), 'e');
+        assert_eq!(grid.find(|c| *c == 'e'), Some(XY::new(1, 1)));
+
+        let digits = Grid::from_lines(["12", "34"], |c| c.to_digit(10).unwrap());
+        assert_eq!(digits[XY::new(1, 1)], 4);
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = sample();
+        assert_eq!(grid.neighbours(&XY::new(0, 0)), vec![XY::new(1, 0), XY::new(0, 1)]);
+        assert_eq!(grid.neighbours(&XY::new(1, 1)).len(), 3);
+        assert_eq!(grid.neighbours8(&XY::new(1, 0)).len(), 5);
+        assert_eq!(grid.neighbours8(&XY::new(2, 1)),
+            vec![XY::new(1, 0), XY::new(2, 0), XY::new(1, 1)]);
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = sample();
+        assert_eq!(grid.rows().collect::<Vec<_>>(), vec![&['a', 'b', 'c'], &['d', 'e', 'f']]);
+        let columns: Vec<String> = grid.columns().map(|c| c.collect()).collect();
+        assert_eq!(columns, vec!["ad", "be", "cf"]);
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(grid.transpose().transpose(), grid);
+    }
+
+    #[test]
+    fn test_rotations() {
+        let grid = sample();
+        assert_eq!(grid.rotate_clockwise().to_string(), "da\neb\nfc\n");
+        assert_eq!(grid.rotate_counterclockwise().to_string(), "cf\nbe\nad\n");
+        let mut full_turn = grid.clone();
+        for _ in 0..4 {
+            full_turn = full_turn.rotate_clockwise();
+        }
+        assert_eq!(full_turn, grid);
+        assert_eq!(grid.rotate_clockwise().rotate_counterclockwise(), grid);
+    }
+
+    #[test]
+    fn test_display() {
+        let grid = Grid::from_fn(2, 2, |pos| pos.x + 2 * pos.y);
+        assert_eq!(grid.to_string(), "01\n23\n");
+        assert_eq!(Grid::filled(3, 1, '.').to_string(), "...\n");
+        assert_eq!(grid.map(|n| n * 2).to_string(), "02\n46\n");
+    }
+}
@@ -0,0 +1,371 @@
+//! Sets of numbers kept as sorted, disjoint intervals, and boxes of intervals
+//! in any number of dimensions.
+//!
+//! Intervals are half-open, `start..end`, like Rust ranges: `[3, 5)` holds 3
+//! and 4, and `[3, 3)` is empty.
+
+use std::cmp::{max, min};
+use std::iter::{Product, Sum};
+use std::ops::{Add, Sub};
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash, PartialOrd, Ord)]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T: Copy + Ord> Interval<T> {
+    pub fn new(start: T, end: T) -> Interval<T> {
+        Interval { start, end }
+    }
+
+    pub fn is_empty(&self) -> bool { self.end <= self.start }
+
+    pub fn contains(&self, x: T) -> bool { self.start <= x && x < self.end }
+
+    /// Whether every value of `other` is in this one, which holds for an
+    /// empty `other`.
+    pub fn contains_interval(&self, other: &Interval<T>) -> bool {
+        other.is_empty() || (self.start <= other.start && other.end <= self.end)
+    }
+
+    /// The values in both, possibly none.
+    pub fn intersection(&self, other: &Interval<T>) -> Interval<T> {
+        Interval::new(max(self.start, other.start), min(self.end, other.end))
+    }
+
+    pub fn range(&self) -> std::ops::Range<T> { self.start..self.end }
+}
+
+impl<T: Copy + Ord + Sub<Output = T> + Default> Interval<T> {
+    /// The number of values, zero (`T::default()`) for an empty interval.
+    pub fn len(&self) -> T {
+        if self.is_empty() { T::default() } else { self.end - self.start }
+    }
+}
+
+impl<T: Copy + Add<Output = T>> Interval<T> {
+    pub fn shifted(&self, by: T) -> Interval<T> {
+        Interval { start: self.start + by, end: self.end + by }
+    }
+}
+
+/// Any set of values as the fewest intervals covering it.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct IntervalSet<T> {
+    /// Sorted, none of them empty, and none touching or overlapping another.
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> IntervalSet<T> {
+        IntervalSet { intervals: Vec::new() }
+    }
+}
+
+impl<T: Copy + Ord> IntervalSet<T> {
+    pub fn new() -> IntervalSet<T> { IntervalSet::default() }
+
+    pub fn intervals(&self) -> &[Interval<T>] { &self.intervals }
+
+    pub fn is_empty(&self) -> bool { self.intervals.is_empty() }
+
+    pub fn min(&self) -> Option<T> { self.intervals.first().map(|i| i.start) }
+
+    /// The interval of the set holding `x`.
+    fn position(&self, x: T) -> Option<usize> {
+        let idx = self.intervals.partition_point(|i| i.end <= x);
+        (idx < self.intervals.len() && self.intervals[idx].contains(x)).then_some(idx)
+    }
+
+    pub fn contains(&self, x: T) -> bool { self.position(x).is_some() }
+
+    pub fn contains_interval(&self, interval: &Interval<T>) -> bool {
+        interval.is_empty() || self.position(interval.start)
+            .is_some_and(|idx| self.intervals[idx].contains_interval(interval))
+    }
+
+    pub fn insert(&mut self, interval: Interval<T>) {
+        *self = self.union(&IntervalSet::from(interval));
+    }
+
+    pub fn remove(&mut self, interval: Interval<T>) {
+        *self = self.difference(&IntervalSet::from(interval));
+    }
+
+    pub fn union(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        self.intervals.iter().chain(&other.intervals).copied().collect()
+    }
+
+    pub fn intersection(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut intervals = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < self.intervals.len() && j < other.intervals.len() {
+            let common = self.intervals[i].intersection(&other.intervals[j]);
+            if !common.is_empty() {
+                intervals.push(common);
+            }
+            // Whichever ends first can't meet anything further in the other.
+            if self.intervals[i].end < other.intervals[j].end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        IntervalSet { intervals }
+    }
+
+    pub fn difference(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut intervals = Vec::new();
+        let mut j = 0;
+        for interval in &self.intervals {
+            let mut start = interval.start;
+            while j < other.intervals.len() && other.intervals[j].end <= start {
+                j += 1;
+            }
+            // Cut out every interval of `other` starting before this one ends,
+            // the last of them may still cut into the next one.
+            let mut k = j;
+            while k < other.intervals.len() && other.intervals[k].start < interval.end {
+                if start < other.intervals[k].start {
+                    intervals.push(Interval::new(start, other.intervals[k].start));
+                }
+                start = max(start, other.intervals[k].end);
+                k += 1;
+            }
+            if start < interval.end {
+                intervals.push(Interval::new(start, interval.end));
+            }
+        }
+        IntervalSet { intervals }
+    }
+}
+
+impl<T: Copy + Ord + Sub<Output = T> + Default + Sum> IntervalSet<T> {
+    /// The number of values in the set.
+    pub fn total_len(&self) -> T {
+        self.intervals.iter().map(|i| i.len()).sum()
+    }
+}
+
+impl<T: Copy + Ord> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> IntervalSet<T> {
+        let mut sorted: Vec<_> = iter.into_iter().filter(|i| !i.is_empty()).collect();
+        sorted.sort();
+
+        let mut intervals: Vec<Interval<T>> = Vec::new();
+        for interval in sorted {
+            match intervals.last_mut() {
+                Some(last) if last.end >= interval.start => last.end = max(last.end, interval.end),
+                _ => intervals.push(interval),
+            }
+        }
+        IntervalSet { intervals }
+    }
+}
+
+impl<T: Copy + Ord> From<Interval<T>> for IntervalSet<T> {
+    fn from(interval: Interval<T>) -> IntervalSet<T> {
+        IntervalSet::from_iter([interval])
+    }
+}
+
+/// The points whose every coordinate is in the interval of its dimension.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct IntervalBox<T> {
+    pub dims: Vec<Interval<T>>,
+}
+
+impl<T: Copy + Ord> IntervalBox<T> {
+    pub fn new(dims: Vec<Interval<T>>) -> IntervalBox<T> {
+        IntervalBox { dims }
+    }
+
+    pub fn is_empty(&self) -> bool { self.dims.iter().any(|d| d.is_empty()) }
+
+    pub fn contains(&self, point: &[T]) -> bool {
+        self.dims.len() == point.len() && self.dims.iter().zip(point).all(|(d, x)| d.contains(*x))
+    }
+
+    /// Whether every point of `other` is in this one, which holds for an
+    /// empty `other`. Both must have the same dimensions.
+    pub fn contains_box(&self, other: &IntervalBox<T>) -> bool {
+        assert_eq!(self.dims.len(), other.dims.len(), "Comparing boxes of different dimensions");
+        other.is_empty() || self.dims.iter().zip(&other.dims).all(|(a, b)| a.contains_interval(b))
+    }
+
+    /// The points of this box not in `other`, as disjoint boxes: slices of
+    /// this one below and above `other` in each dimension in turn. Both must
+    /// have the same dimensions.
+    pub fn difference(&self, other: &IntervalBox<T>) -> Vec<IntervalBox<T>> {
+        assert_eq!(self.dims.len(), other.dims.len(), "Subtracting boxes of different dimensions");
+        if self.is_empty() {
+            return Vec::new();
+        }
+        if other.is_empty() {
+            return vec![self.clone()];
+        }
+        let mut pieces = Vec::new();
+        let mut rest = self.clone();
+        for i in 0..self.dims.len() {
+            let (dim, cut) = (rest.dims[i], other.dims[i]);
+            let below = Interval::new(dim.start, min(dim.end, cut.start));
+            let above = Interval::new(max(dim.start, cut.end), dim.end);
+            for slice in [below, above] {
+                if !slice.is_empty() {
+                    let mut piece = rest.clone();
+                    piece.dims[i] = slice;
+                    pieces.push(piece);
+                }
+            }
+            rest.dims[i] = dim.intersection(&cut);
+            if rest.is_empty() {
+                break;
+            }
+        }
+        pieces
+    }
+
+    /// The box of points in both, which must have the same dimensions.
+    pub fn intersection(&self, other: &IntervalBox<T>) -> IntervalBox<T> {
+        assert_eq!(self.dims.len(), other.dims.len(), "Intersecting boxes of different dimensions");
+        IntervalBox {
+            dims: self.dims.iter().zip(&other.dims).map(|(a, b)| a.intersection(b)).collect(),
+        }
+    }
+}
+
+impl<T: Copy + Ord + Sub<Output = T> + Default + Product> IntervalBox<T> {
+    /// The number of points in the box.
+    pub fn volume(&self) -> T {
+        self.dims.iter().map(|d| d.len()).product()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    /// Values the random sets are made of, with some room around them.
+    const UNIVERSE: std::ops::Range<i64> = -3..43;
+
+    fn random_interval(rng: &mut StdRng) -> Interval<i64> {
+        // Empty and reversed intervals included.
+        Interval::new(rng.gen_range(0..40), rng.gen_range(0..40))
+    }
+
+    fn random_set(rng: &mut StdRng) -> (IntervalSet<i64>, BTreeSet<i64>) {
+        let intervals: Vec<_> = (0..rng.gen_range(0..6)).map(|_| random_interval(rng)).collect();
+        let brute = intervals.iter().flat_map(|i| i.range()).collect();
+        (intervals.into_iter().collect(), brute)
+    }
+
+    fn assert_same(set: &IntervalSet<i64>, brute: &BTreeSet<i64>) {
+        for pair in set.intervals.windows(2) {
+            assert!(pair[0].end < pair[1].start, "Not normalized: {:?}", set);
+        }
+        assert!(set.intervals.iter().all(|i| !i.is_empty()), "Not normalized: {:?}", set);
+        for x in UNIVERSE {
+            assert_eq!(set.contains(x), brute.contains(&x), "{} in {:?}", x, set);
+        }
+        assert_eq!(set.total_len(), brute.len() as i64);
+        assert_eq!(set.min(), brute.first().copied());
+        assert_eq!(set.is_empty(), brute.is_empty());
+    }
+
+    #[test]
+    fn test_interval() {
+        let interval = Interval::new(3, 7);
+        assert_eq!(interval.len(), 4);
+        assert!(interval.contains(3) && !interval.contains(7));
+        assert_eq!(interval.intersection(&Interval::new(5, 10)), Interval::new(5, 7));
+        assert!(interval.intersection(&Interval::new(7, 10)).is_empty());
+        assert_eq!(Interval::new(7, 3).len(), 0);
+        assert!(interval.contains_interval(&Interval::new(4, 7)));
+        assert!(interval.contains_interval(&Interval::new(9, 9)));
+        assert!(!interval.contains_interval(&Interval::new(2, 5)));
+        assert_eq!(interval.shifted(-3), Interval::new(0, 4));
+    }
+
+    #[test]
+    fn test_interval_set() {
+        let mut set: IntervalSet<i64> = [Interval::new(5, 8), Interval::new(1, 3), Interval::new(3, 4)]
+            .into_iter().collect();
+        assert_eq!(set.intervals(), &[Interval::new(1, 4), Interval::new(5, 8)]);
+        set.remove(Interval::new(2, 6));
+        assert_eq!(set.intervals(), &[Interval::new(1, 2), Interval::new(6, 8)]);
+        set.insert(Interval::new(2, 6));
+        assert_eq!(set.intervals(), &[Interval::new(1, 8)]);
+        assert_eq!(set.total_len(), 7);
+    }
+
+    #[test]
+    fn test_interval_set_against_brute_force() {
+        let mut rng = StdRng::seed_from_u64(2023);
+        for _ in 0..2000 {
+            let (a, brute_a) = random_set(&mut rng);
+            let (b, brute_b) = random_set(&mut rng);
+            assert_same(&a, &brute_a);
+            assert_same(&a.union(&b), &brute_a.union(&brute_b).copied().collect());
+            assert_same(&a.intersection(&b), &brute_a.intersection(&brute_b).copied().collect());
+            assert_same(&a.difference(&b), &brute_a.difference(&brute_b).copied().collect());
+
+            let interval = random_interval(&mut rng);
+            let values: BTreeSet<i64> = interval.range().collect();
+            assert_eq!(a.contains_interval(&interval), values.is_subset(&brute_a), "{:?} in {:?}", interval, a);
+
+            let mut inserted = a.clone();
+            inserted.insert(interval);
+            assert_same(&inserted, &brute_a.union(&values).copied().collect());
+            let mut removed = a.clone();
+            removed.remove(interval);
+            assert_same(&removed, &brute_a.difference(&values).copied().collect());
+        }
+    }
+
+    #[test]
+    fn test_interval_box_against_brute_force() {
+        let mut rng = StdRng::seed_from_u64(2024);
+        let points = |b: &IntervalBox<i64>| -> BTreeSet<Vec<i64>> {
+            b.dims.iter().fold(vec![vec![]], |prefixes, d| {
+                prefixes.iter()
+                    .flat_map(|p| d.range().map(move |x| [p.clone(), vec![x]].concat()))
+                    .collect()
+            }).into_iter().collect()
+        };
+        for _ in 0..300 {
+            let dims = rng.gen_range(1..4);
+            let a = IntervalBox::new((0..dims).map(|_| random_interval(&mut rng)).collect());
+            let b = IntervalBox::new((0..dims).map(|_| random_interval(&mut rng)).collect());
+            let (points_a, points_b) = (points(&a), points(&b));
+
+            assert_eq!(a.volume(), points_a.len() as i64);
+            assert_eq!(a.is_empty(), points_a.is_empty());
+            let common = a.intersection(&b);
+            assert_eq!(points(&common), points_a.intersection(&points_b).cloned().collect());
+            assert_eq!(a.contains_box(&b), points_b.is_subset(&points_a), "{:?} in {:?}", b, a);
+
+            let pieces = a.difference(&b);
+            let covered: BTreeSet<Vec<i64>> = pieces.iter().flat_map(&points).collect();
+            assert_eq!(covered, points_a.difference(&points_b).cloned().collect());
+            assert_eq!(pieces.iter().map(|p| p.volume()).sum::<i64>(), covered.len() as i64, "Overlapping {:?}", pieces);
+            assert_eq!(common.volume(), points(&common).len() as i64);
+            for point in points_a.iter().chain(&points_b) {
+                assert_eq!(a.contains(point), points_a.contains(point));
+            }
+        }
+        assert_eq!(IntervalBox::<i64>::new(vec![]).volume(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Subtracting boxes of different dimensions")]
+    fn test_interval_box_difference_dimensions() {
+        let flat = IntervalBox::new(vec![Interval::new(0, 5)]);
+        let square = IntervalBox::new(vec![Interval::new(0, 5), Interval::new(0, 5)]);
+        // Else the second dimension of the square would be ignored.
+        flat.difference(&square);
+    }
+}
@@ -0,0 +1,16 @@
+//! Code shared between the days.
+
+pub mod biblioteczka;
+pub mod examples;
+pub mod grid;
+pub mod intervals;
+pub mod parse;
+pub mod scanner;
+pub mod shortest_path;
+pub mod solution;
+pub mod testing;
+pub mod trace;
+pub mod xy;
+
+pub use grid::Grid;
+pub use xy::{Direction, XY};
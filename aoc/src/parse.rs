@@ -0,0 +1,555 @@
+//! Reading the input line by line, with errors pointing at the offending spot.
+//!
+//! A typical `solve` goes through the input with `Lines`, and parses each
+//! `Line` with a `Parser`:
+//!
+//! ```
+//! use aoc::parse::{Lines, Result};
+//!
+//! fn sum(input: &[u8]) -> Result<i64> {
+//!     let mut sum = 0;
+//!     for line in Lines::new(input, 1) {
+//!         let line = line?;
+//!         let mut parser = line.parser();
+//!         parser.expect("add")?;
+//!         sum += parser.number::<i64>()?;
+//!         parser.end()?;
+//!     }
+//!     Ok(sum)
+//! }
+//!
+//! assert_eq!(sum(b"add 1\nadd 2").unwrap(), 3);
+//! assert_eq!(sum(b"add 1\nsub 2").unwrap_err().to_string(),
+//!            "day 1, line 2, column 1: expected `add`, found `sub`");
+//! ```
+
+use std::fmt;
+use std::io::{self, BufRead};
+use std::str::FromStr;
+
+use crate::Grid;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: u32,
+    /// Counted from 1.
+    pub line: usize,
+    /// Counted from 1, in characters.
+    pub column: usize,
+    pub expected: String,
+    pub found: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "day {}, line {}, column {}: expected {}, found {}",
+               self.day, self.line, self.column, self.expected, self.found)
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    Parse(ParseError),
+    /// The input parsed, but it doesn't describe a puzzle, e.g. has no start.
+    Invalid { day: u32, message: String },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Io(e) => write!(f, "{}", e),
+            Error::Parse(e) => write!(f, "{}", e),
+            Error::Invalid { day, message } => write!(f, "day {}: {}", day, message),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Error { Error::Io(e) }
+}
+
+impl From<ParseError> for Error {
+    fn from(e: ParseError) -> Error { Error::Parse(e) }
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Line {
+    pub day: u32,
+    /// Counted from 1.
+    pub number: usize,
+    pub text: String,
+}
+
+impl Line {
+    pub fn parser(&self) -> Parser<'_> {
+        Parser { line: self, rest: &self.text }
+    }
+
+    pub fn is_blank(&self) -> bool {
+        self.text.trim().is_empty()
+    }
+
+    pub fn error(&self, column: usize, expected: &str, found: &str) -> Error {
+        Error::Parse(ParseError {
+            day: self.day,
+            line: self.number,
+            column,
+            expected: expected.to_string(),
+            found: found.to_string(),
+        })
+    }
+
+    /// Maps every character, ignoring surrounding whitespace, with `f`.
+    /// `expected` describes the characters `f` accepts.
+    pub fn row<T, F>(&self, mut f: F, expected: &str) -> Result<Vec<T>>
+        where F: FnMut(char) -> Option<T>
+    {
+        let mut parser = self.parser();
+        parser.skip_whitespace();
+        let mut row = Vec::new();
+        while let Some(c) = parser.peek().filter(|c| !c.is_whitespace()) {
+            row.push(f(c).ok_or_else(|| parser.error(expected))?);
+            parser.advance(c.len_utf8());
+        }
+        parser.end()?;
+        Ok(row)
+    }
+}
+
+/// Lines of one day's input.
+pub struct Lines<R> {
+    day: u32,
+    lines: io::Lines<R>,
+    number: usize,
+    peeked: Option<Line>,
+}
+
+impl<R: BufRead> Lines<R> {
+    pub fn new(input: R, day: u32) -> Lines<R> {
+        Lines { day, lines: input.lines(), number: 0, peeked: None }
+    }
+
+    pub fn day(&self) -> u32 { self.day }
+
+    fn read(&mut self) -> Result<Option<Line>> {
+        if let Some(line) = self.peeked.take() {
+            return Ok(Some(line));
+        }
+        match self.lines.next() {
+            None => Ok(None),
+            Some(text) => {
+                self.number += 1;
+                Ok(Some(Line { day: self.day, number: self.number, text: text? }))
+            },
+        }
+    }
+
+    pub fn peek(&mut self) -> Result<Option<&Line>> {
+        if self.peeked.is_none() {
+            self.peeked = self.read()?;
+        }
+        Ok(self.peeked.as_ref())
+    }
+
+    /// Like `next`, but running out of input is an error.
+    pub fn expect_line(&mut self, expected: &str) -> Result<Line> {
+        match self.read()? {
+            Some(line) => Ok(line),
+            None => Err(self.end_error(expected)),
+        }
+    }
+
+    pub fn expect_blank(&mut self) -> Result<()> {
+        let line = self.expect_line("an empty line")?;
+        if !line.is_blank() {
+            return Err(line.parser().error("an empty line"));
+        }
+        Ok(())
+    }
+
+    /// Lines up to the next blank one or the end of input, skipping the
+    /// blank line.
+    pub fn block(&mut self) -> Result<Vec<Line>> {
+        let mut block = Vec::new();
+        while let Some(line) = self.read()? {
+            if line.is_blank() {
+                break;
+            }
+            block.push(line);
+        }
+        Ok(block)
+    }
+
+    /// All the remaining lines which are not blank.
+    pub fn non_blank(&mut self) -> Result<Vec<Line>> {
+        let mut lines = Vec::new();
+        while let Some(line) = self.read()? {
+            if !line.is_blank() {
+                lines.push(line);
+            }
+        }
+        Ok(lines)
+    }
+
+    /// Error for when the input ended too soon.
+    pub fn end_error(&self, expected: &str) -> Error {
+        Error::Parse(ParseError {
+            day: self.day,
+            line: self.number + 1,
+            column: 1,
+            expected: expected.to_string(),
+            found: "end of input".to_string(),
+        })
+    }
+
+    pub fn invalid(&self, message: &str) -> Error {
+        Error::Invalid { day: self.day, message: message.to_string() }
+    }
+}
+
+impl<R: BufRead> Iterator for Lines<R> {
+    type Item = Result<Line>;
+
+    fn next(&mut self) -> Option<Result<Line>> {
+        self.read().transpose()
+    }
+}
+
+/// Parses a grid from `lines`, one row per line, with `f` mapping the
+/// characters. `expected` describes the characters `f` accepts.
+pub fn grid<T, F>(lines: &[Line], mut f: F, expected: &str) -> Result<Grid<T>>
+    where F: FnMut(char) -> Option<T>
+{
+    let mut rows: Vec<Vec<T>> = Vec::new();
+    for line in lines {
+        let row = line.row(&mut f, expected)?;
+        if let Some(first) = rows.first() {
+            if row.len() != first.len() {
+                let expected = format!("a row of {} characters", first.len());
+                return Err(line.error(1, &expected, &format!("{} characters", row.len())));
+            }
+        }
+        rows.push(row);
+    }
+    Ok(Grid::new(rows))
+}
+
+/// What a value of type `T` is called in errors, e.g. `` `u32` ``.
+pub(crate) fn type_expected<T>() -> String {
+    let name = std::any::type_name::<T>();
+    format!("`{}`", name.rsplit("::").next().unwrap_or(name))
+}
+
+/// Reads tokens from a single line. Every method skips the whitespace before
+/// the token it reads.
+pub struct Parser<'a> {
+    line: &'a Line,
+    rest: &'a str,
+}
+
+impl<'a> Parser<'a> {
+    pub fn column(&self) -> usize {
+        let offset = self.line.text.len() - self.rest.len();
+        self.line.text[..offset].chars().count() + 1
+    }
+
+    /// Error at the current position, which names the token found there.
+    pub fn error(&self, expected: &str) -> Error {
+        let rest = self.rest.trim_start();
+        let skipped = &self.rest[..self.rest.len() - rest.len()];
+        let found = match rest.split_whitespace().next() {
+            Some(token) => format!("`{}`", token),
+            None => "end of line".to_string(),
+        };
+        self.line.error(self.column() + skipped.chars().count(), expected, &found)
+    }
+
+    fn advance(&mut self, bytes: usize) {
+        self.rest = &self.rest[bytes..];
+    }
+
+    pub fn skip_whitespace(&mut self) {
+        self.rest = self.rest.trim_start();
+    }
+
+    pub fn peek(&self) -> Option<char> {
+        self.rest.chars().next()
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.rest.trim().is_empty()
+    }
+
+    pub fn end(&mut self) -> Result<()> {
+        if !self.is_done() {
+            return Err(self.error("end of line"));
+        }
+        self.rest = "";
+        Ok(())
+    }
+
+    /// Everything left on the line, without surrounding whitespace.
+    pub fn rest(&mut self) -> &'a str {
+        let rest = self.rest.trim();
+        self.rest = "";
+        rest
+    }
+
+    /// Skips `token` if it comes next.
+    pub fn skip(&mut self, token: &str) -> bool {
+        match self.rest.trim_start().strip_prefix(token) {
+            Some(rest) => {
+                self.rest = rest;
+                true
+            },
+            None => false,
+        }
+    }
+
+    pub fn expect(&mut self, token: &str) -> Result<()> {
+        if !self.skip(token) {
+            return Err(self.error(&format!("`{}`", token)));
+        }
+        Ok(())
+    }
+
+    /// Skips whichever of `options` comes next and returns its value. When one
+    /// option is a prefix of another, the longer one has to come first.
+    pub fn one_of<T: Copy>(&mut self, options: &[(&str, T)]) -> Result<T> {
+        for (token, value) in options {
+            if self.skip(token) {
+                return Ok(*value);
+            }
+        }
+        let tokens: Vec<String> = options.iter().map(|(t, _)| format!("`{}`", t)).collect();
+        let expected = match tokens.split_last() {
+            Some((last, rest)) if !rest.is_empty() => format!("{} or {}", rest.join(", "), last),
+            _ => tokens.join(""),
+        };
+        Err(self.error(&expected))
+    }
+
+    /// Takes the longest non-empty run of characters accepted by `accept`.
+    fn take_while<F>(&mut self, expected: &str, accept: F) -> Result<&'a str>
+        where F: Fn(char) -> bool
+    {
+        self.skip_whitespace();
+        let len = self.rest.find(|c| !accept(c)).unwrap_or(self.rest.len());
+        if len == 0 {
+            return Err(self.error(expected));
+        }
+        let (taken, rest) = self.rest.split_at(len);
+        self.rest = rest;
+        Ok(taken)
+    }
+
+    /// Letters, digits and underscores.
+    pub fn word(&mut self) -> Result<&'a str> {
+        self.take_while("a word", |c| c.is_alphanumeric() || c == '_')
+    }
+
+    /// Anything up to the next whitespace.
+    pub fn token(&mut self) -> Result<&'a str> {
+        self.take_while("a token", |c| !c.is_whitespace())
+    }
+
+    pub fn number<T: FromStr>(&mut self) -> Result<T> {
+        self.skip_whitespace();
+        let sign = if self.rest.starts_with('-') || self.rest.starts_with('+') { 1 } else { 0 };
+        let len = sign + self.rest[sign..].find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(self.rest.len() - sign);
+        match self.rest[..len].parse() {
+            Ok(number) if len > sign => {
+                self.advance(len);
+                Ok(number)
+            },
+            _ => Err(self.error("a number")),
+        }
+    }
+
+    /// Anything up to whitespace or one of `stops`, parsed with `FromStr`.
+    pub fn parsed<T: FromStr>(&mut self, stops: &str) -> Result<T> {
+        self.skip_whitespace();
+        let len = self.rest.find(|c: char| c.is_whitespace() || stops.contains(c))
+            .unwrap_or(self.rest.len());
+        match self.rest[..len].parse() {
+            Ok(value) if len > 0 => {
+                self.advance(len);
+                Ok(value)
+            },
+            _ => Err(self.error(&type_expected::<T>())),
+        }
+    }
+
+    /// Whitespace separated numbers up to the end of the line.
+    pub fn numbers<T: FromStr>(&mut self) -> Result<Vec<T>> {
+        let mut numbers = Vec::new();
+        while !self.is_done() {
+            numbers.push(self.number()?);
+        }
+        Ok(numbers)
+    }
+
+    pub fn char(&mut self) -> Result<char> {
+        self.skip_whitespace();
+        let c = self.peek().ok_or_else(|| self.error("a character"))?;
+        self.advance(c.len_utf8());
+        Ok(c)
+    }
+
+    /// Maps the next character with `f`. `expected` describes the characters
+    /// `f` accepts.
+    pub fn char_as<T, F>(&mut self, expected: &str, f: F) -> Result<T>
+        where F: FnOnce(char) -> Option<T>
+    {
+        self.skip_whitespace();
+        let mapped = self.peek().and_then(f).ok_or_else(|| self.error(expected))?;
+        self.advance(self.peek().unwrap().len_utf8());
+        Ok(mapped)
+    }
+
+    /// One or more items parsed with `item`, separated with `separator`.
+    pub fn separated<T, F>(&mut self, separator: &str, mut item: F) -> Result<Vec<T>>
+        where F: FnMut(&mut Parser<'a>) -> Result<T>
+    {
+        let mut items = vec![item(self)?];
+        while self.skip(separator) {
+            items.push(item(self)?);
+        }
+        Ok(items)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_line(text: &str) -> Line {
+        Line { day: 7, number: 3, text: text.to_string() }
+    }
+
+    fn error_of<T: fmt::Debug>(result: Result<T>) -> String {
+        result.unwrap_err().to_string()
+    }
+
+    #[test]
+    fn test_parser() {
+        let line = new_line("AAA = (BBB, -12)  ");
+        let mut parser = line.parser();
+        assert_eq!(parser.word().unwrap(), "AAA");
+        parser.expect("=").unwrap();
+        parser.expect("(").unwrap();
+        assert_eq!(parser.token().unwrap(), "BBB,");
+        assert_eq!(parser.column(), 12);
+        assert_eq!(parser.number::<i64>().unwrap(), -12);
+        assert!(!parser.skip(","));
+        parser.expect(")").unwrap();
+        parser.end().unwrap();
+    }
+
+    #[test]
+    fn test_parser_errors() {
+        let line = new_line("Card  1: x");
+        let mut parser = line.parser();
+        assert_eq!(error_of(parser.expect("Game")),
+                   "day 7, line 3, column 1: expected `Game`, found `Card`");
+        parser.expect("Card").unwrap();
+        assert_eq!(parser.number::<u8>().unwrap(), 1);
+        assert_eq!(error_of(parser.number::<u8>()),
+                   "day 7, line 3, column 8: expected a number, found `:`");
+        parser.expect(":").unwrap();
+        assert_eq!(error_of(parser.end()),
+                   "day 7, line 3, column 10: expected end of line, found `x`");
+        assert_eq!(parser.char().unwrap(), 'x');
+        assert_eq!(error_of(parser.word()),
+                   "day 7, line 3, column 11: expected a word, found end of line");
+
+        let numbers = new_line("300 -");
+        let mut parser = numbers.parser();
+        assert_eq!(error_of(parser.number::<u8>()),
+                   "day 7, line 3, column 1: expected a number, found `300`");
+        assert_eq!(parser.number::<u16>().unwrap(), 300);
+        assert_eq!(error_of(parser.number::<i8>()),
+                   "day 7, line 3, column 5: expected a number, found `-`");
+    }
+
+    #[test]
+    fn test_parsed() {
+        let line = new_line("12 4.5,true, x");
+        let mut parser = line.parser();
+        assert_eq!(parser.parsed::<u32>(",").unwrap(), 12);
+        assert_eq!(parser.parsed::<f64>(",").unwrap(), 4.5);
+        parser.expect(",").unwrap();
+        assert!(parser.parsed::<bool>(",").unwrap());
+        parser.expect(",").unwrap();
+        assert_eq!(error_of(parser.parsed::<i64>(",")),
+                   "day 7, line 3, column 14: expected `i64`, found `x`");
+        assert_eq!(parser.parsed::<String>(",").unwrap(), "x");
+        assert_eq!(error_of(parser.parsed::<String>(",")),
+                   "day 7, line 3, column 15: expected `String`, found end of line");
+    }
+
+    #[test]
+    fn test_separated_and_chars() {
+        let line = new_line("3,4, 5 L?");
+        let mut parser = line.parser();
+        assert_eq!(parser.separated(",", |p| p.number::<i32>()).unwrap(), vec![3, 4, 5]);
+        let dir = |c| match c { 'L' => Some(-1), 'R' => Some(1), _ => None };
+        assert_eq!(parser.char_as("`L` or `R`", dir).unwrap(), -1);
+        assert_eq!(error_of(parser.char_as("`L` or `R`", dir)),
+                   "day 7, line 3, column 9: expected `L` or `R`, found `?`");
+
+        let colors = [("red", 0), ("green", 1), ("blue", 2)];
+        let color_line = new_line("green blue");
+        let mut parser = color_line.parser();
+        assert_eq!(parser.one_of(&colors).unwrap(), 1);
+        assert_eq!(parser.one_of(&colors[2..]).unwrap(), 2);
+        assert_eq!(error_of(parser.one_of(&colors)),
+                   "day 7, line 3, column 11: expected `red`, `green` or `blue`, found end of line");
+
+        let mut parser = line.parser();
+        assert_eq!(parser.numbers::<i32>().map_err(|e| e.to_string()),
+                   Err("day 7, line 3, column 2: expected a number, found `,4,`".to_string()));
+        assert_eq!(line.parser().rest(), "3,4, 5 L?");
+    }
+
+    #[test]
+    fn test_lines() {
+        let mut lines = Lines::new("a\n\nb\nc\n\nd".as_bytes(), 2);
+        assert_eq!(lines.peek().unwrap().unwrap().text, "a");
+        assert_eq!(lines.expect_line("a").unwrap().number, 1);
+        lines.expect_blank().unwrap();
+        let block: Vec<String> = lines.block().unwrap().into_iter().map(|l| l.text).collect();
+        assert_eq!(block, vec!["b", "c"]);
+        assert_eq!(error_of(lines.expect_blank()),
+                   "day 2, line 6, column 1: expected an empty line, found `d`");
+        assert!(lines.next().is_none());
+        assert_eq!(error_of(lines.expect_line("a seed")),
+                   "day 2, line 7, column 1: expected a seed, found end of input");
+
+        let mut lines = Lines::new("a\n\n  \nb\n".as_bytes(), 2);
+        let texts: Vec<String> = lines.non_blank().unwrap().into_iter().map(|l| l.text).collect();
+        assert_eq!(texts, vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_grid() {
+        let lines: Vec<Line> = Lines::new("  #.\n  .#".as_bytes(), 3).map(|l| l.unwrap()).collect();
+        let wall = |c| match c { '#' => Some(true), '.' => Some(false), _ => None };
+        let grid = grid(&lines, wall, "`#` or `.`").unwrap();
+        assert_eq!(grid.map(|w| if *w { 1 } else { 0 }).to_string(), "10\n01\n");
+
+        let lines: Vec<Line> = Lines::new("#.\n.x".as_bytes(), 3).map(|l| l.unwrap()).collect();
+        assert_eq!(error_of(super::grid(&lines, wall, "`#` or `.`")),
+                   "day 3, line 2, column 2: expected `#` or `.`, found `x`");
+
+        let lines: Vec<Line> = Lines::new("#.\n.".as_bytes(), 3).map(|l| l.unwrap()).collect();
+        assert_eq!(error_of(super::grid(&lines, wall, "`#` or `.`")),
+                   "day 3, line 2, column 1: expected a row of 2 characters, found 1 characters");
+    }
+}
@@ -0,0 +1,208 @@
+//! Token by token reading of the input, for when lines don't matter much, as
+//! in Codeforces style inputs. It replaces `UnsafeScanner` from
+//! https://github.com/EbTech/rust-algorithms without its `unsafe` and panics:
+//! every method returns a `parse::Result` pointing at the offending spot.
+//!
+//! ```
+//! use aoc::scanner::Scanner;
+//!
+//! let mut scan = Scanner::new("2\n10 -3\n\nseeds: 79 14\n1,2,3\n123\n456".as_bytes(), 1);
+//! let n = scan.token::<usize>().unwrap();
+//! let pair: Vec<i64> = (0..n).map(|_| scan.token().unwrap()).collect();
+//! assert_eq!(pair, vec![10, -3]);
+//! scan.expect_blank().unwrap();
+//! assert_eq!(scan.key_values::<u64>("seeds").unwrap(), vec![79, 14]);
+//! assert_eq!(scan.comma_list::<u8>().unwrap(), vec![1, 2, 3]);
+//! assert_eq!(scan.digit_grid().unwrap().height(), 2);
+//! ```
+
+use std::io::BufRead;
+use std::str::FromStr;
+
+use crate::parse::{self, Line, Lines, Result};
+use crate::Grid;
+
+pub struct Scanner<R> {
+    lines: Lines<R>,
+    /// The line `token` reads from and the byte offset of what is left of it.
+    line: Option<(Line, usize)>,
+}
+
+impl<R: BufRead> Scanner<R> {
+    pub fn new(input: R, day: u32) -> Scanner<R> {
+        Scanner::from_lines(Lines::new(input, day))
+    }
+
+    /// A scanner over what is left of `lines`, as in `Solution::parse`.
+    pub fn from_lines(lines: Lines<R>) -> Scanner<R> {
+        Scanner { lines, line: None }
+    }
+
+    /// The next whitespace separated token, from this line or the following
+    /// ones, parsed with `FromStr`.
+    pub fn token<T: FromStr>(&mut self) -> Result<T> {
+        loop {
+            if let Some((line, offset)) = &self.line {
+                let rest = &line.text[*offset..];
+                let trimmed = rest.trim_start();
+                if !trimmed.is_empty() {
+                    let start = *offset + rest.len() - trimmed.len();
+                    let end = start + trimmed.find(char::is_whitespace).unwrap_or(trimmed.len());
+                    let token = &line.text[start..end];
+                    let value = token.parse().map_err(|_| {
+                        let column = line.text[..start].chars().count() + 1;
+                        line.error(column, &parse::type_expected::<T>(), &format!("`{}`", token))
+                    })?;
+                    self.line.as_mut().unwrap().1 = end;
+                    return Ok(value);
+                }
+            }
+            match self.lines.next() {
+                Some(line) => self.line = Some((line?, 0)),
+                None => return Err(self.lines.end_error(&parse::type_expected::<T>())),
+            }
+        }
+    }
+
+    /// Drops the line `token` was reading from, which has to have nothing
+    /// left, so the line-based methods start at a fresh line.
+    fn finish_line(&mut self) -> Result<()> {
+        if let Some((line, offset)) = self.line.take() {
+            let rest = &line.text[offset..];
+            if let Some(token) = rest.split_whitespace().next() {
+                let start = offset + rest.find(token).unwrap();
+                let column = line.text[..start].chars().count() + 1;
+                return Err(line.error(column, "end of line", &format!("`{}`", token)));
+            }
+        }
+        Ok(())
+    }
+
+    /// The next line, whole.
+    pub fn line(&mut self) -> Result<Line> {
+        self.finish_line()?;
+        self.lines.expect_line("a line")
+    }
+
+    pub fn expect_blank(&mut self) -> Result<()> {
+        self.finish_line()?;
+        self.lines.expect_blank()
+    }
+
+    /// The lines up to the next blank one, or `None` at the end of input.
+    pub fn block(&mut self) -> Result<Option<Vec<Line>>> {
+        self.finish_line()?;
+        if self.lines.peek()?.is_none() {
+            return Ok(None);
+        }
+        self.lines.block().map(Some)
+    }
+
+    /// Values of a `key: v1 v2 ...` line.
+    pub fn key_values<T: FromStr>(&mut self, key: &str) -> Result<Vec<T>> {
+        self.finish_line()?;
+        let line = self.lines.expect_line(&format!("`{}:`", key))?;
+        let mut parser = line.parser();
+        parser.expect(key)?;
+        parser.expect(":")?;
+        let mut values = Vec::new();
+        while !parser.is_done() {
+            values.push(parser.parsed("")?);
+        }
+        Ok(values)
+    }
+
+    /// Values of a `v1,v2,...` line, with optional spaces around the commas.
+    pub fn comma_list<T: FromStr>(&mut self) -> Result<Vec<T>> {
+        self.finish_line()?;
+        let line = self.lines.expect_line("a comma separated list")?;
+        let mut parser = line.parser();
+        let values = parser.separated(",", |p| p.parsed(","))?;
+        parser.end()?;
+        Ok(values)
+    }
+
+    /// Rows of digits up to the next blank line or the end of input.
+    pub fn digit_grid(&mut self) -> Result<Grid<u8>> {
+        let lines = self.block()?.filter(|lines| !lines.is_empty())
+            .ok_or_else(|| self.lines.end_error("a row of digits"))?;
+        parse::grid(&lines, |c| c.to_digit(10).map(|d| d as u8), "a digit")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scanner(input: &str) -> Scanner<&[u8]> {
+        Scanner::new(input.as_bytes(), 4)
+    }
+
+    fn error_of<T: std::fmt::Debug>(result: Result<T>) -> String {
+        result.unwrap_err().to_string()
+    }
+
+    #[test]
+    fn test_tokens() {
+        let mut scan = scanner("  3 x\n\n   4.5\nend");
+        assert_eq!(scan.token::<i32>().unwrap(), 3);
+        assert_eq!(scan.token::<char>().unwrap(), 'x');
+        assert_eq!(scan.token::<f64>().unwrap(), 4.5);
+        assert_eq!(error_of(scan.token::<u8>()),
+                   "day 4, line 4, column 1: expected `u8`, found `end`");
+        assert_eq!(scan.token::<String>().unwrap(), "end");
+        assert_eq!(error_of(scan.token::<u8>()),
+                   "day 4, line 5, column 1: expected `u8`, found end of input");
+    }
+
+    #[test]
+    fn test_blocks() {
+        let mut scan = scanner("5\nab\ncd\n\nef\n");
+        assert_eq!(scan.token::<u32>().unwrap(), 5);
+        let texts = |lines: Vec<Line>| lines.into_iter().map(|l| l.text).collect::<Vec<_>>();
+        assert_eq!(scan.block().unwrap().map(texts), Some(vec!["ab".to_string(), "cd".to_string()]));
+        assert_eq!(scan.block().unwrap().map(texts), Some(vec!["ef".to_string()]));
+        assert_eq!(scan.block().unwrap(), None);
+
+        let mut lines = Lines::new("skipped\n5 6\nab".as_bytes(), 4);
+        lines.next();
+        let mut scan = Scanner::from_lines(lines);
+        assert_eq!(scan.token::<u32>().unwrap(), 5);
+        assert_eq!(error_of(scan.block()),
+                   "day 4, line 2, column 3: expected end of line, found `6`");
+    }
+
+    #[test]
+    fn test_key_values() {
+        let mut scan = scanner("Time:      7  15   30\nDistance:  9  x\nTime: 1");
+        assert_eq!(scan.key_values::<u64>("Time").unwrap(), vec![7, 15, 30]);
+        assert_eq!(error_of(scan.key_values::<u64>("Distance")),
+                   "day 4, line 2, column 15: expected `u64`, found `x`");
+        assert_eq!(error_of(scan.key_values::<u64>("Distance")),
+                   "day 4, line 3, column 1: expected `Distance`, found `Time:`");
+        assert_eq!(error_of(scan.key_values::<u64>("Time")),
+                   "day 4, line 4, column 1: expected `Time:`, found end of input");
+    }
+
+    #[test]
+    fn test_comma_list() {
+        let mut scan = scanner("rn=1,cm-,qp=3\n1, 2 ,3\n1,,2");
+        assert_eq!(scan.comma_list::<String>().unwrap(), vec!["rn=1", "cm-", "qp=3"]);
+        assert_eq!(scan.comma_list::<i8>().unwrap(), vec![1, 2, 3]);
+        assert_eq!(error_of(scan.comma_list::<i8>()),
+                   "day 4, line 3, column 3: expected `i8`, found `,2`");
+    }
+
+    #[test]
+    fn test_digit_grid() {
+        let grid = scanner("2413\n3215\n\n").digit_grid().unwrap();
+        assert_eq!(grid.height(), 2);
+        assert_eq!(grid.width(), 4);
+        assert_eq!(error_of(scanner("24\n3x").digit_grid()),
+                   "day 4, line 2, column 2: expected a digit, found `x`");
+        assert_eq!(error_of(scanner("24\n345").digit_grid()),
+                   "day 4, line 2, column 1: expected a row of 2 characters, found 3 characters");
+        assert_eq!(error_of(scanner("").digit_grid()),
+                   "day 4, line 1, column 1: expected a row of digits, found end of input");
+    }
+}
@@ -0,0 +1,161 @@
+//! Dijkstra and A* over any hashable state type.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::hash::Hash;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Path<S> {
+    pub cost: i64,
+    /// From the start state to the goal state, both included.
+    pub states: Vec<S>,
+}
+
+/// Cheapest path from any of `starts` to a state accepted by `is_goal`.
+///
+/// `successors` lists the states reachable in one step, with the (non-negative)
+/// cost of that step.
+pub fn dijkstra<S, I, FS, FG>(starts: I, successors: FS, is_goal: FG) -> Option<Path<S>>
+    where S: Clone + Eq + Hash,
+          I: IntoIterator<Item = S>,
+          FS: FnMut(&S) -> Vec<(S, i64)>,
+          FG: FnMut(&S) -> bool,
+{
+    a_star(starts, successors, |_| 0, is_goal)
+}
+
+/// Like `dijkstra`, but visits states in order of cost plus `heuristic`.
+///
+/// The heuristic must never overestimate the remaining cost, otherwise the
+/// returned path might not be the cheapest one.
+pub fn a_star<S, I, FS, FH, FG>(
+    starts: I, mut successors: FS, mut heuristic: FH, mut is_goal: FG) -> Option<Path<S>>
+    where S: Clone + Eq + Hash,
+          I: IntoIterator<Item = S>,
+          FS: FnMut(&S) -> Vec<(S, i64)>,
+          FH: FnMut(&S) -> i64,
+          FG: FnMut(&S) -> bool,
+{
+    // States are kept in `states` and referred to by their index everywhere
+    // else, so that `S` needs neither `Ord` nor `Copy`.
+    let mut states: Vec<S> = Vec::new();
+    let mut indices: HashMap<S, usize> = HashMap::new();
+    let mut costs: Vec<i64> = Vec::new();
+    let mut parents: Vec<Option<usize>> = Vec::new();
+
+    let mut heap = BinaryHeap::new();
+    for start in starts {
+        if indices.contains_key(&start) {
+            continue;
+        }
+        let idx = states.len();
+        heap.push(Reverse((heuristic(&start), 0, idx)));
+        indices.insert(start.clone(), idx);
+        states.push(start);
+        costs.push(0);
+        parents.push(None);
+    }
+
+    while let Some(Reverse((_, cost, idx))) = heap.pop() {
+        if cost > costs[idx] {
+            continue;
+        }
+        if is_goal(&states[idx]) {
+            let mut path = vec![idx];
+            while let Some(parent) = parents[*path.last().unwrap()] {
+                path.push(parent);
+            }
+            return Some(Path {
+                cost,
+                states: path.into_iter().rev().map(|i| states[i].clone()).collect(),
+            });
+        }
+
+        for (next, step_cost) in successors(&states[idx]) {
+            let new_cost = cost + step_cost;
+            let next_idx = match indices.get(&next) {
+                Some(&next_idx) => {
+                    if new_cost >= costs[next_idx] {
+                        continue;
+                    }
+                    costs[next_idx] = new_cost;
+                    parents[next_idx] = Some(idx);
+                    next_idx
+                },
+                None => {
+                    let next_idx = states.len();
+                    indices.insert(next.clone(), next_idx);
+                    states.push(next);
+                    costs.push(new_cost);
+                    parents.push(Some(idx));
+                    next_idx
+                },
+            };
+            let estimate = new_cost + heuristic(&states[next_idx]);
+            heap.push(Reverse((estimate, new_cost, next_idx)));
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Grid, XY};
+
+    fn graph_successors(node: &char) -> Vec<(char, i64)> {
+        match node {
+            'a' => vec![('b', 7), ('c', 9), ('f', 14)],
+            'b' => vec![('a', 7), ('c', 10), ('d', 15)],
+            'c' => vec![('a', 9), ('b', 10), ('d', 11), ('f', 2)],
+            'd' => vec![('b', 15), ('c', 11), ('e', 6)],
+            'e' => vec![('d', 6), ('f', 9)],
+            'f' => vec![('a', 14), ('c', 2), ('e', 9)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let path = dijkstra(['a'], graph_successors, |n| *n == 'e').unwrap();
+        assert_eq!(path.cost, 20);
+        assert_eq!(path.states, vec!['a', 'c', 'f', 'e']);
+
+        let path = dijkstra(['a'], graph_successors, |n| *n == 'a').unwrap();
+        assert_eq!(path, Path { cost: 0, states: vec!['a'] });
+
+        let path = dijkstra(['a', 'd'], graph_successors, |n| *n == 'e').unwrap();
+        assert_eq!(path.states, vec!['d', 'e']);
+
+        assert_eq!(dijkstra(['a'], graph_successors, |n| *n == 'x'), None);
+    }
+
+    #[test]
+    fn test_a_star_on_grid() {
+        let grid = Grid::from_lines(
+            "..#....
+             .##.##.
+             ....#..
+             .##...#
+             ....#..".lines(),
+            |c| c,
+        );
+        let goal = XY::new(6, 4);
+        let successors = |pos: &XY| grid.neighbours(pos).into_iter()
+            .filter(|n| grid[*n] != '#')
+            .map(|n| (n, 1))
+            .collect();
+        let manhattan = |pos: &XY| (goal.x - pos.x).abs() + (goal.y - pos.y).abs();
+
+        let path = a_star([XY::new(0, 0)], successors, manhattan, |pos| *pos == goal).unwrap();
+        assert_eq!(path.cost, 10);
+        assert_eq!(path.states.len(), 11);
+        for step in path.states.windows(2) {
+            assert!(grid.neighbours(&step[0]).contains(&step[1]));
+        }
+
+        let plain = dijkstra([XY::new(0, 0)], successors, |pos| *pos == goal).unwrap();
+        assert_eq!(plain.cost, path.cost);
+    }
+}
@@ -0,0 +1,98 @@
+//! One puzzle, both parts: the input is parsed once into a type implementing
+//! `Solution`, and each part is a method on it.
+//!
+//! A day crate names its solution `Puzzle` in `lib.rs`, which is what the
+//! runner, `main.rs` and the generated example tests use:
+//!
+//! ```text
+//! pub type Puzzle = cubes::Games;
+//! ```
+
+use std::fmt::Display;
+use std::io::{BufRead, Write};
+
+use crate::parse::{Error, Lines, Result};
+
+pub trait Solution: Sized {
+    const DAY: u32;
+
+    fn parse<R: BufRead>(lines: Lines<R>) -> Result<Self>;
+
+    fn part1(&self) -> Result<impl Display>;
+
+    fn part2(&self) -> Result<impl Display>;
+
+    /// An error for input that parses but has no answer.
+    fn invalid(message: &str) -> Error {
+        Error::Invalid { day: Self::DAY, message: message.to_string() }
+    }
+}
+
+pub fn solve_part1<S: Solution, R: BufRead, W: Write>(input: R, mut output: W) -> Result<()> {
+    let solution = S::parse(Lines::new(input, S::DAY))?;
+    writeln!(output, "{}", solution.part1()?)?;
+    Ok(())
+}
+
+pub fn solve_part2<S: Solution, R: BufRead, W: Write>(input: R, mut output: W) -> Result<()> {
+    let solution = S::parse(Lines::new(input, S::DAY))?;
+    writeln!(output, "{}", solution.part2()?)?;
+    Ok(())
+}
+
+/// Both answers, one per line, from a single parse.
+pub fn solve_both<S: Solution, R: BufRead, W: Write>(input: R, mut output: W) -> Result<()> {
+    let solution = S::parse(Lines::new(input, S::DAY))?;
+    writeln!(output, "{}", solution.part1()?)?;
+    writeln!(output, "{}", solution.part2()?)?;
+    Ok(())
+}
+
+/// `main` of a day: both answers for the input on stdin.
+pub fn main<S: Solution>() {
+    let stdin = std::io::stdin();
+    let stdout = std::io::stdout();
+    if let Err(e) = solve_both::<S, _, _>(stdin.lock(), stdout.lock()) {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{test_error, test_exact};
+
+    struct Sums {
+        numbers: Vec<i64>,
+    }
+
+    impl Solution for Sums {
+        const DAY: u32 = 1;
+
+        fn parse<R: BufRead>(lines: Lines<R>) -> Result<Sums> {
+            let mut numbers = Vec::new();
+            for line in lines {
+                numbers.push(line?.parser().number()?);
+            }
+            Ok(Sums { numbers })
+        }
+
+        fn part1(&self) -> Result<impl Display> {
+            Ok(self.numbers.iter().sum::<i64>())
+        }
+
+        fn part2(&self) -> Result<impl Display> {
+            Ok(self.numbers.iter().product::<i64>())
+        }
+    }
+
+    #[test]
+    fn test_solve() {
+        test_exact(|i, o| solve_part1::<Sums, _, _>(i, o), "2\n3\n4", "9\n");
+        test_exact(|i, o| solve_part2::<Sums, _, _>(i, o), "2\n3\n4", "24\n");
+        test_exact(|i, o| solve_both::<Sums, _, _>(i, o), "2\n3\n4", "9\n24\n");
+        test_error(|i, o| solve_both::<Sums, _, _>(i, o), "2\nx",
+                   "day 1, line 2, column 1: expected a number, found `x`");
+    }
+}
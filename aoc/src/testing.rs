@@ -0,0 +1,74 @@
+//! Helpers for testing `solve` functions on samples.
+//!
+//! `solve` is usually generic over its input and output, so pass it wrapped
+//! in a closure: `test_exact(|i, o| solve(i, o), input, output)`.
+
+use crate::parse::Result;
+
+pub fn run<F>(solve: F, input: &str) -> String
+    where F: FnOnce(&[u8], &mut Vec<u8>) -> Result<()>
+{
+    let mut actual_out: Vec<u8> = Vec::new();
+    if let Err(e) = solve(input.as_bytes(), &mut actual_out) {
+        panic!("solve failed: {}", e);
+    }
+    String::from_utf8(actual_out).unwrap()
+}
+
+pub fn test_exact<F>(solve: F, input: &str, output: &str)
+    where F: FnOnce(&[u8], &mut Vec<u8>) -> Result<()>
+{
+    assert_eq!(run(solve, input), output);
+}
+
+pub fn test_ignore_whitespaces<F>(solve: F, input: &str, output: &str)
+    where F: FnOnce(&[u8], &mut Vec<u8>) -> Result<()>
+{
+    let actual_out_str = run(solve, input);
+    let actual_outs = actual_out_str.split_whitespace().collect::<Vec<&str>>();
+    let expected_outs = output.split_whitespace().collect::<Vec<&str>>();
+    assert_eq!(actual_outs, expected_outs);
+}
+
+/// Like `test_exact`, but ignores whitespace at the end of the outputs, as sample
+/// files may or may not end with a newline. The input is passed as it is.
+pub fn test_example<F>(solve: F, input: &str, output: &str)
+    where F: FnOnce(&[u8], &mut Vec<u8>) -> Result<()>
+{
+    assert_eq!(run(solve, input).trim_end(), output.trim_end());
+}
+
+/// Checks that `solve` fails with the given message.
+pub fn test_error<F>(solve: F, input: &str, error: &str)
+    where F: FnOnce(&[u8], &mut Vec<u8>) -> Result<()>
+{
+    let mut actual_out: Vec<u8> = Vec::new();
+    match solve(input.as_bytes(), &mut actual_out) {
+        Ok(()) => panic!("solve succeeded with: {}", String::from_utf8_lossy(&actual_out)),
+        Err(e) => assert_eq!(e.to_string(), error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, Write};
+    use crate::parse::Lines;
+
+    fn solve<R: BufRead, W: Write>(input: R, mut output: W) -> Result<()> {
+        for line in Lines::new(input, 1) {
+            let line = line?;
+            writeln!(output, "{}", line.parser().word()?)?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_test_functions() {
+        test_ignore_whitespaces(|i, o| solve(i, o), "1\n  2", "1 2");
+        test_exact(|i, o| solve(i, o), "1\n  2", "1\n2\n");
+        test_example(|i, o| solve(i, o), "1\n  2", "1\n2");
+        test_error(|i, o| solve(i, o), "1\n  -",
+                   "day 1, line 2, column 3: expected a word, found `-`");
+    }
+}
@@ -0,0 +1,223 @@
+//! Diagnostics for solvers, written to stderr so they never mix with answers.
+//!
+//! Nothing is printed until enabled, either with the `AOC_TRACE` environment
+//! variable or with `init` (`aoc --trace` in the runner). The filter is a comma
+//! separated list of `level` and `target=level`, where targets are module paths
+//! like `day12` or `day12::springs_brut` and the most specific one wins:
+//!
+//! ```text
+//! AOC_TRACE=debug cargo test -p day21
+//! AOC_TRACE=warn,day12::springs_brut=trace cargo test -p day12
+//! ```
+//!
+//! Use the `error!`, `warn!`, `info!`, `debug!` and `trace!` macros, they take
+//! the module they are called from as the target. Their arguments are not
+//! evaluated when the level is off.
+
+use std::fmt;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::RwLock;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Error = 1,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl Level {
+    fn parse(name: &str) -> Option<Option<Level>> {
+        Some(match name.to_ascii_lowercase().as_str() {
+            "off" => None,
+            "error" => Some(Level::Error),
+            "warn" => Some(Level::Warn),
+            "info" => Some(Level::Info),
+            "debug" => Some(Level::Debug),
+            "trace" => Some(Level::Trace),
+            _ => return None,
+        })
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Level::Error => "ERROR",
+            Level::Warn => "WARN",
+            Level::Info => "INFO",
+            Level::Debug => "DEBUG",
+            Level::Trace => "TRACE",
+        }
+    }
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Filter {
+    default: Option<Level>,
+    /// Sorted from the longest target, so the first match is the most specific.
+    targets: Vec<(String, Option<Level>)>,
+}
+
+impl Filter {
+    pub fn parse(spec: &str) -> Result<Filter, String> {
+        let mut filter = Filter::default();
+        for item in spec.split(',').map(str::trim).filter(|i| !i.is_empty()) {
+            let (target, level) = match item.split_once('=') {
+                Some((target, level)) => (Some(target.trim()), level.trim()),
+                None => (None, item),
+            };
+            let level = Level::parse(level)
+                .ok_or(format!("trace filter: unknown level `{}` in `{}`", level, item))?;
+            match target {
+                Some(target) => filter.targets.push((target.to_string(), level)),
+                None => filter.default = level,
+            }
+        }
+        filter.targets.sort_by_key(|(target, _)| std::cmp::Reverse(target.len()));
+        Ok(filter)
+    }
+
+    pub fn enabled(&self, target: &str, level: Level) -> bool {
+        let matches = |prefix: &str| {
+            target.strip_prefix(prefix).is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+        };
+        let max = self.targets.iter()
+            .find(|(prefix, _)| matches(prefix))
+            .map_or(self.default, |(_, level)| *level);
+        max.is_some_and(|max| level <= max)
+    }
+
+    /// The most verbose level enabled for any target.
+    fn max_level(&self) -> u8 {
+        self.targets.iter().map(|(_, l)| *l).chain([self.default])
+            .map(|l| l.map_or(0, |l| l as u8))
+            .max()
+            .unwrap()
+    }
+}
+
+const UNINITIALIZED: u8 = u8::MAX;
+
+/// Checked before taking the lock, so disabled tracing costs one atomic load.
+static MAX_LEVEL: AtomicU8 = AtomicU8::new(UNINITIALIZED);
+static FILTER: RwLock<Option<Filter>> = RwLock::new(None);
+
+fn set(filter: Filter) {
+    let max_level = filter.max_level();
+    *FILTER.write().unwrap() = Some(filter);
+    MAX_LEVEL.store(max_level, Ordering::Relaxed);
+}
+
+/// Replaces the filter, including the one from `AOC_TRACE`.
+pub fn init(spec: &str) -> Result<(), String> {
+    set(Filter::parse(spec)?);
+    Ok(())
+}
+
+pub fn enabled(target: &str, level: Level) -> bool {
+    let mut max_level = MAX_LEVEL.load(Ordering::Relaxed);
+    if max_level == UNINITIALIZED {
+        let spec = std::env::var("AOC_TRACE").unwrap_or_default();
+        set(Filter::parse(&spec).unwrap_or_else(|e| {
+            eprintln!("AOC_TRACE: {}", e);
+            Filter::default()
+        }));
+        max_level = MAX_LEVEL.load(Ordering::Relaxed);
+    }
+    if (level as u8) > max_level {
+        return false;
+    }
+    FILTER.read().unwrap().as_ref().is_some_and(|f| f.enabled(target, level))
+}
+
+pub fn log(target: &str, level: Level, message: fmt::Arguments) {
+    eprintln!("{:<5} {}: {}", level.name(), target, message);
+}
+
+/// Logs at `level` with the calling module as the target.
+#[macro_export]
+macro_rules! log {
+    ( $level:expr ) => {
+        $crate::log!($level, "")
+    };
+    ( $level:expr, $( $arg:tt )+ ) => {
+        if $crate::trace::enabled(module_path!(), $level) {
+            $crate::trace::log(module_path!(), $level, format_args!($( $arg )+));
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! error {
+    () => { $crate::log!($crate::trace::Level::Error) };
+    ( $( $arg:tt )+ ) => { $crate::log!($crate::trace::Level::Error, $( $arg )+) };
+}
+
+#[macro_export]
+macro_rules! warn {
+    () => { $crate::log!($crate::trace::Level::Warn) };
+    ( $( $arg:tt )+ ) => { $crate::log!($crate::trace::Level::Warn, $( $arg )+) };
+}
+
+#[macro_export]
+macro_rules! info {
+    () => { $crate::log!($crate::trace::Level::Info) };
+    ( $( $arg:tt )+ ) => { $crate::log!($crate::trace::Level::Info, $( $arg )+) };
+}
+
+#[macro_export]
+macro_rules! debug {
+    () => { $crate::log!($crate::trace::Level::Debug) };
+    ( $( $arg:tt )+ ) => { $crate::log!($crate::trace::Level::Debug, $( $arg )+) };
+}
+
+#[macro_export]
+macro_rules! trace {
+    () => { $crate::log!($crate::trace::Level::Trace) };
+    ( $( $arg:tt )+ ) => { $crate::log!($crate::trace::Level::Trace, $( $arg )+) };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter() {
+        let filter = Filter::parse("warn, day21=debug,day21::steps2=off").unwrap();
+        assert!(filter.enabled("day5::almanac", Level::Error));
+        assert!(filter.enabled("day5::almanac", Level::Warn));
+        assert!(!filter.enabled("day5::almanac", Level::Info));
+        assert!(filter.enabled("day21::steps", Level::Debug));
+        assert!(!filter.enabled("day21::steps", Level::Trace));
+        assert!(!filter.enabled("day21::steps2", Level::Error));
+        // `day2` is not a prefix of `day21` as a target.
+        assert!(!Filter::parse("day2=trace").unwrap().enabled("day21::steps", Level::Error));
+        assert!(Filter::parse("day2=trace").unwrap().enabled("day2::cubes::tests", Level::Trace));
+
+        assert_eq!(Filter::parse("").unwrap(), Filter::default());
+        assert!(!Filter::default().enabled("day1", Level::Error));
+        assert_eq!(Filter::parse("day1=loud").unwrap_err(),
+                   "trace filter: unknown level `loud` in `day1=loud`");
+    }
+
+    #[test]
+    fn test_max_level() {
+        assert_eq!(Filter::default().max_level(), 0);
+        assert_eq!(Filter::parse("info,day3=trace").unwrap().max_level(), Level::Trace as u8);
+        assert_eq!(Filter::parse("day3=error,day4=warn").unwrap().max_level(), Level::Warn as u8);
+    }
+
+    #[test]
+    fn test_macros_skip_disabled_arguments() {
+        init("aoc::trace::tests=info").unwrap();
+        let mut evaluated = 0;
+        let mut count = || { evaluated += 1; evaluated };
+        crate::info!("info {}", count());
+        crate::debug!("debug {}", count());
+        crate::trace!();
+        assert_eq!(evaluated, 1);
+        assert!(enabled(module_path!(), Level::Warn));
+        assert!(!enabled("day1", Level::Error));
+        init("").unwrap();
+    }
+}
@@ -0,0 +1,107 @@
+//! Positions and directions on a 2D grid, with y growing downwards.
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash, Ord, PartialOrd)]
+pub struct XY {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl XY {
+    /// Unit steps in the order of `Direction::ALL`.
+    pub const AROUND: [XY; 4] = [
+        XY::new(0, -1),
+        XY::new(1, 0),
+        XY::new(0, 1),
+        XY::new(-1, 0),
+    ];
+
+    pub const fn new(x: i64, y: i64) -> XY { XY {x, y} }
+    pub const fn newu(x: usize, y: usize) -> XY { XY {x: x as i64, y: y as i64} }
+
+    pub const fn add(&self, other: &XY) -> XY { XY { x: self.x + other.x, y: self.y + other.y } }
+    pub const fn sub(&self, other: &XY) -> XY { XY { x: self.x - other.x, y: self.y - other.y } }
+    pub const fn mul(&self, scalar: i64) -> XY { XY { x: self.x * scalar, y: self.y * scalar } }
+
+    pub const fn ux(&self) -> usize { self.x as usize }
+    pub const fn uy(&self) -> usize { self.y as usize }
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
+pub enum Direction {
+    UP,
+    RIGHT,
+    DOWN,
+    LEFT,
+}
+use Direction::{UP, RIGHT, DOWN, LEFT};
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        UP,
+        RIGHT,
+        DOWN,
+        LEFT,
+    ];
+
+    pub const fn from(xy: XY) -> Direction {
+        match xy {
+            XY{x: 0, y: -1} => UP,
+            XY{x: 1, y: 0}  => RIGHT,
+            XY{x: 0, y: 1}  => DOWN,
+            XY{x: -1, y: 0} => LEFT,
+            _ => panic!("This is no direction"),
+        }
+    }
+
+    /// Index in `Direction::ALL`.
+    pub const fn as_entry(&self) -> usize {
+        match self {
+            UP => 0,
+            RIGHT => 1,
+            DOWN => 2,
+            LEFT => 3,
+        }
+    }
+
+    pub const fn as_direction(&self) -> XY {
+        match self {
+            UP => XY::new(0, -1),
+            RIGHT => XY::new(1, 0),
+            DOWN => XY::new(0, 1),
+            LEFT => XY::new(-1, 0),
+        }
+    }
+
+    pub const fn as_char(&self) -> char {
+        match self {
+            UP    => '^',
+            RIGHT => '>',
+            DOWN  => 'v',
+            LEFT  => '<',
+        }
+    }
+
+    pub const fn opposite(&self) -> Direction {
+        match self {
+            UP    => DOWN,
+            RIGHT => LEFT,
+            DOWN  => UP,
+            LEFT  => RIGHT,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_directions() {
+        for (i, d) in Direction::ALL.iter().enumerate() {
+            assert_eq!(d.as_entry(), i);
+            assert_eq!(d.as_direction(), XY::AROUND[i]);
+            assert_eq!(Direction::from(d.as_direction()), *d);
+            assert_eq!(d.opposite().as_direction(), d.as_direction().mul(-1));
+        }
+    }
+}
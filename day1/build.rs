@@ -0,0 +1,3 @@
+fn main() {
+    aoc::examples::generate_tests();
+}
@@ -0,0 +1,5 @@
+pub const DAY: u32 = 1;
+
+pub mod trebuchet;
+
+pub type Puzzle = trebuchet::Calibration;
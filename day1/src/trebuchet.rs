@@ -0,0 +1,161 @@
+use std::fmt::Display;
+use std::io::BufRead;
+use aoc::debug;
+use aoc::parse::{Lines, Result};
+use aoc::solution::Solution;
+
+const SPELLED_DIGITS: &[(&str, char)] = &[
+    ("one", '1'),
+    ("two", '2'),
+    ("three", '3'),
+    ("four", '4'),
+    ("five", '5'),
+    ("six", '6'),
+    ("seven", '7'),
+    ("eight", '8'),
+    ("nine", '9'),
+];
+
+fn to_char_ends(substring: &str, digits: &[(&str, char)]) -> Option<char> {
+    for (name, value) in digits {
+        if substring.ends_with(name) {
+            return Some(*value)
+        }
+    }
+    None
+}
+
+fn to_char_starts(substring: &str, digits: &[(&str, char)]) -> Option<char> {
+    for (name, value) in digits {
+        if substring.starts_with(name) {
+            return Some(*value)
+        }
+    }
+    None
+}
+
+/// The first and last digit of `line`, where `digits` are the spelled out
+/// ones that count too.
+fn calibration_value(line: &str, digits: &[(&str, char)]) -> i64 {
+    debug!("line: {}", line);
+    let mut first = '0';
+    for (i, c) in line.char_indices() {
+        debug!("c: {}", c);
+        if c.is_ascii_digit() {
+            first = c;
+            break;
+        }
+        if let Some(parsed) = to_char_ends(&line[..i + c.len_utf8()], digits) {
+            first = parsed;
+            break;
+        }
+    }
+    let mut last = '0';
+    for (i, c) in line.char_indices().rev() {
+        debug!("c: {}", c);
+        if c.is_ascii_digit() {
+            last = c;
+            break;
+        }
+        if let Some(parsed) = to_char_starts(&line[i..], digits) {
+            last = parsed;
+            break;
+        }
+    }
+    debug!("first: {}, last: {}", first, last);
+
+    (first.to_digit(10).unwrap() * 10 + last.to_digit(10).unwrap()) as i64
+}
+
+pub struct Calibration {
+    lines: Vec<String>,
+}
+
+impl Solution for Calibration {
+    const DAY: u32 = crate::DAY;
+
+    fn parse<R: BufRead>(lines: Lines<R>) -> Result<Calibration> {
+        let lines = lines.map(|line| Ok(line?.text.trim().to_string())).collect::<Result<_>>()?;
+        Ok(Calibration { lines })
+    }
+
+    fn part1(&self) -> Result<impl Display> {
+        Ok(self.lines.iter().map(|line| calibration_value(line, &[])).sum::<i64>())
+    }
+
+    fn part2(&self) -> Result<impl Display> {
+        Ok(self.lines.iter().map(|line| calibration_value(line, SPELLED_DIGITS)).sum::<i64>())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Calibration;
+    use aoc::solution::{solve_part1, solve_part2};
+
+    fn test_part1(input: &str, output: &str) {
+        aoc::testing::test_ignore_whitespaces(|i, o| solve_part1::<Calibration, _, _>(i, o), input, output);
+    }
+
+    fn test_part2(input: &str, output: &str) {
+        aoc::testing::test_ignore_whitespaces(|i, o| solve_part2::<Calibration, _, _>(i, o), input, output);
+    }
+
+    include!(concat!(env!("OUT_DIR"), "/examples/trebuchet.rs"));
+
+    #[test]
+    fn just_numbers() {
+        test_part1(
+            "12
+            0",
+            "12",
+        );
+        test_part2(
+            "12
+            0",
+            "12",
+        );
+    }
+
+    #[test]
+    fn just_numbers_and_spelled() {
+        test_part2(
+            "12
+            two2
+            0",
+            "34",
+        );
+    }
+
+    #[test]
+    fn single_digit() {
+        test_part1(
+            "1
+            a5d",
+            "66",
+        );
+        test_part2(
+            "1
+            a5d",
+            "66",
+        );
+    }
+
+    #[test]
+    fn single_spelled_digit() {
+        test_part2(
+            "one
+            afived",
+            "66",
+        );
+    }
+
+    #[test]
+    fn almost_spelled_digit() {
+        test_part2(
+            "one
+            thre5sine7",
+            "68",
+        );
+    }
+}
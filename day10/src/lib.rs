@@ -0,0 +1,6 @@
+pub const DAY: u32 = 10;
+
+pub mod maze;
+pub mod maze2;
+
+pub type Puzzle = maze::Graph;
@@ -1,6 +1,3 @@
-mod maze2easier;
-
-
 fn main() {
-    maze2easier::main();
+    aoc::solution::main::<day10::Puzzle>();
 }
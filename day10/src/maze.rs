@@ -1,27 +1,12 @@
 use std::cmp::max;
-use std::io::{BufRead, BufReader, Write};
+use std::fmt::Display;
+use std::io::BufRead;
 use std::collections::{HashSet, VecDeque};
-
-macro_rules! dprintln {
-    ( $( $x:expr ),* ) => {
-        {
-	    #[cfg(test)]
-            println!($($x), *);
-        }
-    };
-}
-
-#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
-struct Position {
-    x: usize,
-    y: usize,
-}
-
-#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
-struct Direction {
-    x: i64,
-    y: i64,
-}
+use aoc::debug;
+use aoc::{Direction, Grid, XY};
+use aoc::Direction::{UP, RIGHT, DOWN, LEFT};
+use aoc::parse::{self, Lines, Result};
+use aoc::solution::Solution;
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
 enum Status {
@@ -31,47 +16,28 @@ enum Status {
     Distance(i64),
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct Node {
-    position: Position,
     neighbours: HashSet<Direction>,
     status: Status,
 }
 
-#[derive(Debug)]
-struct Graph {
-    nodes: Vec<Vec<Node>>,
-    start: Position,
-}
-
-impl Position {
-    fn from(x: usize, y: usize) -> Position { Position {x, y}}
-    fn move_in(&self, d: &Direction) -> Position { Position {
-        x: (self.x as i64 + d.x) as usize,
-        y: (self.y as i64 + d.y) as usize,
-    } }
-}
-
-impl Direction {
-    fn from(x: i64, y: i64) -> Direction { Direction {x, y}}
-
-    fn up() -> Direction { Direction::from(0, -1) }
-    fn down() -> Direction { Direction::from(0, 1) }
-    fn left() -> Direction { Direction::from(-1, 0) }
-    fn right() -> Direction { Direction::from(1, 0) }
+#[derive(Debug, Clone)]
+pub struct Graph {
+    nodes: Grid<Node>,
+    start: XY,
 }
 
 impl Node {
-    fn from_char(c: char, pos: &Position) -> Node {
+    fn from_char(c: char) -> Node {
         Node {
-            position: pos.clone(),
             neighbours: match c {
-                '|' => HashSet::from([ Direction::up(), Direction::down() ]),
-                '-' => HashSet::from([ Direction::left(), Direction::right() ]),
-                'L' => HashSet::from([ Direction::up(), Direction::right() ]),
-                'J' => HashSet::from([ Direction::up(), Direction::left() ]),
-                '7' => HashSet::from([ Direction::down(), Direction::left() ]),
-                'F' => HashSet::from([ Direction::down(), Direction::right() ]),
+                '|' => HashSet::from([ UP, DOWN ]),
+                '-' => HashSet::from([ LEFT, RIGHT ]),
+                'L' => HashSet::from([ UP, RIGHT ]),
+                'J' => HashSet::from([ UP, LEFT ]),
+                '7' => HashSet::from([ DOWN, LEFT ]),
+                'F' => HashSet::from([ DOWN, RIGHT ]),
                 '.' => HashSet::new(),
                 'S' => HashSet::new(),
                 _ => panic!("Don't know this input!"),
@@ -84,80 +50,49 @@ impl Node {
             },
         }
     }
-}
-
-impl Graph {
-    fn from_lines<I>(lines: I) -> Graph
-        where I: Iterator<Item = String>,
-    {
-        let mut nodes = Vec::new();
-        let mut start = Position::from(usize::MAX, usize::MAX);
-        for (y, line) in lines.enumerate() {
-            nodes.push(Vec::new());
-            for (x, c) in line.trim().char_indices() {
-                let pos = Position::from(x, y);
-                let node = Node::from_char(c, &pos);
-                if node.status == Status::Start {
-                    start = pos.clone();
-                }
-                nodes[y].push(node);
-            }
-        }
 
-        Graph {
-            nodes: nodes,
-            start: start,
-        }
-    }
-
-    fn node_at(&self, pos: &Position) -> &Node {
-        &self.nodes[pos.y][pos.x]
+    fn is_vertical(&self) -> bool {
+         self.neighbours == HashSet::from([ UP, DOWN ])
     }
 
-    fn node_at_mut(&mut self, pos: &Position) -> &mut Node {
-        &mut self.nodes[pos.y][pos.x]
-    }
-
-    fn neighbours_from(&self, pos: &Position) -> Vec<Position> {
-        if pos.y >= self.nodes.len() || pos.y >= self.nodes[0].len() {
-            panic!("Trying to access non-existing position")
+    fn is_half_vertical(&self) -> Option<Direction> {
+        if self.neighbours.is_empty() ||
+             self.neighbours == HashSet::from([ LEFT, RIGHT ]) ||
+             self.neighbours == HashSet::from([ UP, DOWN ]) {
+            return None;
         }
 
-        let mut neighs = vec![];
-        for d in &self.node_at(pos).neighbours {
-            if !self.is_valid_move(pos, d) {
-                continue
-            }
-            neighs.push(pos.move_in(&d));
+        if self.neighbours.contains(&DOWN) {
+            return Some(DOWN);
         }
-        neighs
+        if self.neighbours.contains(&UP) {
+            return Some(UP);
+        }
+        None
     }
+}
 
-    fn is_valid_move(&self, pos: &Position, dir: &Direction) -> bool {
-        let new_x: i64 = pos.x as i64 + dir.x;
-        let new_y: i64 = pos.y as i64 + dir.y;
-        if new_x < 0 || new_y < 0 { 
-            return false;
-        }
-        if new_y as usize >= self.nodes.len() || new_x as usize >= self.nodes[0].len() {
-            return false;
-        }
-        return true;
+impl Graph {
+    fn neighbours_from(&self, pos: &XY) -> Vec<XY> {
+        self.nodes[*pos].neighbours.iter()
+            .map(|d| pos.add(&d.as_direction()))
+            .filter(|n| self.nodes.is_valid(n))
+            .collect()
     }
 
-    fn start_neighbours(&self) -> Vec<Position> {
-        let start_pos = self.start.clone();
+    fn start_neighbours(&mut self) -> Vec<XY> {
+        let start_pos = self.start;
         let mut neighs = vec![];
-        for d in vec![Direction::up(), Direction::left(), Direction::down(), Direction::right()] {
-            if !self.is_valid_move(&start_pos, &d) { continue }
-            let maybe_neigh = self.node_at(&start_pos.move_in(&d));
-            for pos in self.neighbours_from(&maybe_neigh.position) {
-                if pos == start_pos {
-                    neighs.push(maybe_neigh.position.clone());
-                    break
-                }
+        let mut dirs = HashSet::new();
+        for d in [UP, LEFT, DOWN, RIGHT] {
+            let maybe_neigh = start_pos.add(&d.as_direction());
+            if !self.nodes.is_valid(&maybe_neigh) { continue }
+            if self.neighbours_from(&maybe_neigh).contains(&start_pos) {
+                neighs.push(maybe_neigh);
+                dirs.insert(d);
             }
         }
+        self.nodes[start_pos].neighbours = dirs;
         neighs
     }
 
@@ -166,101 +101,128 @@ impl Graph {
         let mut queue = VecDeque::new();
 
         let start_neighbours = self.start_neighbours();
-        dprintln!("start neighbours: {:?}", start_neighbours);
+        debug!("start neighbours: {:?}", start_neighbours);
         for p in &start_neighbours {
             max_dist = 1;
-            let n = self.node_at_mut(p);
-            n.status = Status::Distance(1);
-            queue.push_back(p.clone());
+            self.nodes[*p].status = Status::Distance(1);
+            queue.push_back(*p);
         }
 
         while let Some(pos) = queue.pop_front() {
-            let curr_dist = if let Status::Distance(dist) = self.node_at(&pos).status {
+            let curr_dist = if let Status::Distance(dist) = self.nodes[pos].status {
                 dist
             } else {
-                panic!("Node without distance was on queue: {:?}", self.node_at(&pos))
+                panic!("Node without distance was on queue: {:?}", self.nodes[pos])
             };
             for npos in self.neighbours_from(&pos) {
-                let nnode = self.node_at_mut(&npos);
+                let nnode = &mut self.nodes[npos];
                 if nnode.status != Status::Unvisited {
                     continue
                 }
                 let new_dist = curr_dist + 1;
                 nnode.status = Status::Distance(new_dist);
                 max_dist = max(max_dist, new_dist);
-                queue.push_back(nnode.position.clone());
+                queue.push_back(npos);
             }
         }
 
         max_dist
     }
+
+    fn count_insides(&self) -> i64 {
+        let mut insides = 0;
+        for row in self.nodes.rows() {
+            let mut num_vert = 0;
+            let mut open_half = None;
+            for n in row {
+                match n.status {
+                    Status::Start | Status::Distance(_) => {
+                        if n.is_vertical() {
+                            num_vert += 1;
+                        } else if let Some(dir) = n.is_half_vertical() {
+                            open_half = match open_half {
+                                Some(hdir) => {
+                                    if hdir != dir {
+                                        num_vert += 1;
+                                    }
+                                    None
+                                },
+                                None => Some(dir),
+                            }
+                        }
+                    },
+                    _ => {
+                        if num_vert % 2 != 0 {
+                            insides += 1;
+                        }
+                    },
+                }
+            }
+        }
+        insides
+    }
 }
 
+impl Solution for Graph {
+    const DAY: u32 = crate::DAY;
 
-fn solve<R: BufRead, W: Write>(input: R, mut output: W) {
-    let lines = BufReader::new(input).lines().map(|l| l.unwrap());
-    let mut graph = Graph::from_lines(lines);
-    dprintln!("Graph: {:?}", graph);
+    fn parse<R: BufRead>(mut lines: Lines<R>) -> Result<Graph> {
+        let chars = parse::grid(
+            &lines.non_blank()?, |c| "|-LJ7F.S".contains(c).then_some(c), "a pipe, `.` or `S`")?;
 
-    writeln!(output, "{}", graph.furthest_on_loop()).unwrap();
-}
+        let nodes = chars.map(|c| Node::from_char(*c));
+
+        match nodes.find(|n| n.status == Status::Start) {
+            Some(start) => Ok(Graph {
+                nodes,
+                start,
+            }),
+            None => Err(lines.invalid("there is no starting position `S`")),
+        }
+    }
+
+    fn part1(&self) -> Result<impl Display> {
+        let mut graph = self.clone();
+        debug!("Graph: {:?}", graph);
+        Ok(graph.furthest_on_loop())
+    }
 
-pub fn main() {
-    let stdin = std::io::stdin();
-    let stdout = std::io::stdout();
-    solve(stdin.lock(), stdout.lock());
+    fn part2(&self) -> Result<impl Display> {
+        let mut graph = self.clone();
+        let _ = graph.furthest_on_loop();
+        Ok(graph.count_insides())
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use super::Graph;
+    use aoc::solution::solve_part1;
 
-    fn test_ignore_whitespaces(input: &str, output: &str) {
-        let mut actual_out: Vec<u8> = Vec::new();
-        solve(input.as_bytes(), &mut actual_out);
-        let actual_out_str = String::from_utf8(actual_out).unwrap();
-        let actual_outs = actual_out_str.split_whitespace().collect::<Vec<&str>>();
-        let expected_outs = output.split_whitespace().collect::<Vec<&str>>();
-        assert_eq!(actual_outs, expected_outs);
+    fn test_error(input: &str, error: &str) {
+        aoc::testing::test_error(|i, o| solve_part1::<Graph, _, _>(i, o), input, error);
     }
 
+    include!(concat!(env!("OUT_DIR"), "/examples/maze.rs"));
+
     #[test]
-    fn sample() {
-        test_ignore_whitespaces(
-            ".....
-            .S-7.
+    fn malformed() {
+        test_error(
+            ".F-7.
             .|.|.
-            .L-J.
-            .....",
-            "4",
-        );
-        test_ignore_whitespaces(
-            "-L|F7
-            7S-7|
-            L|7||
-            -L-J|
-            L|-JF",
-            "4",
+            .L-J.",
+            "day 10: there is no starting position `S`",
         );
-    }
-
-    #[test]
-    fn sample2() {
-        test_ignore_whitespaces(
-            "..F7.
-            .FJ|.
-            SJ.L7
-            |F--J
-            LJ...",
-            "8",
+        test_error(
+            ".S-7.
+            .|.|
+            .L-J.",
+            "day 10, line 2, column 1: expected a row of 5 characters, found 4 characters",
         );
-        test_ignore_whitespaces(
-            "7-F7-
-            .FJ|7
-            SJLL7
-            |F--J
-            LJ.LJ",
-            "8",
+        test_error(
+            ".S-7.
+            .|x|.",
+            "day 10, line 2, column 15: expected a pipe, `.` or `S`, found `x|.`",
         );
     }
 }
@@ -1,27 +1,10 @@
 use std::cmp::max;
-use std::io::{BufRead, BufReader, Write};
+use std::io::{BufRead, Write};
 use std::collections::{HashSet, VecDeque};
-
-macro_rules! dprintln {
-    ( $( $x:expr ),* ) => {
-        {
-	    #[cfg(test)]
-            println!($($x), *);
-        }
-    };
-}
-
-#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
-struct Position {
-    x: usize,
-    y: usize,
-}
-
-#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
-struct Direction {
-    x: i64,
-    y: i64,
-}
+use aoc::debug;
+use aoc::{Direction, Grid, XY};
+use aoc::Direction::{UP, RIGHT, DOWN, LEFT};
+use aoc::parse::{self, Lines, Result};
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
 enum Status {
@@ -41,7 +24,6 @@ enum InsideStatus {
 
 #[derive(Debug)]
 struct Node {
-    position: Position,
     neighbours: HashSet<Direction>,
     status: Status,
     inside_status: InsideStatus,
@@ -53,63 +35,33 @@ struct Node {
 
 #[derive(Debug)]
 struct Graph {
-    nodes: Vec<Vec<Node>>,
-    start: Position,
-}
-
-impl Position {
-    fn from(x: usize, y: usize) -> Position { Position {x, y}}
-    fn move_in(&self, d: &Direction) -> Position { Position {
-        x: (self.x as i64 + d.x) as usize,
-        y: (self.y as i64 + d.y) as usize,
-    } }
-}
-
-impl Direction {
-    const LEFT: Direction = Direction::left();
-    const RIGHT: Direction = Direction::right();
-    const UP: Direction = Direction::up();
-    const DOWN: Direction = Direction::down();
-
-    const fn from(x: i64, y: i64) -> Direction { Direction {x, y}}
-
-    const fn up() -> Direction { Direction::from(0, -1) }
-    const fn down() -> Direction { Direction::from(0, 1) }
-    const fn left() -> Direction { Direction::from(-1, 0) }
-    const fn right() -> Direction { Direction::from(1, 0) }
-
-    fn from_to(from: &Position, to: &Position) -> Direction {
-        Direction {
-            x: to.x as i64 - from.x as i64,
-            y: to.y as i64- from.y as i64,
-        }
-    }
+    nodes: Grid<Node>,
+    start: XY,
 }
 
 impl Node {
     fn sides_for(c: char) -> Vec<HashSet<Direction>> {
         match c {
-            '|' => vec![HashSet::from([Direction::left()]), HashSet::from([Direction::right()])],
-            '-' => vec![HashSet::from([Direction::up()]), HashSet::from([Direction::down()])],
-            'L' => vec![HashSet::from([Direction::left(), Direction::down()]), HashSet::from([])],
-            'J' => vec![HashSet::from([]), HashSet::from([Direction::right(), Direction::down()])],
-            '7' => vec![HashSet::from([]), HashSet::from([Direction::up(), Direction::right()])],
-            'F' => vec![HashSet::from([Direction::up(), Direction::left()]), HashSet::from([])],
+            '|' => vec![HashSet::from([LEFT]), HashSet::from([RIGHT])],
+            '-' => vec![HashSet::from([UP]), HashSet::from([DOWN])],
+            'L' => vec![HashSet::from([LEFT, DOWN]), HashSet::from([])],
+            'J' => vec![HashSet::from([]), HashSet::from([RIGHT, DOWN])],
+            '7' => vec![HashSet::from([]), HashSet::from([UP, RIGHT])],
+            'F' => vec![HashSet::from([UP, LEFT]), HashSet::from([])],
             '.' | 'S'  => vec![HashSet::from([]), HashSet::from([])],
             _ => panic!("Don't know this input!"),
         }
     }
 
-    fn from_char(c: char, pos: &Position) -> Node {
+    fn from_char(c: char) -> Node {
         Node {
-            position: pos.clone(),
             neighbours: match c {
-                '|' => HashSet::from([ Direction::up(), Direction::down() ]),
-                '-' => HashSet::from([ Direction::left(), Direction::right() ]),
-                'L' => HashSet::from([ Direction::up(), Direction::right() ]),
-                'J' => HashSet::from([ Direction::up(), Direction::left() ]),
-                '7' => HashSet::from([ Direction::down(), Direction::left() ]),
-                'F' => HashSet::from([ Direction::down(), Direction::right() ]),
+                '|' => HashSet::from([ UP, DOWN ]),
+                '-' => HashSet::from([ LEFT, RIGHT ]),
+                'L' => HashSet::from([ UP, RIGHT ]),
+                'J' => HashSet::from([ UP, LEFT ]),
+                '7' => HashSet::from([ DOWN, LEFT ]),
+                'F' => HashSet::from([ DOWN, RIGHT ]),
                 '.' => HashSet::new(),
                 'S' => HashSet::new(),
                 _ => panic!("Don't know this input!"),
@@ -135,7 +87,7 @@ impl Node {
         } else if self.sides[inside].contains(dir) {
             return Some(InsideStatus::Inside);
         }
-        return None
+        None
     }
 
     fn mark_outside(&mut self, dir: &Direction) {
@@ -180,146 +132,92 @@ impl Node {
 }
 
 impl Graph {
-    fn from_lines<I>(lines: I) -> Graph
-        where I: Iterator<Item = String>,
-    {
-        let mut nodes = Vec::new();
-        let mut start = Position::from(usize::MAX, usize::MAX);
-        for (y, line) in lines.enumerate() {
-            nodes.push(Vec::new());
-            for (x, c) in line.trim().char_indices() {
-                let pos = Position::from(x, y);
-                let node = Node::from_char(c, &pos);
-                if node.status == Status::Start {
-                    start = pos.clone();
-                }
-                nodes[y].push(node);
-            }
-        }
+    fn parse<R: BufRead>(mut lines: Lines<R>) -> Result<Graph> {
+        let chars = parse::grid(
+            &lines.non_blank()?, |c| "|-LJ7F.S".contains(c).then_some(c), "a pipe, `.` or `S`")?;
 
-        Graph {
-            nodes: nodes,
-            start: start,
-        }
-    }
+        let nodes = chars.map(|c| Node::from_char(*c));
 
-    fn node_at(&self, pos: &Position) -> &Node {
-        &self.nodes[pos.y][pos.x]
+        match nodes.find(|n| n.status == Status::Start) {
+            Some(start) => Ok(Graph {
+                nodes,
+                start,
+            }),
+            None => Err(lines.invalid("there is no starting position `S`")),
+        }
     }
 
-    fn node_at_mut(&mut self, pos: &Position) -> &mut Node {
-        &mut self.nodes[pos.y][pos.x]
+    /// The positions next to `at` in `dirs` which are on the grid.
+    fn moves<'a>(&self, at: &XY, dirs: impl IntoIterator<Item = &'a Direction>) -> Vec<XY> {
+        dirs.into_iter()
+            .map(|d| at.add(&d.as_direction()))
+            .filter(|p| self.nodes.is_valid(p))
+            .collect()
     }
 
-    fn outside_adjacents(&self, at: &Position) -> Vec<Position> {
-        let node = self.node_at(at);
+    fn outside_adjacents(&self, at: &XY) -> Vec<XY> {
+        let node = &self.nodes[*at];
         let outside = match node.outside_side {
             Some(o) => o,
             _ => panic!("outside not marked for: {:?}", node),
         };
 
-        return node.sides[outside].iter().filter(|d| self.is_valid_move(at, &d)).
-            map(|d| at.move_in(&d)).collect();
+        self.moves(at, &node.sides[outside])
     }
 
-    fn inside_adjacents(&self, at: &Position) -> Vec<Position> {
-        let node = self.node_at(at);
+    fn inside_adjacents(&self, at: &XY) -> Vec<XY> {
+        let node = &self.nodes[*at];
         let outside = match node.outside_side {
             Some(o) => o,
             _ => panic!("inside not marked for: {:?}", node),
         };
         let inside = (outside + 1) % 2;
 
-        return node.sides[inside].iter().filter(|d| self.is_valid_move(at, &d)).
-            map(|d| at.move_in(&d)).collect();
-    }
-
-    fn pipe_adjacents(&self, at: &Position) -> Vec<Position> {
-        let mut pipes = vec![];
-        let node = self.node_at(at);
-        for d in vec![Direction::up(), Direction::left(), Direction::down(), Direction::right()] {
-            if node.sides[0].contains(&d) || node.sides[0].contains(&d) {
-                continue
-            }
-            if self.is_valid_move(at, &d) {
-                pipes.push(at.move_in(&d));
-            }
-        }
-        pipes
-    }
-
-    fn neighbours_from(&self, pos: &Position) -> Vec<Position> {
-        if pos.y >= self.nodes.len() || pos.y >= self.nodes[0].len() {
-            panic!("Trying to access non-existing position")
-        }
-
-        let mut neighs = vec![];
-        for d in &self.node_at(pos).neighbours {
-            if self.is_valid_move(pos, d) {
-                neighs.push(pos.move_in(&d));
-            }
-        }
-        neighs
+        self.moves(at, &node.sides[inside])
     }
 
-    fn adjacents(&self, pos: &Position) -> Vec<Position> {
-        if pos.y >= self.nodes.len() || pos.y >= self.nodes[0].len() {
-            panic!("Trying to access non-existing position")
-        }
-        let mut adjs = vec![];
-        for d in vec![Direction::up(), Direction::left(), Direction::down(), Direction::right()] {
-            if self.is_valid_move(pos, &d) {
-                adjs.push(pos.move_in(&d));
-            }
-        }
-        adjs
+    fn pipe_adjacents(&self, at: &XY) -> Vec<XY> {
+        let node = &self.nodes[*at];
+        let dirs: Vec<_> = [UP, LEFT, DOWN, RIGHT].into_iter()
+            .filter(|d| !node.is_side(d))
+            .collect();
+        self.moves(at, &dirs)
     }
 
-    fn is_valid_move(&self, pos: &Position, dir: &Direction) -> bool {
-        let new_x: i64 = pos.x as i64 + dir.x;
-        let new_y: i64 = pos.y as i64 + dir.y;
-        if new_x < 0 || new_y < 0 { 
-            return false;
-        }
-        if new_y as usize >= self.nodes.len() || new_x as usize >= self.nodes[0].len() {
-            return false;
-        }
-        return true;
+    fn neighbours_from(&self, pos: &XY) -> Vec<XY> {
+        self.moves(pos, &self.nodes[*pos].neighbours)
     }
 
-    fn start_neighbours(&mut self) -> Vec<Position> {
-        let start_pos = self.start.clone();
+    fn start_neighbours(&mut self) -> Vec<XY> {
+        let start_pos = self.start;
         let mut neighs = vec![];
         let mut dirs = HashSet::new();
-        for d in vec![Direction::up(), Direction::left(), Direction::down(), Direction::right()] {
-            if !self.is_valid_move(&start_pos, &d) { continue }
-            let maybe_neigh = self.node_at(&start_pos.move_in(&d));
-            for pos in self.neighbours_from(&maybe_neigh.position) {
-                if pos == start_pos {
-                    neighs.push(maybe_neigh.position.clone());
-                    dirs.insert(d);
-                    break
-                }
+        for d in [UP, LEFT, DOWN, RIGHT] {
+            let maybe_neigh = start_pos.add(&d.as_direction());
+            if !self.nodes.is_valid(&maybe_neigh) { continue }
+            if self.neighbours_from(&maybe_neigh).contains(&start_pos) {
+                neighs.push(maybe_neigh);
+                dirs.insert(d);
             }
         }
 
-        let start = self.node_at_mut(&start_pos);
-        if HashSet::from([Direction::up(), Direction::down()]) == dirs {
+        let start = &mut self.nodes[start_pos];
+        if HashSet::from([UP, DOWN]) == dirs {
             start.sides = Node::sides_for('|');
         }
-        if HashSet::from([Direction::left(), Direction::right()]) == dirs {
+        if HashSet::from([LEFT, RIGHT]) == dirs {
             start.sides = Node::sides_for('-');
         }
-        if HashSet::from([Direction::up(), Direction::right()]) == dirs {
+        if HashSet::from([UP, RIGHT]) == dirs {
             start.sides = Node::sides_for('L');
         }
-        if HashSet::from([Direction::up(), Direction::left()]) == dirs {
+        if HashSet::from([UP, LEFT]) == dirs {
             start.sides = Node::sides_for('J');
         }
-        if HashSet::from([Direction::left(), Direction::down()]) == dirs {
+        if HashSet::from([LEFT, DOWN]) == dirs {
             start.sides = Node::sides_for('7');
         }
-        if HashSet::from([Direction::right(), Direction::down()]) == dirs {
+        if HashSet::from([RIGHT, DOWN]) == dirs {
             start.sides = Node::sides_for('F');
         }
 
@@ -333,96 +231,95 @@ impl Graph {
         let start_neighbours = self.start_neighbours();
         for p in &start_neighbours {
             max_dist = 1;
-            let n = self.node_at_mut(p);
-            n.status = Status::Distance(1);
-            queue.push_back(p.clone());
+            self.nodes[*p].status = Status::Distance(1);
+            queue.push_back(*p);
         }
 
         while let Some(pos) = queue.pop_front() {
-            let curr_dist = if let Status::Distance(dist) = self.node_at(&pos).status {
+            let curr_dist = if let Status::Distance(dist) = self.nodes[pos].status {
                 dist
             } else {
-                panic!("Node without distance was on queue: {:?}", self.node_at(&pos))
+                panic!("Node without distance was on queue: {:?}", self.nodes[pos])
             };
             for npos in self.neighbours_from(&pos) {
-                let nnode = self.node_at_mut(&npos);
+                let nnode = &mut self.nodes[npos];
                 if nnode.status != Status::Unvisited {
                     continue
                 }
                 let new_dist = curr_dist + 1;
                 nnode.status = Status::Distance(new_dist);
                 max_dist = max(max_dist, new_dist);
-                queue.push_back(nnode.position.clone());
+                queue.push_back(npos);
             }
         }
 
         max_dist
     }
 
-    fn parameter_positions(&self) -> Vec<(Direction, Position)> {
-        let max_x = self.nodes[0].len() - 1;
-        let max_y = self.nodes.len() - 1;
+    fn parameter_positions(&self) -> Vec<(Direction, XY)> {
+        let max_x = self.nodes.width() - 1;
+        let max_y = self.nodes.height() - 1;
 
-        let horizontal_top = (0..self.nodes[0].len()).map(|x| Position::from(x, 0)).
-            map(|p| (Direction::up(), p));
-        let horizontal_bottom = (0..self.nodes[0].len()).map(|x| Position::from(x, max_y)).
-            map(|p| (Direction::down(), p));
-        let vertical_left = (0..self.nodes.len()).map(|y| Position::from(0, y)).
-            map(|p| (Direction::left(), p));
-        let vertical_right= (0..self.nodes.len()).map(|y| Position::from(max_x, y)).
-            map(|p| (Direction::right(), p));
+        let horizontal_top = (0..self.nodes.width()).map(|x| XY::newu(x, 0)).
+            map(|p| (UP, p));
+        let horizontal_bottom = (0..self.nodes.width()).map(|x| XY::newu(x, max_y)).
+            map(|p| (DOWN, p));
+        let vertical_left = (0..self.nodes.height()).map(|y| XY::newu(0, y)).
+            map(|p| (LEFT, p));
+        let vertical_right= (0..self.nodes.height()).map(|y| XY::newu(max_x, y)).
+            map(|p| (RIGHT, p));
 
         horizontal_top.chain(horizontal_bottom).chain(vertical_left).chain(vertical_right).collect()
     }
 
-    fn propagate_outsides(&mut self, from: &Position, to: &Position) {
-        let dir = Direction::from_to(from, to);
+    fn propagate_outsides(&mut self, from: &XY, to: &XY) {
+        let dir = Direction::from(to.sub(from));
 
         let mut propagated = false;
 
         // Horizontal
-        if dir.y == 0 {
-            if let Some(side) = self.node_at(from).side_in(&Direction::UP) {
-                let node_to = self.node_at_mut(to);
-                if node_to.is_side(&Direction::UP) {
-                    node_to.mark(&Direction::UP, side);
+        if matches!(dir, LEFT | RIGHT) {
+            if let Some(side) = self.nodes[*from].side_in(&UP) {
+                let node_to = &mut self.nodes[*to];
+                if node_to.is_side(&UP) {
+                    node_to.mark(&UP, side);
                     propagated = true;
                 } else {
-                    node_to.mark_inverted(&Direction::DOWN, side);
+                    node_to.mark_inverted(&DOWN, side);
                     propagated = true;
                 }
             }
             if !propagated {
-                if let Some(side) = self.node_at(from).side_in(&Direction::DOWN) {
-                    let node_to = self.node_at_mut(to);
-                    if node_to.is_side(&Direction::DOWN) {
-                        self.node_at_mut(to).mark(&Direction::DOWN, side);
+                if let Some(side) = self.nodes[*from].side_in(&DOWN) {
+                    let node_to = &mut self.nodes[*to];
+                    if node_to.is_side(&DOWN) {
+                        self.nodes[*to].mark(&DOWN, side);
                         propagated = true;
                     } else {
-                        node_to.mark_inverted(&Direction::UP, side);
+                        node_to.mark_inverted(&UP, side);
                         propagated = true;
                     }
                 }
             }
         } else {
-            if let Some(side) = self.node_at(from).side_in(&Direction::LEFT) {
-                let node_to = self.node_at_mut(to);
-                if node_to.is_side(&Direction::LEFT) {
-                    self.node_at_mut(to).mark(&Direction::LEFT, side);
+            if let Some(side) = self.nodes[*from].side_in(&LEFT) {
+                let node_to = &mut self.nodes[*to];
+                if node_to.is_side(&LEFT) {
+                    self.nodes[*to].mark(&LEFT, side);
                     propagated = true;
                 } else {
-                    node_to.mark_inverted(&Direction::RIGHT, side);
+                    node_to.mark_inverted(&RIGHT, side);
                     propagated = true;
                 }
             } 
             if !propagated {
-                if let Some(side) = self.node_at(from).side_in(&Direction::RIGHT) {
-                    let node_to = self.node_at_mut(to);
-                    if node_to.is_side(&Direction::RIGHT) {
-                        self.node_at_mut(to).mark(&Direction::RIGHT, side);
+                if let Some(side) = self.nodes[*from].side_in(&RIGHT) {
+                    let node_to = &mut self.nodes[*to];
+                    if node_to.is_side(&RIGHT) {
+                        self.nodes[*to].mark(&RIGHT, side);
                         propagated = true;
                     } else {
-                        node_to.mark_inverted(&Direction::LEFT, side);
+                        node_to.mark_inverted(&LEFT, side);
                         propagated = true;
                     }
                 }
@@ -440,50 +337,50 @@ impl Graph {
         let mut inside_queue = VecDeque::new();
 
         for (dir, pos) in self.parameter_positions() {
-            let node = self.node_at_mut(&pos);
+            let node = &mut self.nodes[pos];
             match node.status {
                 Status::Start | Status::Distance(_) => {
                     node.inside_status = InsideStatus::Pipe;
                     node.mark_outside(&dir);
-                    inside_queue.push_back(node.position.clone());
+                    inside_queue.push_back(pos);
                 }
                 Status::Empty | Status::Unvisited => {
                     node.inside_status = InsideStatus::Outside;
-                    outside_queue.push_back(node.position.clone());
+                    outside_queue.push_back(pos);
                 },
             }
         }
 
         while let Some(pos) = outside_queue.pop_front() {
-            for adj_pos in self.adjacents(&pos) {
-                let anode = self.node_at_mut(&adj_pos);
+            for adj_pos in self.nodes.neighbours(&pos) {
+                let anode = &mut self.nodes[adj_pos];
                 if anode.inside_status != InsideStatus::Unknown {
                     continue
                 }
                 match anode.status {
                     Status::Start | Status::Distance(_) => {
                         anode.inside_status = InsideStatus::Pipe;
-                        anode.mark_outside(&Direction::from_to(&anode.position, &pos));
-                        inside_queue.push_back(anode.position.clone());
+                        anode.mark_outside(&Direction::from(pos.sub(&adj_pos)));
+                        inside_queue.push_back(adj_pos);
                     }
                     Status::Empty | Status::Unvisited => {
                         anode.inside_status = InsideStatus::Outside;
-                        outside_queue.push_back(anode.position.clone());
+                        outside_queue.push_back(adj_pos);
                     },
                 }
             }
         }
 
-        fn handle_adjacent(graph: &mut Graph, from: &Position, pos: &Position, s: InsideStatus,
-                queue: &mut VecDeque<Position>, insides: &mut i64) {
-            let anode = graph.node_at_mut(pos);
+        fn handle_adjacent(graph: &mut Graph, from: &XY, pos: &XY, s: InsideStatus,
+                queue: &mut VecDeque<XY>, insides: &mut i64) {
+            let anode = &mut graph.nodes[*pos];
             if anode.inside_status != InsideStatus::Unknown {
                 return
             }
             match anode.status {
                 Status::Start | Status::Distance(_) => {
                     anode.inside_status = InsideStatus::Pipe;
-                    let dir_back = Direction::from_to(pos, from);
+                    let dir_back = Direction::from(from.sub(pos));
                     anode.mark(&dir_back, s);
                 },
                 Status::Empty | Status::Unvisited => {
@@ -493,11 +390,11 @@ impl Graph {
                     }
                 },
             }
-            queue.push_back(anode.position.clone());
+            queue.push_back(*pos);
         }
 
         while let Some(pos) = inside_queue.pop_front() {
-            let node = self.node_at(&pos);
+            let node = &self.nodes[pos];
             match node.inside_status {
                 InsideStatus::Pipe => {
                     for o in self.outside_adjacents(&pos) {
@@ -510,7 +407,7 @@ impl Graph {
                     }
                     for p in self.pipe_adjacents(&pos) {
                         {
-                            let pnode = self.node_at_mut(&p);
+                            let pnode = &mut self.nodes[p];
                             if pnode.inside_status != InsideStatus::Unknown {
                                 continue
                             }
@@ -524,7 +421,7 @@ impl Graph {
                     panic!("There should be no unknowns here. Node: {:?}", node)
                 },
                 s => {
-                    for adj_pos in self.adjacents(&pos) {
+                    for adj_pos in self.nodes.neighbours(&pos) {
                         handle_adjacent(self, &pos, &adj_pos, s, &mut inside_queue, &mut insides);
                     }
                 },
@@ -536,111 +433,30 @@ impl Graph {
 }
 
 
-fn solve<R: BufRead, W: Write>(input: R, mut output: W) {
-    let lines = BufReader::new(input).lines().map(|l| l.unwrap());
-    let mut graph = Graph::from_lines(lines);
-    dprintln!("Graph: {:?}", graph);
+pub fn solve<R: BufRead, W: Write>(input: R, mut output: W) -> Result<()> {
+    let mut graph = Graph::parse(Lines::new(input, crate::DAY))?;
+    debug!("Graph: {:?}", graph);
     let _ = graph.furthest_on_loop();
 
-    writeln!(output, "{}", graph.find_insides()).unwrap();
-}
-
-pub fn main() {
-    let stdin = std::io::stdin();
-    let stdout = std::io::stdout();
-    solve(stdin.lock(), stdout.lock());
+    writeln!(output, "{}", graph.find_insides())?;
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    fn test_ignore_whitespaces(input: &str, output: &str) {
-        let mut actual_out: Vec<u8> = Vec::new();
-        solve(input.as_bytes(), &mut actual_out);
-        let actual_out_str = String::from_utf8(actual_out).unwrap();
-        let actual_outs = actual_out_str.split_whitespace().collect::<Vec<&str>>();
-        let expected_outs = output.split_whitespace().collect::<Vec<&str>>();
-        assert_eq!(actual_outs, expected_outs);
-    }
-
-    #[test]
-    fn sample() {
-        test_ignore_whitespaces(
-            "...........
-            .S-------7.
-            .|F-----7|.
-            .||.....||.
-            .||.....||.
-            .|L-7.F-J|.
-            .|..|.|..|.
-            .L--J.L--J.
-            ...........",
-            "4",
-        );
-    }
-
-    #[test]
-    fn sample2() {
-        test_ignore_whitespaces(
-            "..........
-            .S------7.
-            .|F----7|.
-            .||....||.
-            .||....||.
-            .|L-7F-J|.
-            .|..||..|.
-            .L--JL--J.
-            ..........",
-            "4",
-        );
-    }
-
     #[test]
-    fn sample3() {
-        test_ignore_whitespaces(
-            ".F----7F7F7F7F-7....
-            .|F--7||||||||FJ....
-            .||.FJ||||||||L7....
-            FJL7L7LJLJ||LJ.L-7..
-            L--J.L7...LJS7F-7L7.
-            ....F-J..F7FJ|L7L7L7
-            ....L7.F7||L7|.L7L7|
-            .....|FJLJ|FJ|F7|.LJ
-            ....FJL-7.||.||||...
-            ....L---J.LJ.LJLJ...",
-            "8",
-        );
-    }
-
-    #[test]
-    fn sample4() {
-        test_ignore_whitespaces(
-            "FF7FSF7F7F7F7F7F---7
-            L|LJ||||||||||||F--J
-            FL-7LJLJ||||||LJL-77
-            F--JF--7||LJLJ7F7FJ-
-            L---JF-JLJ.||-FJLJJ7
-            |F|F-JF---7F7-L7L|7|
-            |FFJF7L7F-JF7|JL---7
-            7-L-JL7||F7|L7F-7F7|
-            L.L7LFJ|||||FJL7||LJ
-            L7JLJL-JLJLJL--JLJ.L",
-            "10",
-        );
-    }
-
-    #[test]
-    fn mine() {
-        test_ignore_whitespaces(
-            "S-----7
-            |.F-7.|
-            |.|.|.|
-            |.|FJ.|
-            |.||..|
-            |.|L--J
-            L-J....",
-            "10",
-        );
+    fn samples() {
+        // The same as part 2 of `maze`.
+        for (input, output) in [
+            (include_str!("../examples/maze.insides1.in"), include_str!("../examples/maze.insides1.part2.out")),
+            (include_str!("../examples/maze.insides2.in"), include_str!("../examples/maze.insides2.part2.out")),
+            (include_str!("../examples/maze.insides3.in"), include_str!("../examples/maze.insides3.part2.out")),
+            (include_str!("../examples/maze.insides4.in"), include_str!("../examples/maze.insides4.part2.out")),
+            (include_str!("../examples/maze.mine.in"), include_str!("../examples/maze.mine.part2.out")),
+        ] {
+            aoc::testing::test_example(|i, o| solve(i, o), input, output);
+        }
     }
 }
@@ -1,24 +1,19 @@
 //use std::cmp::{max, min};
-use std::io::{BufRead, BufReader, Write};
 use std::collections::{HashMap};
+use std::fmt::Display;
+use std::io::BufRead;
+use aoc::debug;
+use aoc::parse::{Lines, Result};
+use aoc::solution::Solution;
 
-macro_rules! dprintln {
-    ( $( $x:expr ),* ) => {
-        {
-	    #[cfg(test)]
-            println!($($x), *);
-        }
-    };
-}
-
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct Galaxy {
     x: usize,
     y: usize,
 }
 
-#[derive(Debug)]
-struct GalaxyMap {
+#[derive(Debug, Clone)]
+pub struct GalaxyMap {
     empty_rows: Vec<usize>,
     empty_columns: Vec<usize>,
 
@@ -30,50 +25,14 @@ impl Galaxy {
 }
 
 impl GalaxyMap {
-    fn from_input<I>(lines: I) -> GalaxyMap
-        where I: Iterator<Item = String>
-    {
-
-        let mut galaxies_per_col = HashMap::new();
-        let mut galaxies = Vec::new();
-        let mut empty_rows = Vec::new();
-        for (row, l) in lines.enumerate() {
-            let line = l.trim();
-            let mut galaxies_per_row = 0;
-
-            for (col, c) in line.char_indices() {
-                let entry = galaxies_per_col.entry(col).or_insert(0);
-                match c {
-                    '#' => {
-                        galaxies_per_row += 1;
-                        *entry += 1;
-                        galaxies.push(Galaxy::from_coords(col, row));
-                    },
-                    _ => { }
-                }
-            }
-            if galaxies_per_row == 0 {
-                empty_rows.push(row);
-            }
-        }
-        let mut empty_columns = galaxies_per_col.iter()
-            .filter(|(_, &num)| num == 0).map(|(col, _)| *col).collect::<Vec<usize>>();
-        empty_columns.sort();
-        GalaxyMap {
-            empty_rows: empty_rows,
-            empty_columns: empty_columns,
-
-            galaxies: galaxies,
-        }
-    }
-
-    fn expand(&mut self) {
+    /// Makes every empty row and column `expansion_ratio` times as big.
+    fn expand(&mut self, expansion_ratio: usize) {
         let mut row_idx = 0;
         for g in &mut self.galaxies {
             while row_idx < self.empty_rows.len() && g.y > self.empty_rows[row_idx] {
                 row_idx += 1;
             }
-            g.y += row_idx;
+            g.y += row_idx * (expansion_ratio - 1);
         }
 
         self.galaxies.sort_by_key(|g| g.x);
@@ -83,7 +42,7 @@ impl GalaxyMap {
             while col_idx < self.empty_columns.len() && g.x > self.empty_columns[col_idx] {
                 col_idx += 1;
             }
-            g.x += col_idx;
+            g.x += col_idx * (expansion_ratio - 1);
         }
     }
 
@@ -99,57 +58,77 @@ impl GalaxyMap {
         }
         sum
     }
+
+    fn sum_expanded_distances(&self, expansion_ratio: usize) -> i64 {
+        let mut galaxy_map = self.clone();
+        galaxy_map.expand(expansion_ratio);
+        debug!("Expanded: {:?}", galaxy_map);
+        galaxy_map.sum_distances()
+    }
 }
 
-fn solve<R: BufRead, W: Write>(input: R, mut output: W) {
-    let lines_it = BufReader::new(input).lines().map(|l| l.unwrap());
-    let mut galaxy_map = GalaxyMap::from_input(lines_it);
-    dprintln!("Map: {:?}", galaxy_map);
-    galaxy_map.expand();
-    dprintln!("Expanded: {:?}", galaxy_map);
+impl Solution for GalaxyMap {
+    const DAY: u32 = crate::DAY;
 
-    writeln!(output, "{}", galaxy_map.sum_distances()).unwrap();
-}
+    fn parse<R: BufRead>(lines: Lines<R>) -> Result<GalaxyMap> {
+
+        let mut galaxies_per_col = HashMap::new();
+        let mut galaxies = Vec::new();
+        let mut empty_rows = Vec::new();
+        for (row, line) in lines.enumerate() {
+            let is_galaxy = line?.row(|c| match c {
+                '#' => Some(true),
+                '.' => Some(false),
+                _ => None,
+            }, "`#` or `.`")?;
+            let mut galaxies_per_row = 0;
+
+            for (col, galaxy) in is_galaxy.into_iter().enumerate() {
+                let entry = galaxies_per_col.entry(col).or_insert(0);
+                if galaxy {
+                    galaxies_per_row += 1;
+                    *entry += 1;
+                    galaxies.push(Galaxy::from_coords(col, row));
+                }
+            }
+            if galaxies_per_row == 0 {
+                empty_rows.push(row);
+            }
+        }
+        let mut empty_columns = galaxies_per_col.iter()
+            .filter(|(_, &num)| num == 0).map(|(col, _)| *col).collect::<Vec<usize>>();
+        empty_columns.sort();
+        let galaxy_map = GalaxyMap {
+            empty_rows,
+            empty_columns,
+
+            galaxies,
+        };
+        debug!("Map: {:?}", galaxy_map);
+        Ok(galaxy_map)
+    }
+
+    fn part1(&self) -> Result<impl Display> {
+        Ok(self.sum_expanded_distances(2))
+    }
 
-pub fn main() {
-    let stdin = std::io::stdin();
-    let stdout = std::io::stdout();
-    solve(stdin.lock(), stdout.lock());
+    fn part2(&self) -> Result<impl Display> {
+        Ok(self.sum_expanded_distances(1000000))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    fn test_exact(input: &str, output: &str) {
-        let mut actual_out: Vec<u8> = Vec::new();
-        solve(input.as_bytes(), &mut actual_out);
-        assert_eq!(String::from_utf8(actual_out).unwrap(), output);
-    }
+    const SAMPLE: &str = include_str!("../examples/galaxies.sample.in");
 
-    fn test_ignore_whitespaces(input: &str, output: &str) {
-        let mut actual_out: Vec<u8> = Vec::new();
-        solve(input.as_bytes(), &mut actual_out);
-        let actual_out_str = String::from_utf8(actual_out).unwrap();
-        let actual_outs = actual_out_str.split_whitespace().collect::<Vec<&str>>();
-        let expected_outs = output.split_whitespace().collect::<Vec<&str>>();
-        assert_eq!(actual_outs, expected_outs);
-    }
+    include!(concat!(env!("OUT_DIR"), "/examples/galaxies.rs"));
 
     #[test]
-    fn sample() {
-        test_ignore_whitespaces(
-            "...#......
-            .......#..
-            #.........
-            ..........
-            ......#...
-            .#........
-            .........#
-            ..........
-            .......#..
-            #...#.....",
-            "374",
-        );
+    fn expansion_ratios() {
+        let galaxy_map = GalaxyMap::parse(Lines::new(SAMPLE.as_bytes(), crate::DAY)).unwrap();
+        assert_eq!(galaxy_map.sum_expanded_distances(10), 1030);
+        assert_eq!(galaxy_map.sum_expanded_distances(100), 8410);
     }
 }
@@ -0,0 +1,5 @@
+pub const DAY: u32 = 11;
+
+pub mod galaxies;
+
+pub type Puzzle = galaxies::GalaxyMap;
@@ -1,6 +1,3 @@
-mod galaxies2;
-
-
 fn main() {
-    galaxies2::main();
+    aoc::solution::main::<day11::Puzzle>();
 }
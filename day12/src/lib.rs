@@ -0,0 +1,6 @@
+pub const DAY: u32 = 12;
+
+pub mod springs;
+pub mod springs_brut;
+
+pub type Puzzle = springs::Records;
@@ -1,6 +1,3 @@
-mod springs2;
-
-
 fn main() {
-    springs2::main();
+    aoc::solution::main::<day12::Puzzle>();
 }
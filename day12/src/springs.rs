@@ -1,35 +1,52 @@
 //use std::cmp::{min, max};
-use std::io::{BufRead, BufReader, Write};
+use std::fmt::Display;
+use std::io::BufRead;
+use std::collections::HashMap;
+use aoc::debug;
+use aoc::parse::{Line, Lines, Result};
+use aoc::solution::Solution;
 
+#[derive(Debug)]
+struct Cache {
+    results: HashMap<(usize, usize, usize), i64>,
+}
 
-macro_rules! dprintln {
-    ( $( $x:expr ),* ) => {
-        {
-	    #[cfg(test)]
-            println!($($x), *);
+impl Cache {
+    fn new() -> Cache {
+        Cache {
+            results: HashMap::new(),
         }
-    };
+    }
+
+    fn cached(&self, chars: &[char], to_fit: &[usize], prefix_hashes: usize) -> Option<i64> {
+        self.results.get(&(chars.len(), to_fit.len(), prefix_hashes)).copied()
+    }
+
+    fn insert(&mut self, val: i64, chars: &[char], to_fit: &[usize], prefix_hashes: usize) {
+        self.results.insert((chars.len(), to_fit.len(), prefix_hashes), val);
+    }
 }
 
-fn arrangements(chars: &[char], to_fit: &[usize], prefix_hashes: usize) -> i64 {
-    if to_fit.is_empty() {
-        if chars.contains(&'#') {
-            return 0;
+#[derive(Debug)]
+struct Counter {
+    cache: Cache,
+}
+
+impl Counter {
+    fn new() -> Counter {
+        Counter {
+            cache: Cache::new(),
         }
-        return 1;
-    }
-    if chars.is_empty() {
-        return 0;
     }
 
-    fn handle_dot(chars: &[char], to_fit: &[usize], prefix_hashes: usize) -> i64 {
+    fn handle_dot(&mut self, chars: &[char], to_fit: &[usize], prefix_hashes: usize) -> i64 {
         if prefix_hashes > 0 {
             return 0;
         }
-        arrangements(&chars[1..], to_fit, 0)
+        self.arrangements(&chars[1..], to_fit, 0)
     }
 
-    fn handle_hash(chars: &[char], to_fit: &[usize], prefix_hashes: usize) -> i64 {
+    fn handle_hash(&mut self, chars: &[char], to_fit: &[usize], prefix_hashes: usize) -> i64 {
         let hashes = prefix_hashes + 1;
         if hashes > to_fit[0] {
             panic!("we should catch that earlier");
@@ -45,90 +62,139 @@ fn arrangements(chars: &[char], to_fit: &[usize], prefix_hashes: usize) -> i64 {
             if chars[1] == '#' {
                 return 0;
             }
-            return arrangements(&chars[2..], &to_fit[1..], 0);
+            return self.arrangements(&chars[2..], &to_fit[1..], 0);
         }
-        arrangements(&chars[1..], to_fit, hashes)
+        self.arrangements(&chars[1..], to_fit, hashes)
     }
 
-    match chars[0] {
-        '.'=> handle_dot(chars, to_fit, prefix_hashes),
+    fn arrangements(&mut self, chars: &[char], to_fit: &[usize], prefix_hashes: usize) -> i64 {
+        if let Some(v) = self.cache.cached(chars, to_fit, prefix_hashes) {
+            return v;
+        }
+        if to_fit.is_empty() {
+            if chars.contains(&'#') {
+                return 0;
+            }
+            return 1;
+        }
 
-        '#'=> handle_hash(chars, to_fit, prefix_hashes),
-        '?' => {
-            let mut sol = 0;
-            // it is dot
-            sol += handle_dot(chars, to_fit, prefix_hashes);
-            // it is #
-            sol += handle_hash(chars, to_fit, prefix_hashes);
+        if chars.is_empty() {
+            return 0;
+        }
 
-            sol
-        },
-        c @ _ => panic!("unexpected char: {}", c)
+        let result = match chars[0] {
+            '.'=> self.handle_dot(chars, to_fit, prefix_hashes),
+            '#'=> self.handle_hash(chars, to_fit, prefix_hashes),
+            '?' => {
+                let mut sol = 0;
+                // it is dot
+                sol += self.handle_dot(chars, to_fit, prefix_hashes);
+                // it is #
+                sol += self.handle_hash(chars, to_fit, prefix_hashes);
+
+                sol
+            },
+            c => panic!("unexpected char: {}", c)
+        };
+        self.cache.insert(result, chars, to_fit, prefix_hashes);
+        result
     }
 }
 
-fn solve<R: BufRead, W: Write>(input: R, mut output: W) {
-    let mut solution: i64 = 0;
+fn multifold<I>(base: &[I], separator: Option<I>, times: usize) -> Vec<I>
+where I: Clone
+{
+    let mut result = Vec::from(base);
+    for _ in 0..(times - 1) {
+        if let Some(s) = &separator {
+            result.push(s.clone());
+        }
+        result.extend_from_slice(base);
+    }
+    result
+}
 
-    for l in BufReader::new(input).lines().map(|l| l.unwrap()) {
-        let line = l.trim();
+/// Springs and the sizes of the damaged groups, like `???.### 1,1,3`.
+pub(crate) fn parse_record(line: &Line) -> Result<(Vec<char>, Vec<usize>)> {
+    let mut parser = line.parser();
+    parser.skip_whitespace();
+    let mut chars = Vec::new();
+    while parser.peek().is_some_and(|c| !c.is_whitespace()) {
+        chars.push(parser.char_as("`.`, `#` or `?`", |c| ".#?".contains(c).then_some(c))?);
+    }
+    if chars.is_empty() {
+        return Err(parser.error("springs"));
+    }
+    let knowns = parser.separated(",", |p| p.number())?;
+    parser.end()?;
+    Ok((chars, knowns))
+}
 
-        let mut chunks = line.split_whitespace();
+#[derive(Debug)]
+struct Record {
+    chars: Vec<char>,
+    knowns: Vec<usize>,
+}
 
-        let chars: Vec<char> = chunks.next().unwrap().chars().collect();
-        let knowns: Vec<usize> =
-            chunks.next().unwrap().split(',').map(|s| s.parse::<usize>().unwrap()).collect();
+impl Record {
+    /// Arrangements of the record repeated `folds` times.
+    fn arrangements(&self, folds: usize) -> i64 {
+        let big_chars = multifold(&self.chars, Some('?'), folds);
+        let big_knowns = multifold(&self.knowns, None, folds);
 
-        dprintln!("line: {:?}", line);
-        let arrangements = arrangements(&chars, &knowns, 0);
-        dprintln!("arrgs: {:?}", arrangements);
-        solution += arrangements;
+        debug!("big chars: {:?}", big_chars);
+        debug!("big knowns: {:?}", big_knowns);
+        let arrangements = Counter::new().arrangements(&big_chars, &big_knowns, 0);
+        debug!("arrgs: {:?}", arrangements);
+        arrangements
     }
+}
 
-    writeln!(output, "{}", solution).unwrap();
+pub struct Records {
+    records: Vec<Record>,
 }
 
-pub fn main() {
-    let stdin = std::io::stdin();
-    let stdout = std::io::stdout();
-    solve(stdin.lock(), stdout.lock());
+impl Solution for Records {
+    const DAY: u32 = crate::DAY;
+
+    fn parse<R: BufRead>(lines: Lines<R>) -> Result<Records> {
+        let mut records = Vec::new();
+        for line in lines {
+            let line = line?;
+            let (chars, knowns) = parse_record(&line)?;
+            debug!("line: {:?}", line.text);
+            records.push(Record { chars, knowns });
+        }
+        Ok(Records { records })
+    }
+
+    fn part1(&self) -> Result<impl Display> {
+        Ok(self.records.iter().map(|r| r.arrangements(1)).sum::<i64>())
+    }
+
+    fn part2(&self) -> Result<impl Display> {
+        Ok(self.records.iter().map(|r| r.arrangements(5)).sum::<i64>())
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use super::Records;
+    use aoc::solution::{solve_part1, solve_part2};
 
-    fn test_exact(input: &str, output: &str) {
-        let mut actual_out: Vec<u8> = Vec::new();
-        solve(input.as_bytes(), &mut actual_out);
-        assert_eq!(String::from_utf8(actual_out).unwrap(), output);
+    fn test_part1(input: &str, output: &str) {
+        aoc::testing::test_ignore_whitespaces(|i, o| solve_part1::<Records, _, _>(i, o), input, output);
     }
 
-    fn test_ignore_whitespaces(input: &str, output: &str) {
-        let mut actual_out: Vec<u8> = Vec::new();
-        solve(input.as_bytes(), &mut actual_out);
-        let actual_out_str = String::from_utf8(actual_out).unwrap();
-        let actual_outs = actual_out_str.split_whitespace().collect::<Vec<&str>>();
-        let expected_outs = output.split_whitespace().collect::<Vec<&str>>();
-        assert_eq!(actual_outs, expected_outs);
+    fn test_part2(input: &str, output: &str) {
+        aoc::testing::test_ignore_whitespaces(|i, o| solve_part2::<Records, _, _>(i, o), input, output);
     }
 
-    #[test]
-    fn sample() {
-        test_ignore_whitespaces(
-            "???.### 1,1,3
-            .??..??...?##. 1,1,3
-            ?#?#?#?#?#?#?#? 1,3,1,6
-            ????.#...#... 4,1,1
-            ????.######..#####. 1,6,5
-            ?###???????? 3,2,1",
-            "21",
-        );
-    }
+    include!(concat!(env!("OUT_DIR"), "/examples/springs.rs"));
 
     #[test]
     fn fully_filled() {
-        test_ignore_whitespaces(
+        test_part1(
             "#.#.### 1,1,3
             .#...#....###. 1,1,3
             .#.###.#.###### 1,3,1,6
@@ -141,7 +207,7 @@ mod tests {
 
     #[test]
     fn mine() {
-        test_ignore_whitespaces(
+        test_part1(
             "??#??#?? 1,2,1",
             "2",
         );
@@ -149,7 +215,7 @@ mod tests {
 
     #[test]
     fn mine2() {
-        test_ignore_whitespaces(
+        test_part1(
             "???# 3",
             "1",
         );
@@ -157,15 +223,15 @@ mod tests {
 
     #[test]
     fn mine3() {
-        test_ignore_whitespaces(
+        test_part1(
             "# 3",
             "0",
         );
-        test_ignore_whitespaces(
+        test_part1(
             "? 3",
             "0",
         );
-        test_ignore_whitespaces(
+        test_part1(
             ". 3",
             "0",
         );
@@ -173,15 +239,15 @@ mod tests {
 
     #[test]
     fn mine4() {
-        test_ignore_whitespaces(
+        test_part1(
             "# 1",
             "1",
         );
-        test_ignore_whitespaces(
+        test_part1(
             "? 1",
             "1",
         );
-        test_ignore_whitespaces(
+        test_part1(
             ". 1",
             "0",
         );
@@ -189,11 +255,11 @@ mod tests {
 
     #[test]
     fn mine5() {
-        test_ignore_whitespaces(
+        test_part1(
             "## 1",
             "0",
         );
-        test_ignore_whitespaces(
+        test_part1(
             "## 1,1",
             "0",
         );
@@ -201,7 +267,7 @@ mod tests {
 
     #[test]
     fn from_output() {
-        test_ignore_whitespaces(
+        test_part1(
             "?.#????#??? 1,5",
             "3",
         );
@@ -209,7 +275,7 @@ mod tests {
 
     #[test]
     fn from_output2() {
-        test_ignore_whitespaces(
+        test_part1(
             "#???.#???#?.?.??.? 2,1,5,1,1",
             "5",
         );
@@ -217,17 +283,79 @@ mod tests {
 
     #[test]
     fn from_output_bad() {
-        test_ignore_whitespaces(
+        test_part1(
             "??# 1,1",
             "1",
         );
-        test_ignore_whitespaces(
+        test_part1(
             "??.???# 1,1",
             "4",
         );
-        test_ignore_whitespaces(
+        test_part1(
             "##?.??.???# 3,1,1",
             "4",
         );
     }
+
+    #[test]
+    fn unfolded_sample_p1() {
+        test_part2(
+            "???.### 1,1,3",
+            "1",
+        );
+    }
+
+    #[test]
+    fn unfolded_sample_p2() {
+        test_part2(
+            ".??..??...?##. 1,1,3",
+            "16384",
+        );
+    }
+
+    #[test]
+    fn unfolded_sample_p3() {
+        test_part2(
+            "?#?#?#?#?#?#?#? 1,3,1,6",
+            "1",
+        );
+    }
+
+    #[test]
+    fn unfolded_sample_p4() {
+        test_part2(
+            "????.#...#... 4,1,1",
+            "16",
+        );
+    }
+
+    #[test]
+    fn unfolded_sample_p5() {
+        test_part2(
+            "????.######..#####. 1,6,5",
+            "2500",
+        );
+    }
+
+    #[test]
+    fn unfolded_sample_p6() {
+        test_part2(
+            "?###???????? 3,2,1",
+            "506250",
+        );
+    }
+
+
+    #[test]
+    fn unfolded_fully_filled() {
+        test_part2(
+            "#.#.### 1,1,3
+            .#...#....###. 1,1,3
+            .#.###.#.###### 1,3,1,6
+            ####.#...#... 4,1,1
+            #....######..#####. 1,6,5
+            .###.##....# 3,2,1",
+            "6",
+        );
+    }
 }
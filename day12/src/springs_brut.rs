@@ -1,21 +1,15 @@
 //use std::cmp::{min, max};
-use std::io::{BufRead, BufReader, Write};
+use std::io::{BufRead, Write};
+use aoc::debug;
+use aoc::parse::{Lines, Result};
+use crate::springs::parse_record;
 
 
-macro_rules! dprintln {
-    ( $( $x:expr ),* ) => {
-        {
-	    #[cfg(test)]
-            println!($($x), *);
-        }
-    };
-}
-
 // assumes strings without '?'
 fn is_correct(chars: &[char], to_fit: &[usize]) -> bool {
     let s: String = chars.iter().collect();
     let hash_sizes: Vec<usize> = s.split('.').map(|s| s.len()).filter(|&x| x > 0).collect();
-    to_fit == &hash_sizes
+    to_fit == hash_sizes
 }
 
 fn replace_next_with(chars: &[char], c: char) -> Option<Vec<char>> {
@@ -36,7 +30,7 @@ fn arrangements(chars: &[char], to_fit: &[usize]) -> i64 {
             Some(new_chars) => arrangements(&new_chars, to_fit),
             None => {
                 if is_correct(chars, to_fit) {
-                    dprintln!("Correct: {:?}", chars);
+                    debug!("Correct: {:?}", chars);
                     1
                 } else {
                     0
@@ -45,9 +39,9 @@ fn arrangements(chars: &[char], to_fit: &[usize]) -> i64 {
         }
     }
 
-    if chars.iter().filter(|&c| *c == '?').collect::<Vec<_>>().len() == 0 {
+    if chars.iter().filter(|&c| *c == '?').collect::<Vec<_>>().is_empty() {
         return if is_correct(chars, to_fit) {
-            dprintln!("Correct: {:?}", chars);
+            debug!("Correct: {:?}", chars);
             1
         } else {0}
     }
@@ -56,63 +50,41 @@ fn arrangements(chars: &[char], to_fit: &[usize]) -> i64 {
     sol
 }
 
-fn solve<R: BufRead, W: Write>(input: R, mut output: W) {
+pub fn solve<R: BufRead, W: Write>(input: R, mut output: W) -> Result<()> {
     let mut solution: i64 = 0;
 
-    for l in BufReader::new(input).lines().map(|l| l.unwrap()) {
-        let line = l.trim();
-
-        let mut chunks = line.split_whitespace();
-
-        let chars: Vec<char> = chunks.next().unwrap().chars().collect();
-        let knowns: Vec<usize> =
-            chunks.next().unwrap().split(',').map(|s| s.parse::<usize>().unwrap()).collect();
+    for line in Lines::new(input, crate::DAY) {
+        let line = line?;
+        let (chars, knowns) = parse_record(&line)?;
 
-        println!("line: {:?}", line);
+        debug!("line: {:?}", line.text);
         let arrangements = arrangements(&chars, &knowns);
-        println!("arrgs: {:?}", arrangements);
+        debug!("arrgs: {:?}", arrangements);
         solution += arrangements;
     }
 
-    writeln!(output, "{}", solution).unwrap();
-}
-
-pub fn main() {
-    let stdin = std::io::stdin();
-    let stdout = std::io::stdout();
-    solve(stdin.lock(), stdout.lock());
+    writeln!(output, "{}", solution)?;
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[allow(dead_code)]
     fn test_exact(input: &str, output: &str) {
-        let mut actual_out: Vec<u8> = Vec::new();
-        solve(input.as_bytes(), &mut actual_out);
-        assert_eq!(String::from_utf8(actual_out).unwrap(), output);
+        aoc::testing::test_exact(|i, o| solve(i, o), input, output);
     }
 
     fn test_ignore_whitespaces(input: &str, output: &str) {
-        let mut actual_out: Vec<u8> = Vec::new();
-        solve(input.as_bytes(), &mut actual_out);
-        let actual_out_str = String::from_utf8(actual_out).unwrap();
-        let actual_outs = actual_out_str.split_whitespace().collect::<Vec<&str>>();
-        let expected_outs = output.split_whitespace().collect::<Vec<&str>>();
-        assert_eq!(actual_outs, expected_outs);
+        aoc::testing::test_ignore_whitespaces(|i, o| solve(i, o), input, output);
     }
 
     #[test]
     fn sample() {
-        test_ignore_whitespaces(
-            "???.### 1,1,3
-            .??..??...?##. 1,1,3
-            ?#?#?#?#?#?#?#? 1,3,1,6
-            ????.#...#... 4,1,1
-            ????.######..#####. 1,6,5
-            ?###???????? 3,2,1",
-            "21",
-        );
+        // The same as part 1 of `springs`.
+        aoc::testing::test_example(|i, o| solve(i, o),
+            include_str!("../examples/springs.sample.in"), include_str!("../examples/springs.sample.part1.out"));
     }
 
     #[test]
@@ -130,9 +102,9 @@ mod tests {
 
     #[test]
     fn test_is_correct() {
-        assert_eq!(is_correct(&['#', '.', '#', '.', '.', '.', '#'], &[1,1,1]), true);
-        assert_eq!(is_correct(&['#', '.', '#', '.', '.', '.', '#'], &[1,1,2]), false);
-        assert_eq!(is_correct(&['#', '.', '#', '.', '.', '#', '#'], &[1,1,2]), true);
+        assert!(is_correct(&['#', '.', '#', '.', '.', '.', '#'], &[1,1,1]));
+        assert!(!is_correct(&['#', '.', '#', '.', '.', '.', '#'], &[1,1,2]));
+        assert!(is_correct(&['#', '.', '#', '.', '.', '#', '#'], &[1,1,2]));
     }
 
     #[test]
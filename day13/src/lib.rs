@@ -0,0 +1,5 @@
+pub const DAY: u32 = 13;
+
+pub mod palindrome;
+
+pub type Puzzle = palindrome::Patterns;
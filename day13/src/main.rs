@@ -1,6 +1,3 @@
-mod palindrome2;
-
-
 fn main() {
-    palindrome2::main();
+    aoc::solution::main::<day13::Puzzle>();
 }
@@ -1,62 +1,38 @@
 //use std::cmp::{max, min};
-use std::io::{BufRead, BufReader, Write};
+use std::fmt::Display;
+use std::io::BufRead;
+use aoc::debug;
+use aoc::parse::{self, Lines, Result};
+use aoc::solution::Solution;
 
-macro_rules! dprintln {
-    ( $( $x:expr ),* ) => {
-        {
-	    #[cfg(test)]
-            println!($($x), *);
-        }
-    };
-}
-
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct Pattern {
     columns: Vec<String>,
     rows: Vec<String>,
 }
 
 impl Pattern {
-    fn from_lines<I>(lines: &mut I) -> Option<Pattern>
-        where I: Iterator<Item = String>
-    {
-        let mut columns = Vec::<Vec<char>>::new();
-        let mut rows = Vec::<Vec<char>>::new();
-
-        let mut row = 0;
-        while let Some(l) = lines.next() {
-            let line = l.trim();
-            if line == "" {
-                break
-            }
-            rows.push(Vec::new());
-
-            for (i, c) in line.char_indices() {
-                if columns.len() <= i {
-                    columns.push(Vec::new());
-                }
-                columns[i].push(c);
-                rows[row].push(c);
-            }
-
-            row += 1;
-        }
-        if row == 0 {
-            return None;
+    /// Reads the next pattern, up to a blank line. Returns `None` at the end
+    /// of input.
+    fn parse<R: BufRead>(lines: &mut Lines<R>) -> Result<Option<Pattern>> {
+        let block = lines.block()?;
+        if block.is_empty() {
+            return Ok(None);
         }
+        let grid = parse::grid(&block, |c| "#.".contains(c).then_some(c), "`#` or `.`")?;
 
-        Some(Pattern {
-            columns: columns.into_iter().map(|chs| chs.into_iter().collect::<String>()).collect(),
-            rows: rows.into_iter().map(|chs| chs.into_iter().collect::<String>()).collect(),
-        })
+        Ok(Some(Pattern {
+            columns: grid.columns().map(|chs| chs.collect::<String>()).collect(),
+            rows: grid.rows().map(|chs| chs.iter().collect::<String>()).collect(),
+        }))
     }
 
     fn find_palindrome(arr: &[String]) -> Option<usize> {
-        dprintln!("find_palindrome: {:?}", arr);
+        debug!("find_palindrome: {:?}", arr);
         for i in 0..(arr.len() - 1) {
-            dprintln!("Try i: {:?}", i);
-            if (arr.len() - i) % 2 != 0 { continue; }
-            dprintln!("Going on..");
+            debug!("Try i: {:?}", i);
+            if !(arr.len() - i).is_multiple_of(2) { continue; }
+            debug!("Going on..");
 
             let mut left_i = i;
             let mut right_i = arr.len() - 1;
@@ -65,10 +41,10 @@ impl Pattern {
                 left_i += 1;
                 right_i -= 1;
             }
-            dprintln!("Final left_i: {:?}, right_i: {:?}", left_i, right_i);
-            dprintln!("arr[left_i]: {:?}, arr[right_i]: {:?}", arr[left_i], arr[right_i]);
+            debug!("Final left_i: {:?}, right_i: {:?}", left_i, right_i);
+            debug!("arr[left_i]: {:?}, arr[right_i]: {:?}", arr[left_i], arr[right_i]);
             if left_i == right_i + 1 && arr[left_i] == arr[right_i] {
-                dprintln!("Thus returning: {:?}", left_i);
+                debug!("Thus returning: {:?}", left_i);
                 return Some(left_i);
             }
         }
@@ -76,109 +52,140 @@ impl Pattern {
     }
 
     fn find_rev_palindrome(arr: &[String]) -> Option<usize> {
-        let arr_rev = arr.iter().rev().map(|x| x.clone()).collect::<Vec<String>>();
-        match Self::find_palindrome(&arr_rev) {
-            Some(p) => Some(arr.len() - p),
-            None => None,
-        }
+        let arr_rev = arr.iter().rev().cloned().collect::<Vec<String>>();
+        Self::find_palindrome(&arr_rev).map(|p| arr.len() - p)
     }
 
-    fn find_longest_palindrome(arr: &[String]) -> Option<usize> {
+    fn find_longest_palindrome(arr: &[String], different: i64) -> Option<usize> {
         let mut found = None;
         if let Some(c) = Self::find_palindrome(arr) {
-            found = Some(c);
+            if c as i64 != different {
+                found = Some(c);
+            }
         }
         if let Some(c) = Self::find_rev_palindrome(arr) {
-            if let Some(pc) = found {
-                let prev_len = arr.len() - pc;
-                let curr_len = c;
-                if curr_len > prev_len {
+            if c as i64 != different {
+                if let Some(pc) = found {
+                    let prev_len = arr.len() - pc;
+                    let curr_len = c;
+                    if curr_len > prev_len {
+                        found = Some(c);
+                    }
+                } else {
                     found = Some(c);
                 }
-            } else {
-                found = Some(c);
             }
         }
         found
     }
 
-    fn summarize(&self) -> i64 {
-        if let Some(c) = Self::find_longest_palindrome(&self.columns) {
-            return c as i64;
+    /// The summary of a reflection line other than the one summarized as
+    /// `different`, -1 for any.
+    fn summarize(&self, different: i64) -> Option<i64> {
+        if let Some(c) = Self::find_longest_palindrome(&self.columns, different) {
+            if c as i64 != different {
+                return Some(c as i64);
+            }
         }
 
-        if let Some(c) = Self::find_longest_palindrome(&self.rows) {
-            return c as i64 * 100;
+        let diff = if different % 100 == 0 {
+            different / 100
+        } else {
+            -1
+        };
+        if let Some(c) = Self::find_longest_palindrome(&self.rows, diff) {
+            if c as i64 != diff {
+                return Some(c as i64 * 100);
+            }
         }
 
-        println!("Not found for: {:?}", self);
-        panic!("No Palindrome found!");
+        None
+    }
+
+    /// The summary of the other reflection line that appears when fixing the
+    /// smudge, if there is one.
+    fn modified_summary(&mut self) -> Option<i64> {
+        let original = self.summarize(-1)?;
+        debug!("original: {}", original);
+        for col in 0..self.columns.len() {
+            for row in 0..self.rows.len() {
+                let char_at: char = self.columns[col].chars().nth(row).unwrap();
+                let new_char = match char_at {
+                    '#' => ".",
+                    '.' => "#",
+                    _ => panic!("Wrong char"),
+                };
+
+                self.columns[col].replace_range(row..(row+1), new_char);
+                self.rows[row].replace_range(col..(col+1), new_char);
+
+                if let Some(r) = self.summarize(original) {
+                    debug!("Found reflection for: {:?}", self);
+                    return Some(r);
+                }
+
+                let old_char = String::from(char_at);
+                self.columns[col].replace_range(row..(row+1), &old_char);
+                self.rows[row].replace_range(col..(col+1), &old_char);
+            }
+        }
+        None
     }
 }
 
-fn solve<R: BufRead, W: Write>(input: R, mut output: W) {
-    let mut solution: i64 = 0;
+pub struct Patterns {
+    patterns: Vec<Pattern>,
+}
 
+impl Solution for Patterns {
+    const DAY: u32 = crate::DAY;
 
-    let mut lines = BufReader::new(input).lines().map(|l| l.unwrap());
-    while let Some(pat) = Pattern::from_lines(&mut lines) {
-        dprintln!("Pattern: {:?}", pat);
-        solution += pat.summarize();
+    fn parse<R: BufRead>(mut lines: Lines<R>) -> Result<Patterns> {
+        let mut patterns = Vec::new();
+        while let Some(pat) = Pattern::parse(&mut lines)? {
+            debug!("Pattern: {:?}", pat);
+            patterns.push(pat);
+        }
+        Ok(Patterns { patterns })
     }
 
-    writeln!(output, "{}", solution).unwrap();
-}
+    fn part1(&self) -> Result<impl Display> {
+        let mut solution: i64 = 0;
+        for (i, pat) in self.patterns.iter().enumerate() {
+            solution += pat.summarize(-1).ok_or_else(|| {
+                Self::invalid(&format!("pattern {} has no reflection", i + 1))
+            })?;
+        }
+        Ok(solution)
+    }
 
-pub fn main() {
-    let stdin = std::io::stdin();
-    let stdout = std::io::stdout();
-    solve(stdin.lock(), stdout.lock());
+    fn part2(&self) -> Result<impl Display> {
+        let mut solution: i64 = 0;
+        for (i, pat) in self.patterns.iter().enumerate() {
+            let res = pat.clone().modified_summary().ok_or_else(|| {
+                Self::invalid(&format!("pattern {} has no smudge making another reflection", i + 1))
+            })?;
+            debug!("Summarization: {:?}", res);
+            solution += res;
+        }
+        Ok(solution)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use super::Patterns;
+    use aoc::solution::solve_part1;
 
-    fn test_exact(input: &str, output: &str) {
-        let mut actual_out: Vec<u8> = Vec::new();
-        solve(input.as_bytes(), &mut actual_out);
-        assert_eq!(String::from_utf8(actual_out).unwrap(), output);
+    fn test_part1(input: &str, output: &str) {
+        aoc::testing::test_ignore_whitespaces(|i, o| solve_part1::<Patterns, _, _>(i, o), input, output);
     }
 
-    fn test_ignore_whitespaces(input: &str, output: &str) {
-        let mut actual_out: Vec<u8> = Vec::new();
-        solve(input.as_bytes(), &mut actual_out);
-        let actual_out_str = String::from_utf8(actual_out).unwrap();
-        let actual_outs = actual_out_str.split_whitespace().collect::<Vec<&str>>();
-        let expected_outs = output.split_whitespace().collect::<Vec<&str>>();
-        assert_eq!(actual_outs, expected_outs);
-    }
-
-    #[test]
-    fn sample() {
-        test_ignore_whitespaces(
-            "#.##..##.
-            ..#.##.#.
-            ##......#
-            ##......#
-            ..#.##.#.
-            ..##..##.
-            #.#.##.#.
-
-            #...##..#
-            #....#..#
-            ..##..###
-            #####.##.
-            #####.##.
-            ..##..###
-            #....#..#",
-            "405",
-        );
-    }
+    include!(concat!(env!("OUT_DIR"), "/examples/palindrome.rs"));
 
     #[test]
     fn mine() {
-        test_ignore_whitespaces(
+        test_part1(
             "#.#....##",
             "8",
         );
@@ -186,7 +193,7 @@ mod tests {
 
     #[test]
     fn mine2() {
-        test_ignore_whitespaces(
+        test_part1(
             "######.##",
             "3",
         );
@@ -194,7 +201,7 @@ mod tests {
 
     #[test]
     fn mine3() {
-        test_ignore_whitespaces(
+        test_part1(
             "####.#.#",
             "2",
         );
@@ -202,7 +209,7 @@ mod tests {
 
     #[test]
     fn input() {
-        test_ignore_whitespaces(
+        test_part1(
             ".#.####
             ##..#.#
             ##..#.#
@@ -216,4 +223,16 @@ mod tests {
             "200",
         );
     }
+
+    #[test]
+    fn no_reflection() {
+        aoc::testing::test_error(
+            |i, o| solve_part1::<Patterns, _, _>(i, o),
+            "#..#
+
+            ##.
+            .##",
+            "day 13: pattern 2 has no reflection",
+        );
+    }
 }
@@ -0,0 +1,5 @@
+pub const DAY: u32 = 14;
+
+pub mod dish;
+
+pub type Puzzle = dish::Dish;
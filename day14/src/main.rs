@@ -1,6 +1,3 @@
-mod dish2;
-
-
 fn main() {
-    dish2::main();
+    aoc::solution::main::<day14::Puzzle>();
 }
@@ -1,66 +1,194 @@
 //use std::cmp::{max, min};
-use std::io::{BufRead, BufReader, Write};
+use std::fmt::Display;
+use std::io::BufRead;
+use aoc::debug;
+use aoc::parse::{Line, Lines, Parser, Result};
+use aoc::solution::Solution;
 
-macro_rules! dprintln {
-    ( $( $x:expr ),* ) => {
-        {
-	    #[cfg(test)]
-            println!($($x), *);
+fn hash(acc: i64, c: char) -> i64 { ((acc + (c as i64)) * 17) % 256 }
+
+fn compute_hash(word: &str) -> usize { word.chars().fold(0, hash) as usize }
+
+#[derive(Debug)]
+struct Boxy {
+    number: usize,
+    lenses_list: Vec<i64>,
+    lenses_pos: Vec<String>,
+}
+
+impl Boxy {
+    fn new(number: usize) -> Boxy {
+        Boxy {
+            number,
+            lenses_list: Vec::new(),
+            lenses_pos: Vec::new(),
+        }
+    }
+
+    fn insert(&mut self, label: &str, val: i64) {
+        if let Some(pos) = self.lenses_pos.iter().position(|s| s == label) {
+            self.lenses_list[pos] = val;
+        } else {
+            self.lenses_list.push(val);
+            self.lenses_pos.push(String::from(label));
         }
-    };
+    }
+
+    fn delete(&mut self, label: &str) {
+        if let Some(pos) = self.lenses_pos.iter().position(|s| s == label) {
+            self.lenses_list.remove(pos);
+            self.lenses_pos.remove(pos);
+        }
+    }
+
+    fn power(&self) -> i64 {
+        let mut pow = 0;
+        for (i, val) in self.lenses_list.iter().enumerate() {
+            let slot = i + 1;
+            pow += (self.number as i64) * slot as i64 * val;
+        }
+        pow
+    }
+
+    fn is_empty(&self) -> bool {
+        self.lenses_list.is_empty()
+    }
 }
 
-fn hash(acc: i64, c: char) -> i64 {
-    ((acc + (c as i64)) * 17) % 256
+#[derive(Debug)]
+enum Operation {
+    Insert(i64),
+    Delete,
 }
 
-fn solve<R: BufRead, W: Write>(input: R, mut output: W) {
+#[derive(Debug)]
+struct Step {
+    label: String,
+    operation: Operation,
+}
 
-    let input = BufReader::new(input).lines().map(|l| l.unwrap()).next().unwrap();
+impl Step {
+    fn parse(parser: &mut Parser) -> Result<Step> {
+        let label = parser.word()?.to_string();
+        let operation = if parser.skip("-") {
+            Operation::Delete
+        } else if parser.skip("=") {
+            Operation::Insert(parser.number()?)
+        } else {
+            return Err(parser.error("`=` or `-`"));
+        };
 
-    let solution: i64 = input.split(',').map(|s| s.chars().fold(0, hash)).sum();
+        Ok(Step {
+            label,
+            operation,
+        })
+    }
+}
 
-    writeln!(output, "{}", solution).unwrap();
+#[derive(Debug)]
+struct Boxes {
+    boxes: Vec<Boxy>,
 }
 
-pub fn main() {
-    let stdin = std::io::stdin();
-    let stdout = std::io::stdout();
-    solve(stdin.lock(), stdout.lock());
+impl Boxes {
+    fn new() -> Boxes {
+        let mut boxes = Vec::new();
+
+        for i in 0..=255 {
+            boxes.push(Boxy::new(i + 1));
+        }
+
+        Boxes {
+            boxes,
+        }
+    }
+
+    fn insert(&mut self, label: &str, val: i64) {
+        debug!("insert({}, {})", label, val);
+        let box_pos = compute_hash(label);
+        self.boxes[box_pos].insert(label, val);
+    }
+
+    fn delete(&mut self, label: &str) {
+        debug!("delete({})", label);
+        let box_pos = compute_hash(label);
+        self.boxes[box_pos].delete(label);
+    }
+
+    fn apply(&mut self, step: &Step) {
+        debug!("Step: {:?}", step);
+
+        match step.operation {
+            Operation::Insert(val) => self.insert(&step.label, val),
+            Operation::Delete => self.delete(&step.label),
+        }
+    }
+
+    fn power(&self) -> i64 {
+        self.boxes.iter().map(|b| b.power()).sum()
+    }
+
+    fn dprint_non_empty(&self) {
+        for b in &self.boxes {
+            if !b.is_empty() {
+                debug!("{:?}", b);
+            }
+        }
+    }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub struct Sequence {
+    /// Kept whole as part 2 reads the steps in it, where part 1 only hashes them.
+    line: Line,
+}
+
+impl Solution for Sequence {
+    const DAY: u32 = crate::DAY;
 
-    fn test_exact(input: &str, output: &str) {
-        let mut actual_out: Vec<u8> = Vec::new();
-        solve(input.as_bytes(), &mut actual_out);
-        assert_eq!(String::from_utf8(actual_out).unwrap(), output);
+    fn parse<R: BufRead>(mut lines: Lines<R>) -> Result<Sequence> {
+        let line = lines.expect_line("the initialization sequence")?;
+        Ok(Sequence { line })
     }
 
-    fn test_ignore_whitespaces(input: &str, output: &str) {
-        let mut actual_out: Vec<u8> = Vec::new();
-        solve(input.as_bytes(), &mut actual_out);
-        let actual_out_str = String::from_utf8(actual_out).unwrap();
-        let actual_outs = actual_out_str.split_whitespace().collect::<Vec<&str>>();
-        let expected_outs = output.split_whitespace().collect::<Vec<&str>>();
-        assert_eq!(actual_outs, expected_outs);
+    fn part1(&self) -> Result<impl Display> {
+        Ok(self.line.text.trim().split(',').map(compute_hash).sum::<usize>())
     }
 
+    fn part2(&self) -> Result<impl Display> {
+        let mut parser = self.line.parser();
+        let steps = parser.separated(",", Step::parse)?;
+        parser.end()?;
+
+        let mut boxes = Boxes::new();
+
+        for step in &steps {
+            boxes.apply(step);
+        }
+
+        debug!("Boxes: ");
+        boxes.dprint_non_empty();
+
+        Ok(boxes.power())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc::solution::{solve_part1, solve_part2};
+    use aoc::testing::{test_error, test_ignore_whitespaces};
+
+    include!(concat!(env!("OUT_DIR"), "/examples/lens.rs"));
+
     #[test]
     fn hash() {
-        test_ignore_whitespaces(
-            "HASH",
-            "52",
-        );
+        assert_eq!(compute_hash("HASH"), 52);
+        test_ignore_whitespaces(|i, o| solve_part1::<Sequence, _, _>(i, o), "HASH", "52");
     }
 
     #[test]
-    fn sample() {
-        test_ignore_whitespaces(
-            "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7",
-            "1320",
-        );
+    fn malformed() {
+        test_error(|i, o| solve_part2::<Sequence, _, _>(i, o), "rn=1,cm+",
+                   "day 15, line 1, column 8: expected `=` or `-`, found `+`");
     }
 }
@@ -0,0 +1,5 @@
+pub const DAY: u32 = 15;
+
+pub mod lens;
+
+pub type Puzzle = lens::Sequence;
@@ -1,6 +1,3 @@
-mod lens2;
-
-
 fn main() {
-    lens2::main();
+    aoc::solution::main::<day15::Puzzle>();
 }
@@ -1,65 +1,12 @@
-//use std::cmp::{max, min};
-use std::io::{BufRead, BufReader, Write};
+use std::cmp::max;
+use std::fmt::Display;
+use std::io::BufRead;
 use std::collections::VecDeque;
-
-macro_rules! dprintln {
-    ( $( $x:expr ),* ) => {
-        {
-	    #[cfg(test)]
-            println!($($x), *);
-        }
-    };
-}
-
-#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
-struct XY {
-    x: i64,
-    y: i64,
-}
-
-impl XY {
-    const fn new(x: i64, y: i64) -> XY { XY {x, y} }
-
-    const fn add(&self, other: &XY) -> XY { XY { x: self.x + other.x, y: self.y + other.y } }
-}
-
-#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
-enum Direction {
-    UP,
-    RIGHT,
-    DOWN,
-    LEFT,
-}
-use Direction::{UP, RIGHT, DOWN, LEFT};
-
-impl Direction {
-    const fn as_entry(&self) -> usize {
-        match self {
-            UP => 0,
-            RIGHT => 1,
-            DOWN => 2,
-            LEFT => 3,
-        }
-    }
-
-    const fn as_direction(&self) -> XY {
-        match self {
-            UP => XY::new(0, -1),
-            RIGHT => XY::new(1, 0),
-            DOWN => XY::new(0, 1),
-            LEFT => XY::new(-1, 0),
-        }
-    }
-
-    const fn opposite(&self) -> Direction {
-        match self {
-            UP => DOWN,
-            RIGHT => LEFT,
-            DOWN => UP,
-            LEFT => RIGHT,
-        }
-    }
-}
+use aoc::debug;
+use aoc::{Direction, Grid, XY};
+use aoc::Direction::{UP, RIGHT, DOWN, LEFT};
+use aoc::parse::{self, Lines, Result};
+use aoc::solution::Solution;
 
 #[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
 enum State {
@@ -76,7 +23,7 @@ enum Type {
     SplitVert,
 }
 
-#[derive(Debug, PartialEq, Eq, Hash)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
 struct Node {
     routes: Vec<Vec<Direction>>,
     states: Vec<State>,
@@ -86,14 +33,14 @@ struct Node {
 
 
 impl Node {
-    fn from_char(c: char) -> Node {
+    fn from_char(c: char) -> Option<Node> {
         let typ = match c {
             '.' => Type::Empty,
             '/' => Type::Mirror,
             '\\' => Type::BackMirror,
             '|' => Type::SplitVert,
             '-' => Type::SplitHor,
-            _ => panic!("Wrong char!"),
+            _ => return None,
         };
         let routes = match typ {
             Type::Empty => vec![
@@ -128,15 +75,15 @@ impl Node {
             ],
         };
         let states = vec![State::Unvisited; 4];
-        Node {
+        Some(Node {
             routes,
             states,
             typ,
-        }
+        })
     }
 
     fn is_energized(&self) -> bool {
-        self.states.iter().any(|&x| x == State::Energized)
+        self.states.contains(&State::Energized)
     }
 
     fn mark_energized(&mut self, from: Direction) {
@@ -147,67 +94,32 @@ impl Node {
             self.states[d.as_entry()] = State::Energized;
         }
     }
+
+    fn clean(&mut self) {
+        for s in &mut self.states {
+            *s = State::Unvisited;
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, Eq, Hash)]
-struct Map {
-    nodes: Vec<Vec<Node>>,
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Map {
+    nodes: Grid<Node>,
 }
 
 impl Map {
-    fn from_input<I>(lines: I) -> Map
-        where I: Iterator<Item = String>
-    {
-        let mut nodes = Vec::new();
-
-        for (y, l) in lines.enumerate() {
-            let line = l.trim();
-
-            nodes.push(Vec::new());
-
-            for c in line.chars() {
-                nodes[y].push(Node::from_char(c));
-            }
-        }
-
-        Map {
-            nodes,
-        }
-    }
-
     fn energized(&self) -> i64 {
-        self.nodes.iter().map(|ns|
-            ns.iter().map(|n| if n.is_energized() {1} else {0}).sum::<i64>()
-        ).sum()
-    }
-
-    fn node_at(&self, at: &XY) -> &Node {
-        if !self.is_valid(at) {
-            panic!("Getting node out of bounds: {:?}", at);
-        }
-        &self.nodes[at.y as usize][at.x as usize ]
-    }
-
-    fn node_at_mut(&mut self, at: &XY) -> &mut Node {
-        if !self.is_valid(at) {
-            panic!("Getting node out of bounds: {:?}", at);
-        }
-        &mut self.nodes[at.y as usize][at.x as usize ]
-    }
-
-    fn is_valid(&self, pos: &XY) -> bool {
-        pos.x >= 0 && pos.y >= 0 &&
-                pos.x < self.nodes[0].len() as i64 && pos.y < self.nodes.len() as i64
+        self.nodes.iter().filter(|(_, n)| n.is_energized()).count() as i64
     }
 
     fn neighbours(&self, from: &Direction, at: &XY) -> Vec<(Direction, XY)> {
         let mut neighs = Vec::new();
-        let node = self.node_at(at);
+        let node = &self.nodes[*at];
         for d in &node.routes[from.as_entry()] {
-            //dprintln!("d: {:?}", d);
+            //debug!("d: {:?}", d);
             let potential = at.add(&d.as_direction());
-            //dprintln!("potential: {:?}", potential);
-            if self.is_valid(&potential) {
+            //debug!("potential: {:?}", potential);
+            if self.nodes.is_valid(&potential) {
                 neighs.push((d.opposite(), potential));
             }
         }
@@ -220,16 +132,16 @@ impl Map {
         queue.push_back((from_dir, start_position));
 
         while let Some((entry, pos)) = queue.pop_front() {
-            dprintln!("entering: ({:?}, {:?})", entry, pos);
+            debug!("entering: ({:?}, {:?})", entry, pos);
             {
-                let node = self.node_at_mut(&pos);
-                dprintln!("node: {:?}", node);
+                let node = &mut self.nodes[pos];
+                debug!("node: {:?}", node);
                 if node.states[entry.as_entry()] != State::Unvisited {
                     continue;
                 }
 
                 node.mark_energized(entry);
-                dprintln!("now energized: {:?}", node);
+                debug!("now energized: {:?}", node);
             }
 
             for n in self.neighbours(&entry, &pos) {
@@ -237,58 +149,60 @@ impl Map {
             }
         }
     }
-}
 
+    fn clean(&mut self) {
+        for (_, node) in self.nodes.iter_mut() {
+            node.clean();
+        }
+    }
 
-fn solve<R: BufRead, W: Write>(input: R, mut output: W) {
+    fn parameter_positions(&self) -> Vec<(Direction, XY)> {
+        let max_x = self.nodes.width() as i64 - 1;
+        let max_y = self.nodes.height() as i64 - 1;
 
-    let lines = BufReader::new(input).lines().map(|l| l.unwrap());
-    let mut map = Map::from_input(lines);
-    dprintln!("Map: {:?}", map);
-    map.bfs(LEFT, XY::new(0, 0));
+        let horizontal_top = (0..self.nodes.width()).map(|x| XY::new(x as i64, 0)).
+            map(|p| (UP, p));
+        let horizontal_bottom = (0..self.nodes.width()).map(|x| XY::new(x as i64, max_y)).
+            map(|p| (DOWN, p));
+        let vertical_left = (0..self.nodes.height()).map(|y| XY::new(0, y as i64)).
+            map(|p| (LEFT, p));
+        let vertical_right= (0..self.nodes.height()).map(|y| XY::new(max_x, y as i64)).
+            map(|p| (RIGHT, p));
 
-    writeln!(output, "{}", map.energized()).unwrap();
+        horizontal_top.chain(horizontal_bottom).chain(vertical_left).chain(vertical_right).collect()
+    }
 }
 
-pub fn main() {
-    let stdin = std::io::stdin();
-    let stdout = std::io::stdout();
-    solve(stdin.lock(), stdout.lock());
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+impl Solution for Map {
+    const DAY: u32 = crate::DAY;
 
-    fn test_exact(input: &str, output: &str) {
-        let mut actual_out: Vec<u8> = Vec::new();
-        solve(input.as_bytes(), &mut actual_out);
-        assert_eq!(String::from_utf8(actual_out).unwrap(), output);
+    fn parse<R: BufRead>(mut lines: Lines<R>) -> Result<Map> {
+        Ok(Map {
+            nodes: parse::grid(&lines.non_blank()?, Node::from_char, "`.`, `/`, `\\`, `|` or `-`")?,
+        })
     }
 
-    fn test_ignore_whitespaces(input: &str, output: &str) {
-        let mut actual_out: Vec<u8> = Vec::new();
-        solve(input.as_bytes(), &mut actual_out);
-        let actual_out_str = String::from_utf8(actual_out).unwrap();
-        let actual_outs = actual_out_str.split_whitespace().collect::<Vec<&str>>();
-        let expected_outs = output.split_whitespace().collect::<Vec<&str>>();
-        assert_eq!(actual_outs, expected_outs);
+    fn part1(&self) -> Result<impl Display> {
+        let mut map = self.clone();
+        debug!("Map: {:?}", map);
+        map.bfs(LEFT, XY::new(0, 0));
+        Ok(map.energized())
     }
 
-    #[test]
-    fn sample() {
-        test_ignore_whitespaces(
-            ".|...\\....
-            |.-.\\.....
-            .....|-...
-            ........|.
-            ..........
-            .........\\
-            ..../.\\\\..
-            .-.-/..|..
-            .|....-|.\\
-            ..//.|....",
-            "46",
-        );
+    fn part2(&self) -> Result<impl Display> {
+        let mut solution = 0;
+        let mut map = self.clone();
+        for (dir, pos) in map.parameter_positions() {
+            map.bfs(dir, pos);
+            solution = max(solution, map.energized());
+            map.clean();
+        }
+        Ok(solution)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    include!(concat!(env!("OUT_DIR"), "/examples/lava_floor.rs"));
+}
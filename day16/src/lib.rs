@@ -0,0 +1,5 @@
+pub const DAY: u32 = 16;
+
+pub mod lava_floor;
+
+pub type Puzzle = lava_floor::Map;
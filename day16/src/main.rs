@@ -1,6 +1,3 @@
-mod lava_floor2;
-
-
 fn main() {
-    lava_floor2::main();
+    aoc::solution::main::<day16::Puzzle>();
 }
@@ -1,258 +1,246 @@
 //use std::cmp::{max, min};
-use std::io::{BufRead, BufReader, Write};
-use std::cmp::Ordering;
-use std::collections::BinaryHeap;
-use std::collections::HashMap;
-
-macro_rules! dprintln {
-    ( $( $x:expr ),* ) => {
-        {
-	    #[cfg(test)]
-            println!($($x), *);
-        }
-    };
-}
-
-#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash, Ord, PartialOrd)]
-struct XY {
-    x: i64,
-    y: i64,
-}
-
+use std::fmt::Display;
+use std::io::{BufRead, Write};
+use aoc::{debug, trace};
+use aoc::shortest_path::{self, Path};
+use aoc::{Direction, Grid, XY};
+use aoc::parse::{self, Lines, Result};
+use aoc::solution::Solution;
+
+#[allow(dead_code)]
 const UP: usize = 0;
 const RIGHT: usize = 1;
 const DOWN: usize = 2;
+#[allow(dead_code)]
 const LEFT: usize = 3;
 
-impl XY {
-    const AROUND: [XY; 4] = [
-        XY::new(0, -1),
-        XY::new(1, 0),
-        XY::new(0, 1),
-        XY::new(-1, 0),
-    ];
-    const fn new(x: i64, y: i64) -> XY { XY {x, y} }
-
-    const fn add(&self, other: &XY) -> XY { XY { x: self.x + other.x, y: self.y + other.y } }
+/// How the crucible is allowed to move.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Rules {
+    /// Blocks to move in a straight line before it can turn or stop at the goal.
+    pub min_run: i64,
+    /// Blocks it can move in a straight line at most.
+    pub max_run: i64,
+    /// Whether it can turn back to the block it came from.
+    pub reversing: bool,
 }
 
-#[derive(Debug, PartialEq, Eq, Hash)]
-struct Grid {
-    nodes: Vec<Vec<i64>>,
+impl Rules {
+    pub const CRUCIBLE: Rules = Rules { min_run: 1, max_run: 3, reversing: false };
+    pub const ULTRA_CRUCIBLE: Rules = Rules { min_run: 4, max_run: 10, reversing: false };
 }
 
-
 #[derive(Debug, PartialEq, Eq, Hash)]
-struct State(i64, XY, usize, i64);
-impl Ord for State {
-    fn cmp(&self, other: &Self) -> Ordering {
-        other.0.cmp(&self.0)
-           .then_with(|| self.1.cmp(&other.1))
-    }
-}
-impl PartialOrd for State {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
+pub struct City {
+    blocks: Grid<i64>,
 }
 
-impl Grid {
-    fn from_input<I>(lines: I) -> Grid
-        where I: Iterator<Item = String>
-    {
-        let mut nodes = Vec::new();
 
-        for (y, l) in lines.enumerate() {
-            let line = l.trim();
-
-            nodes.push(Vec::new());
-
-            for c in line.chars() {
-                nodes[y].push(c.to_string().parse().unwrap());
-            }
-        }
-
-        Grid {
-            nodes,
-        }
-    }
-
-    fn is_valid(&self, pos: &XY) -> bool {
-        pos.x >= 0 && pos.y >= 0 &&
-                pos.x < self.nodes[0].len() as i64 && pos.y < self.nodes.len() as i64
-    }
-
-    fn node_at(&self, at: &XY) -> i64 {
-        if !self.is_valid(at) {
-            panic!("Getting node out of bounds: {:?}", at);
-        }
-        self.nodes[at.y as usize][at.x as usize]
-    }
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+struct Crucible {
+    pos: XY,
+    dir: usize,
+    len: i64,
+}
 
-    fn neighbours(&self, at: &XY, dir: usize, len: i64) -> Vec<(XY, usize, i64)> {
+impl City {
+    fn neighbours(&self, crucible: &Crucible, rules: &Rules) -> Vec<(Crucible, i64)> {
+        let Crucible { pos: at, dir, len } = *crucible;
         let mut neighs = Vec::new();
         for (i, diff) in XY::AROUND.iter().enumerate() {
-            if (i + 2) % 4 == dir {
-                // ignore opposite
+            if (i + 2) % 4 == dir && !rules.reversing {
                 continue;
             }
 
-            if i == dir && len >= 3 {
+            if i != dir && len < rules.min_run {
                 continue;
             }
-            let potential = at.add(&diff);
-            if self.is_valid(&potential) {
+
+            if i == dir && len >= rules.max_run {
+                continue;
+            }
+            let potential = at.add(diff);
+            if self.blocks.is_valid(&potential) {
                 let new_dir = i;
                 let new_len = if new_dir == dir { len + 1 } else { 1 };
-                neighs.push((potential, new_dir, new_len));
+                let next = Crucible { pos: potential, dir: new_dir, len: new_len };
+                neighs.push((next, self.blocks[potential]));
             }
         }
-        dprintln!("neighs({:?}, {:?}, {:?}): {:?}", at, dir, len, neighs);
+        trace!("neighs({:?}): {:?}", crucible, neighs);
         neighs
     }
 
-    fn height(&self) -> usize { self.nodes.len() }
-    fn width(&self) -> usize { self.nodes[0].len() }
+    fn dijkstra(&self, start: XY, goal: XY, rules: &Rules) -> Option<Path<Crucible>> {
+        // Not having moved yet, the crucible can still pick either direction.
+        let starts = [
+            Crucible { pos: start, dir: RIGHT, len: 0 },
+            Crucible { pos: start, dir: DOWN, len: 0 },
+        ];
+        shortest_path::dijkstra(
+            starts, |c| self.neighbours(c, rules), |c| c.pos == goal && c.len >= rules.min_run)
+    }
 
-    fn debug_print_distances(&self, dists: &HashMap<(XY, i64), Vec<i64>>) {
-        for i in 0..4 {
-            dprintln!("For i: {}", i);
-            for y in 0..self.height() {
-                let mut line_str = String::new();
-                for x in 0..self.width() {
-                    line_str += "(";
-                    for len in 0..4 {
-                        if let Some(es) = dists.get(&(XY::new(x as i64, y as i64), len)) {
-                            if es[i] != i64::MAX {
-                                line_str += &format!(" {:03},", es[i]);
-                            } else {
-                                line_str += " MAX,";
-                            }
-                        } else {
-                            line_str += "   -,";
-                        }
-                    }
-                    line_str += ");";
-                }
-                dprintln!("{}", line_str);
-            }
+    /// The city with the direction the crucible moved in drawn on every block
+    /// of the path, except the starting one.
+    fn render_path(&self, path: &[Crucible]) -> String {
+        let mut map = self.blocks.map(|b| char::from_digit(*b as u32, 10).unwrap());
+        for crucible in path.iter().skip(1) {
+            map[crucible.pos] = Direction::ALL[crucible.dir].as_char();
         }
+        map.to_string()
     }
 
-    fn dijkstra(&self, start: XY, goal: XY) -> i64 {
-        let mut distances: HashMap<(XY, i64), Vec<i64>> = HashMap::new();
+    /// The path losing the least heat from the top left to the bottom right
+    /// block, if the rules let the crucible get there.
+    fn best_path(&self, rules: &Rules) -> Option<Path<Crucible>> {
+        let start = XY::new(0, 0);
+        let goal = XY::newu(self.blocks.width() - 1, self.blocks.height() - 1);
+        self.dijkstra(start, goal, rules)
+    }
 
-        let mut heap = BinaryHeap::new();
-        heap.push(State(0, start, RIGHT, 0));
-        for i in 0..4 {
-            distances.insert((start, i as i64), vec![0, 0, 0, 0]);
+    /// The least heat loss from the top left to the bottom right block, or
+    /// "none" if the rules make the bottom right block unreachable.
+    fn least_heat_loss(&self, rules: &Rules) -> String {
+        match self.best_path(rules) {
+            Some(path) => path.cost.to_string(),
+            None => "none".to_string(),
         }
+    }
+}
 
-        while let Some(State(dist, pos, dir, len)) = heap.pop() {
-            dprintln!("Got: {:?}, {:?}, {:?}, {}", dist, pos, dir, len);
-            if pos == goal {
-                return dist;
-            }
-
-            if let Some(es) = distances.get(&(pos, len)) {
-                if es[dir] < dist {
-                    continue;
-                }
-            }
-
-            for (n, new_dir, new_len) in self.neighbours(&pos, dir, len) {
-                let new_cost = dist + self.node_at(&n);
-                if let Some(es) = distances.get_mut(&(n, new_len)) {
-                    if new_cost < es[new_dir] {
-                        es[new_dir] = new_cost;
-                        heap.push(State(new_cost, n, new_dir, new_len));
-                    }
+impl Solution for City {
+    const DAY: u32 = crate::DAY;
 
-                } else {
-                    let mut new_dists = vec![i64::MAX; 4];
-                    new_dists[new_dir] = new_cost;
-                    distances.insert((n, new_len), new_dists);
-                    heap.push(State(new_cost, n, new_dir, new_len));
-                }
-            }
+    fn parse<R: BufRead>(mut lines: Lines<R>) -> Result<City> {
+        let blocks = parse::grid(
+            &lines.non_blank()?, |c| c.to_digit(10).map(|d| d as i64), "a digit")?;
+        if blocks.height() == 0 {
+            return Err(lines.invalid("the city has no blocks"));
         }
+        debug!("City:\n{}", blocks);
 
-        self.debug_print_distances(&distances);
-
-        panic!("No path found!")
+        Ok(City {
+            blocks,
+        })
     }
-}
 
+    fn part1(&self) -> Result<impl Display> {
+        Ok(self.least_heat_loss(&Rules::CRUCIBLE))
+    }
 
-fn solve<R: BufRead, W: Write>(input: R, mut output: W) {
-
-    let lines = BufReader::new(input).lines().map(|l| l.unwrap());
-    dprintln!("reading");
-    let grid = Grid::from_input(lines);
-    dprintln!("Grid: {:?}", grid);
+    fn part2(&self) -> Result<impl Display> {
+        Ok(self.least_heat_loss(&Rules::ULTRA_CRUCIBLE))
+    }
+}
 
-    let start = XY::new(0, 0);
-    let goal = XY::new(grid.width() as i64 - 1, grid.height() as i64 - 1);
+/// Writes the least heat loss with any `rules`, not only those of the puzzle.
+pub fn solve_with_rules<R: BufRead, W: Write>(
+    input: R, mut output: W, rules: &Rules) -> Result<()> {
 
-    writeln!(output, "{}", grid.dijkstra(start, goal)).unwrap();
+    let city = City::parse(Lines::new(input, crate::DAY))?;
+    writeln!(output, "{}", city.least_heat_loss(rules))?;
+    Ok(())
 }
 
-pub fn main() {
-    let stdin = std::io::stdin();
-    let stdout = std::io::stdout();
-    solve(stdin.lock(), stdout.lock());
+/// Like `solve_with_rules`, but writes the city with the path drawn on it
+/// before the heat loss.
+pub fn draw_with_rules<R: BufRead, W: Write>(
+    input: R, mut output: W, rules: &Rules) -> Result<()> {
+
+    let city = City::parse(Lines::new(input, crate::DAY))?;
+    match city.best_path(rules) {
+        Some(path) => writeln!(output, "{}{}", city.render_path(&path.states), path.cost)?,
+        None => writeln!(output, "none")?,
+    }
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    fn test_ignore_whitespaces(input: &str, output: &str) {
-        let mut actual_out: Vec<u8> = Vec::new();
-        solve(input.as_bytes(), &mut actual_out);
-        let actual_out_str = String::from_utf8(actual_out).unwrap();
-        let actual_outs = actual_out_str.split_whitespace().collect::<Vec<&str>>();
-        let expected_outs = output.split_whitespace().collect::<Vec<&str>>();
-        assert_eq!(actual_outs, expected_outs);
+    fn test_rules(rules: Rules, input: &str, output: &str) {
+        aoc::testing::test_ignore_whitespaces(
+            |i, o| solve_with_rules(i, o, &rules), input, output);
     }
 
+    include!(concat!(env!("OUT_DIR"), "/examples/crucible.rs"));
+
+    const SAMPLE: &str = include_str!("../examples/crucible.sample.in");
+
     #[test]
-    fn sample() {
-        test_ignore_whitespaces(
-            "2413432311323
-            3215453535623
-            3255245654254
-            3446585845452
-            4546657867536
-            1438598798454
-            4457876987766
-            3637877979653
-            4654967986887
-            4564679986453
-            1224686865563
-            2546548887735
-            4322674655533",
-            "102",
+    fn sample_path() {
+        let city = City::parse(Lines::new(SAMPLE.as_bytes(), crate::DAY)).unwrap();
+        let path = city.dijkstra(XY::new(0, 0), XY::new(12, 12), &Rules::CRUCIBLE).unwrap();
+        let heat_loss: i64 = path.states.iter().skip(1).map(|c| city.blocks[c.pos]).sum();
+        assert_eq!(heat_loss, path.cost);
+        // Differs from the puzzle's drawing in the second and third row, but
+        // loses exactly the same amount of heat.
+        assert_eq!(
+            city.render_path(&path.states),
+            "2>>34^>>>1323
+32v>>>35v>623
+325524565v>54
+3446585845v52
+4546657867v>6
+14385987984v4
+44578769877v6
+36378779796v>
+465496798688v
+456467998645v
+12246868655<v
+25465488877v5
+43226746555v>\n",
         );
     }
 
     #[test]
-    fn mine() {
-        test_ignore_whitespaces(
-            "9199999999999
-             9199999999999
-             9119999999999
-             9919911199999
-             9911919199999
-             9991119119999
-             9999999919999
-             9999999119999
-             9999999199999
-             9999999119999
-             9999999919999
-             9999999911199
-             9999999999111",
-            "30",
+    fn sample_path_ultra() {
+        let city = City::parse(Lines::new(SAMPLE.as_bytes(), crate::DAY)).unwrap();
+        let path = city.dijkstra(XY::new(0, 0), XY::new(12, 12), &Rules::ULTRA_CRUCIBLE).unwrap();
+        let heat_loss: i64 = path.states.iter().skip(1).map(|c| city.blocks[c.pos]).sum();
+        assert_eq!(heat_loss, path.cost);
+        assert_eq!(
+            city.render_path(&path.states),
+            "2>>>>>>>>1323
+32154535v5623
+32552456v4254
+34465858v5452
+45466578v>>>>
+143859879845v
+445787698776v
+363787797965v
+465496798688v
+456467998645v
+122468686556v
+254654888773v
+432267465553v\n",
         );
     }
+
+    #[test]
+    fn drawn() {
+        let draw = |i: &[u8], o: &mut Vec<u8>| draw_with_rules(i, o, &Rules::CRUCIBLE);
+        aoc::testing::test_exact(draw, "123\n456", "1>>\n45v\n11\n");
+        aoc::testing::test_exact(draw, "1234", "1>>>\n9\n");
+        let draw = |i: &[u8], o: &mut Vec<u8>| draw_with_rules(i, o, &Rules::ULTRA_CRUCIBLE);
+        aoc::testing::test_exact(draw, "1234", "none\n");
+    }
+
+    #[test]
+    fn sample_rules() {
+        test_rules(Rules::CRUCIBLE, SAMPLE, "102");
+        test_rules(Rules::ULTRA_CRUCIBLE, SAMPLE, "94");
+        test_rules(Rules { min_run: 1, max_run: 1, reversing: false }, SAMPLE, "133");
+        test_rules(Rules { min_run: 2, max_run: 2, reversing: false }, SAMPLE, "127");
+        test_rules(Rules { min_run: 13, max_run: 13, reversing: false }, SAMPLE, "none");
+    }
+
+    #[test]
+    fn sample_reversing() {
+        test_rules(Rules { reversing: true, ..Rules::CRUCIBLE }, SAMPLE, "101");
+        test_rules(Rules { reversing: true, ..Rules::ULTRA_CRUCIBLE }, SAMPLE, "94");
+        test_rules(Rules { min_run: 1, max_run: 1, reversing: true }, SAMPLE, "133");
+    }
 }
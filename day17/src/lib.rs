@@ -0,0 +1,5 @@
+pub const DAY: u32 = 17;
+
+pub mod crucible;
+
+pub type Puzzle = crucible::City;
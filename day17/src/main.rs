@@ -1,6 +1,3 @@
-mod crucible2;
-
-
 fn main() {
-    crucible2::main();
+    aoc::solution::main::<day17::Puzzle>();
 }
@@ -1,256 +1,186 @@
 //use std::cmp::{max, min};
-use std::io::{BufRead, BufReader, Write};
-use regex::Regex;
-use lazy_static::lazy_static;
-
-#[allow(unused_macros)]
-macro_rules! dprintln {
-    ( $( $x:expr ),* ) => {
-        {
-	    #[cfg(test)]
-            println!($($x), *);
-        }
-    };
-}
-
-#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
-struct XY {
-    x: i64,
-    y: i64,
+use std::fmt::Display;
+use std::io::BufRead;
+use aoc::debug;
+use aoc::XY;
+use aoc::intervals::{Interval, IntervalSet};
+use aoc::parse::{Line, Lines, Result};
+use aoc::solution::Solution;
+
+const DIRS: [(&str, XY); 4] = [
+    ("U", XY { x: 0, y: -1 }),
+    ("R", XY { x: 1, y: 0 }),
+    ("D", XY { x: 0, y: 1 }),
+    ("L", XY { x: -1, y: 0 }),
+];
+
+fn color_dir(c: char) -> Option<XY> {
+    match c {
+        '3' => Some(XY::new(0, -1)),
+        '0' => Some(XY::new(1, 0)),
+        '1' => Some(XY::new(0, 1)),
+        '2' => Some(XY::new(-1, 0)),
+        _ => None,
+    }
 }
 
-impl XY {
-    const fn new(x: i64, y: i64) -> XY { XY {x, y} }
-
-    const fn add(&self, other: &XY) -> XY { XY { x: self.x + other.x, y: self.y + other.y } }
-    const fn mul(&self, scalar: i64) -> XY { XY { x: self.x * scalar, y: self.y * scalar } }
-
-    fn dir(c: &str) -> XY {
-        match c {
-            "U" => XY::new(0, -1),
-            "R" => XY::new(1, 0),
-            "D" => XY::new(0, 1),
-            "L" => XY::new(-1, 0),
-            _ => panic!("No such dir!"),
-        }
+/// Parses one dig instruction, like `R 6 (#70c710)`, into the trench it digs
+/// as written and as encoded in the color: its first five digits are the
+/// length, the last one the direction.
+fn parse_instruction(line: &Line) -> Result<(XY, XY)> {
+    let mut parser = line.parser();
+    let dir = parser.one_of(&DIRS)?;
+    let len: i64 = parser.number()?;
+    parser.expect("(")?;
+    parser.expect("#")?;
+    let error = parser.error("six hexadecimal digits");
+    let color = parser.word()?;
+    if color.len() != 6 || !color.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(error);
     }
+    let color_len = i64::from_str_radix(&color[0..5], 16).unwrap();
+    let color_dir = color[5..].chars().next().and_then(color_dir).ok_or(error)?;
+    parser.expect(")")?;
+    parser.end()?;
+    Ok((dir.mul(len), color_dir.mul(color_len)))
+}
 
-    fn len_dir(c: &str, len: i64) -> XY { Self::dir(c).mul(len) }
+/// Removes the columns `edge` takes away from the `inside` interval holding
+/// it, returning how many of them were inside until now: the edge itself stays
+/// dug, except the ends it shares with the interval.
+fn remove_edge(inside: &mut IntervalSet<i64>, edge: Interval<i64>) -> Result<i64> {
+    let containing = *inside.intervals().iter().find(|i| i.contains_interval(&edge))
+        .ok_or_else(|| Lagoon::invalid(&format!(
+            "the trench from column {} to {} is not along the inside of the lagoon", edge.start, edge.end - 1)))?;
+    let start_remove =
+        if edge.start == containing.start {
+            edge.start
+        } else {
+            edge.start + 1
+        };
+    let end_remove =
+        if edge.end == containing.end {
+            containing.end
+        } else {
+            edge.end - 1
+        };
+    inside.remove(Interval::new(start_remove, end_remove));
+    Ok(end_remove - start_remove)
 }
 
+/// The dig plan, read both ways.
 #[derive(Debug)]
-struct Intervals {
-    ints: Vec<(i64, i64)>,
+pub struct Lagoon {
+    trenches: Vec<XY>,
+    color_trenches: Vec<XY>,
 }
 
-impl Intervals {
-    fn empty() -> Intervals {
-        Intervals {
-            ints: Vec::new(),
-        }
-    }
-
-    fn is_contained(&self, range: (i64, i64)) -> bool {
-        for &(start, end) in &self.ints {
-            if range.0 >= start && range.1 < end {
-                return true;
-            }
-        }
-        false
-    }
+/// The corners of the loop dug by `trenches`, starting from the origin.
+fn corners(trenches: &[XY]) -> Vec<XY> {
+    let mut corners = Vec::new();
 
-    fn total_len(&self) -> i64 {
-        let mut len = 0;
+    let mut curr = XY::new(0, 0);
+    corners.push(curr);
 
-        for (start, end) in &self.ints {
-            len += end - start;
-        }
-        len
+    for trench in trenches {
+        curr = curr.add(trench);
+        corners.push(curr);
     }
 
-    fn add(&mut self, range: (i64, i64)) {
-        if self.is_contained(range) { return; }
-        self.ints.push((range.0, range.1 + 1));
-        self.compress();
+    if corners[0] == *corners.last().unwrap() {
+        corners.pop();
     }
+    corners
+}
 
-    fn remove(&mut self, range: (i64, i64)) -> i64 {
-        if !self.is_contained(range) { return 0; }
-        let mut removed = 0;
-
-        for i in 0..self.ints.len() {
-            if range.0 >= self.ints[i].0 && range.1 < self.ints[i].1 {
-                let curr_start = self.ints[i].0;
-                let curr_end = self.ints[i].1;
-                let start_remove =
-                    if range.0 == curr_start {
-                        range.0
-                    } else {
-                        range.0 + 1
-                    };
-                let end_remove =
-                    if range.1 == curr_end - 1 {
-                        curr_end
-                    } else {
-                        range.1
-                    };
-                removed = end_remove - start_remove;
-                self.ints[i].1 = start_remove;
-                self.ints.push((end_remove, curr_end));
-                break;
-            }
-        }
-        self.compress();
-        removed
-    }
+/// Cubic meters of lava held by the loop with these corners.
+fn size(mut corners: Vec<XY>) -> Result<i64> {
+    let mut count = 0;
+    let mut inside_intervals = IntervalSet::new();
 
-    fn compress(&mut self) {
-        let mut new_ints = Vec::new();
-        self.ints.sort();
-        new_ints.push(self.ints[0]);
-        for i in 1..self.ints.len() {
-            let prev = new_ints.last_mut().unwrap();
-            let curr = self.ints[i];
-            if curr.0 == curr.1 { continue; }
-            if prev.1 >= curr.0 {
-                prev.1 = curr.1;
+    // Sort by rows
+    corners.sort_by_key(|p| (p.y, p.x));
+    let Some(first) = corners.first() else {
+        return Err(Lagoon::invalid("the dig plan does not dig anything"));
+    };
+    let mut prev_row = first.y;
+
+    let mut i = 0;
+    while i < corners.len() {
+        let row = corners[i].y;
+        let to_add = ((row - prev_row).abs()) * inside_intervals.total_len();
+        debug!("--For rows: {} - {}", prev_row, row);
+        debug!("Intervals were: {:?}", inside_intervals);
+        debug!("Thus we add: {:?}", to_add);
+        count += to_add;
+        while i < corners.len() && corners[i].y == row {
+            let first = corners[i];
+            let Some(second) = corners.get(i + 1).filter(|second| second.y == row) else {
+                return Err(Lagoon::invalid(&format!("there is an odd number of corners in row {}", row)));
+            };
+
+            let edge = Interval::new(first.x, second.x + 1);
+            if inside_intervals.contains_interval(&edge) {
+                count += remove_edge(&mut inside_intervals, edge)?;
             } else {
-                new_ints.push(curr);
+                inside_intervals.insert(edge);
             }
+            i += 2;
         }
-        if new_ints[0].0 == new_ints[0].1 {
-            new_ints.remove(0);
-        }
-        self.ints = new_ints;
+        prev_row = row;
     }
-}
-
 
-#[derive(Debug)]
-struct Lagoon {
-    corners: Vec<XY>,
+    Ok(count)
 }
 
-impl Lagoon {
-
-    fn from_input<I>(lines: I) -> Lagoon
-        where I: Iterator<Item = String>
-    {
-        lazy_static! {
-            static ref INS_RE: Regex = Regex::new(
-                r"(?P<dir>[URDL]) (?P<len>\d+) \((?P<color>.*)\)"
-            ).unwrap();
-        }
-
-        let mut corners = Vec::new();
-
-        let mut curr = XY::new(0, 0);
-        corners.push(curr);
+impl Solution for Lagoon {
+    const DAY: u32 = crate::DAY;
 
-        for l in lines {
-            let line = l.trim();
-            let caps = INS_RE.captures(line).unwrap();
-            let dir = caps.name("dir").unwrap().as_str();
-            let len: i64 = caps.name("len").unwrap().as_str().parse().unwrap();
-            curr = curr.add(&XY::len_dir(dir, len));
-            corners.push(curr);
-        }
+    fn parse<R: BufRead>(lines: Lines<R>) -> Result<Lagoon> {
+        let mut trenches = Vec::new();
+        let mut color_trenches = Vec::new();
 
-        if corners[0] == *corners.last().unwrap() {
-            corners.pop();
+        for line in lines {
+            let (trench, color_trench) = parse_instruction(&line?)?;
+            trenches.push(trench);
+            color_trenches.push(color_trench);
         }
 
-        Lagoon {
-            corners,
-        }
+        let lagoon = Lagoon {
+            trenches,
+            color_trenches,
+        };
+        debug!("Lagoon: {:?}", lagoon);
+        Ok(lagoon)
     }
 
-    fn size(&mut self) -> i64 {
-        let mut count = 0;
-        let mut inside_intervals = Intervals::empty();
-
-        // Sort by rows
-        self.corners.sort_by_key(|p| (p.y, p.x));
-        let mut prev_row = self.corners[0].y;
-
-        let mut i = 0;
-        while i < self.corners.len() {
-            let row = self.corners[i].y;
-            let to_add = ((row - prev_row).abs()) * inside_intervals.total_len();
-            dprintln!("--For rows: {} - {}", prev_row, row);
-            dprintln!("Intervals were: {:?}", inside_intervals);
-            dprintln!("Thus we add: {:?}", to_add);
-            count += to_add;
-            while i < self.corners.len() && self.corners[i].y == row {
-                let first = self.corners[i];
-                if i + 1 >= self.corners.len() {
-                    panic!("There were odd num of corners at end of row: {}", row);
-                }
-                let second = self.corners[i+1];
-                if second.y != row {
-                    panic!("There were odd num of corners in row: {}", row);
-                }
-
-                let new_range = (first.x, second.x);
-                if inside_intervals.is_contained(new_range) {
-                    count += inside_intervals.remove(new_range);
-                } else {
-                    inside_intervals.add(new_range);
-                }
-                i += 2;
-            }
-            prev_row = row;
-        }
-
-        count
+    fn part1(&self) -> Result<impl Display> {
+        size(corners(&self.trenches))
     }
-}
 
-fn solve<R: BufRead, W: Write>(input: R, mut output: W) {
-    let lines = BufReader::new(input).lines().map(|l| l.unwrap());
-    let mut lagoon = Lagoon::from_input(lines);
-    dprintln!("Lagoon: {:?}", lagoon);
-
-    writeln!(output, "{}", lagoon.size()).unwrap();
-}
-
-pub fn main() {
-    let stdin = std::io::stdin();
-    let stdout = std::io::stdout();
-    solve(stdin.lock(), stdout.lock());
+    fn part2(&self) -> Result<impl Display> {
+        size(corners(&self.color_trenches))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc::solution::solve_part1;
+    use aoc::testing::test_error;
+
+    include!(concat!(env!("OUT_DIR"), "/examples/lagoon.rs"));
 
-    fn test_ignore_whitespaces(input: &str, output: &str) {
-        let mut actual_out: Vec<u8> = Vec::new();
-        solve(input.as_bytes(), &mut actual_out);
-        let actual_out_str = String::from_utf8(actual_out).unwrap();
-        let actual_outs = actual_out_str.split_whitespace().collect::<Vec<&str>>();
-        let expected_outs = output.split_whitespace().collect::<Vec<&str>>();
-        assert_eq!(actual_outs, expected_outs);
+    #[test]
+    fn malformed_color() {
+        test_error(|i, o| solve_part1::<Lagoon, _, _>(i, o), "R 6 (#70c71g)",
+                   "day 18, line 1, column 7: expected six hexadecimal digits, found `70c71g)`");
     }
 
     #[test]
-    fn sample() {
-        test_ignore_whitespaces(
-            "R 6 (#70c710)
-            D 5 (#0dc571)
-            L 2 (#5713f0)
-            D 2 (#d2c081)
-            R 2 (#59c680)
-            D 2 (#411b91)
-            L 5 (#8ceee2)
-            U 2 (#caa173)
-            L 1 (#1b58a2)
-            U 2 (#caa171)
-            R 2 (#7807d2)
-            U 3 (#a77fa3)
-            L 2 (#015232)
-            U 2 (#7a21e3)",
-            "62",
-        );
+    fn not_a_loop() {
+        let error = |input, message| test_error(|i, o| solve_part1::<Lagoon, _, _>(i, o), input, message);
+        error("", "day 18: the dig plan does not dig anything");
+        error("R 6 (#70c710)\nD 5 (#0dc571)", "day 18: there is an odd number of corners in row 5");
     }
 }
@@ -0,0 +1,5 @@
+pub const DAY: u32 = 18;
+
+pub mod lagoon;
+
+pub type Puzzle = lagoon::Lagoon;
@@ -1,6 +1,3 @@
-mod lagoon2;
-
-
 fn main() {
-    lagoon2::main();
+    aoc::solution::main::<day18::Puzzle>();
 }
@@ -0,0 +1,320 @@
+//! What can never happen in the workflows, found by following boxes of
+//! ratings from `in`, and the same workflows without it.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt::{self, Display};
+use std::io::{BufRead, Write};
+use aoc::parse::{Lines, Result};
+use crate::aplenty::{Decision, RangeND, Ratings, System, Workflow, Workflows};
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct Analysis {
+    /// The workflow, position and text of rules no part gets to and matches,
+    /// in workflows some part gets to.
+    dead_rules: Vec<(String, usize, String)>,
+    /// Workflows no part gets to.
+    unreachable: Vec<String>,
+    /// Workflows sending parts around in a circle, starting from the first
+    /// label of each circle.
+    cycles: Vec<Vec<String>>,
+    /// The same decisions for every part, `in` first.
+    simplified: Vec<Workflow>,
+}
+
+/// Rules some part gets to and matches, and the workflows some part gets to.
+#[derive(Default)]
+struct Reached {
+    rules: HashSet<(String, usize)>,
+    workflows: HashSet<String>,
+}
+
+impl Workflows {
+    /// Follows the parts of `range` through `label` and what it sends them to,
+    /// one path at a time. Parts going back to a workflow on the path go round
+    /// forever, so they are not followed.
+    fn reach(&self, label: &str, range: RangeND, path: &mut Vec<String>, reached: &mut Reached) {
+        reached.workflows.insert(label.to_string());
+        path.push(label.to_string());
+
+        let mut remaining = vec![range];
+        for (i, rule) in self.workflows[label].rules.iter().enumerate() {
+            let matched: Vec<RangeND> = remaining.iter()
+                .map(|r| rule.matching(r))
+                .filter(|r| !r.is_empty())
+                .collect();
+            remaining = remaining.iter().flat_map(|r| rule.remaining(r)).collect();
+            if matched.is_empty() {
+                continue;
+            }
+            reached.rules.insert((label.to_string(), i));
+            if let Decision::Next(next) = &rule.matched {
+                if !path.contains(next) {
+                    for m in matched {
+                        self.reach(next, m, path, reached);
+                    }
+                }
+            }
+        }
+        path.pop();
+    }
+
+    /// Every cycle closed by going back to a workflow on the path, walking
+    /// from each workflow in turn.
+    fn cycles(&self) -> Vec<Vec<String>> {
+        fn walk(workflows: &Workflows, label: &str, path: &mut Vec<String>,
+                done: &mut HashSet<String>, cycles: &mut BTreeSet<Vec<String>>) {
+            if let Some(start) = path.iter().position(|l| l == label) {
+                let mut cycle = path[start..].to_vec();
+                let first = cycle.iter().enumerate().min_by_key(|(_, l)| *l).unwrap().0;
+                cycle.rotate_left(first);
+                cycles.insert(cycle);
+                return;
+            }
+            if done.contains(label) {
+                return;
+            }
+            path.push(label.to_string());
+            for rule in &workflows.workflows[label].rules {
+                if let Decision::Next(next) = &rule.matched {
+                    walk(workflows, next, path, done, cycles);
+                }
+            }
+            path.pop();
+            done.insert(label.to_string());
+        }
+
+        let mut labels: Vec<_> = self.workflows.keys().collect();
+        labels.sort();
+        let mut cycles = BTreeSet::new();
+        let mut done = HashSet::new();
+        for label in labels {
+            walk(self, label, &mut Vec::new(), &mut done, &mut cycles);
+        }
+        cycles.into_iter().collect()
+    }
+}
+
+/// Merges each rule into the next one when it sends the same parts to the
+/// same place, returning whether anything changed.
+fn merge_rules(workflow: &mut Workflow) -> bool {
+    let mut changed = false;
+    let mut i = 0;
+    while i + 1 < workflow.rules.len() {
+        let (rule, next) = (&workflow.rules[i], &workflow.rules[i + 1]);
+        if rule.matched == next.matched && next.condition.contains_box(&rule.condition) {
+            workflow.rules.remove(i);
+            changed = true;
+            i = i.saturating_sub(1);
+        } else {
+            i += 1;
+        }
+    }
+    changed
+}
+
+/// The decision of a workflow for every part, if it is a single rule
+/// accepting or rejecting them all.
+fn constant_decision(workflow: &Workflow, full: &RangeND) -> Option<Decision> {
+    match &workflow.rules[..] {
+        [rule] if rule.condition == *full
+            && matches!(rule.matched, Decision::Accepted | Decision::Rejected) => Some(rule.matched.clone()),
+        _ => None,
+    }
+}
+
+impl Analysis {
+    pub(crate) fn new(workflows: &Workflows) -> Analysis {
+        let mut reached = Reached::default();
+        workflows.reach("in", workflows.ratings.full(), &mut Vec::new(), &mut reached);
+
+        let mut labels: Vec<&String> = workflows.workflows.keys().collect();
+        labels.sort_by_key(|l| (*l != "in", *l));
+
+        let mut dead_rules = Vec::new();
+        let mut unreachable = Vec::new();
+        let mut simplified: Vec<Workflow> = Vec::new();
+        for label in labels {
+            if !reached.workflows.contains(label) {
+                unreachable.push(label.to_string());
+                continue;
+            }
+            let workflow = &workflows.workflows[label];
+            let last = workflow.rules.len() - 1;
+            let mut rules = Vec::new();
+            for (i, rule) in workflow.rules.iter().enumerate() {
+                if reached.rules.contains(&(label.to_string(), i)) {
+                    rules.push(rule.clone());
+                } else {
+                    dead_rules.push((label.to_string(), i, rule.to_string()));
+                    // Some rule has to decide what no rule matches.
+                    if i == last {
+                        rules.push(rule.clone());
+                    }
+                }
+            }
+            simplified.push(Workflow { label: label.to_string(), rules });
+        }
+
+        Analysis {
+            dead_rules,
+            unreachable,
+            cycles: workflows.cycles(),
+            simplified: Self::simplify(simplified, &workflows.ratings.full()),
+        }
+    }
+
+    /// Merges rules and inlines the workflows accepting or rejecting every
+    /// part, until there is nothing left to do.
+    fn simplify(mut workflows: Vec<Workflow>, full: &RangeND) -> Vec<Workflow> {
+        loop {
+            let mut changed = false;
+            for workflow in workflows.iter_mut() {
+                changed |= merge_rules(workflow);
+            }
+
+            let constants: HashMap<String, Decision> = workflows.iter()
+                .filter(|w| w.label != "in")
+                .filter_map(|w| constant_decision(w, full).map(|d| (w.label.to_string(), d)))
+                .collect();
+            if !constants.is_empty() {
+                changed = true;
+                workflows.retain(|w| !constants.contains_key(&w.label));
+                for rule in workflows.iter_mut().flat_map(|w| w.rules.iter_mut()) {
+                    if let Decision::Next(label) = &rule.matched {
+                        if let Some(decision) = constants.get(label) {
+                            rule.matched = decision.clone();
+                        }
+                    }
+                }
+            }
+
+            if !changed {
+                return workflows;
+            }
+        }
+    }
+}
+
+fn write_list<T>(f: &mut fmt::Formatter, title: &str, items: &[T],
+                 item: impl Fn(&T) -> String) -> fmt::Result {
+    if items.is_empty() {
+        return writeln!(f, "{}: none", title);
+    }
+    writeln!(f, "{}:", title)?;
+    for i in items {
+        writeln!(f, "  {}", item(i))?;
+    }
+    Ok(())
+}
+
+impl Display for Analysis {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_list(f, "Dead rules", &self.dead_rules, |(label, i, rule)| {
+            format!("{} rule {}: {}", label, i + 1, rule)
+        })?;
+        write_list(f, "Unreachable workflows", &self.unreachable, |label| label.to_string())?;
+        write_list(f, "Cycles", &self.cycles, |cycle| {
+            format!("{} -> {}", cycle.join(" -> "), cycle[0])
+        })?;
+        writeln!(f, "Simplified workflows:")?;
+        for workflow in &self.simplified {
+            writeln!(f, "{}", workflow)?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes the analysis of the workflows in `input`, with the categories and
+/// bounds of `ratings`.
+pub fn analyze<R: BufRead, W: Write>(input: R, mut output: W, ratings: &Ratings) -> Result<()> {
+    let system = System::parse_with(Lines::new(input, crate::DAY), ratings)?;
+    write!(output, "{}", Analysis::new(&system.workflows))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aplenty::tests::SAMPLE;
+
+    fn workflows(input: &str) -> Workflows {
+        System::parse_with(Lines::new(input.as_bytes(), crate::DAY), &Ratings::default()).unwrap().workflows
+    }
+
+    fn analysis(input: &str) -> Analysis {
+        Analysis::new(&workflows(input))
+    }
+
+    fn simplified(analysis: &Analysis) -> Workflows {
+        let text: Vec<String> = analysis.simplified.iter().map(|w| w.to_string()).collect();
+        workflows(&text.join("\n"))
+    }
+
+    #[test]
+    fn sample() {
+        let analysis = analysis(SAMPLE);
+        assert_eq!(analysis.to_string(), "Dead rules: none
+Unreachable workflows: none
+Cycles: none
+Simplified workflows:
+in{s<1351:px,qqz}
+crn{x>2662:A,R}
+hdj{m>838:A,pv}
+pv{a>1716:R,A}
+px{a<2006:qkq,m>2090:A,rfg}
+qkq{x<1416:A,crn}
+qqz{s>2770:A,m<1801:hdj,R}
+rfg{s<537:R,x>2440:R,A}
+");
+        assert_eq!(simplified(&analysis).count_combinations().unwrap(), 167409079868000);
+    }
+
+    #[test]
+    fn dead_ends() {
+        let analysis = analysis("in{x<10:a,x<5:gone,m>100:R,R}
+            a{m<5:c,x>20:R,A}
+            c{a>5:a,x<3:A,A}
+            gone{A}
+            unused{R}");
+        assert_eq!(analysis.to_string(), "Dead rules:
+  in rule 2: x<5:gone
+  a rule 2: x>20:R
+Unreachable workflows:
+  gone
+  unused
+Cycles:
+  a -> c -> a
+Simplified workflows:
+in{x<10:a,R}
+a{m<5:c,A}
+c{a>5:a,A}
+");
+    }
+
+    #[test]
+    fn out_of_bounds() {
+        let analysis = analysis("in{m<10:w,R}
+            w{x<5000:A,A}");
+        assert_eq!(analysis.to_string(), "Dead rules:
+  w rule 2: A
+Unreachable workflows: none
+Cycles: none
+Simplified workflows:
+in{m<10:A,R}
+");
+    }
+
+    #[test]
+    fn operators() {
+        let analysis = analysis("in{x==5:a,x>=5:R,x==5:A,A}
+            a{x<=4:R,A}");
+        assert_eq!(analysis.to_string(), "Dead rules:
+  in rule 3: x==5:A
+  a rule 1: x<=4:R
+Unreachable workflows: none
+Cycles: none
+Simplified workflows:
+in{x==5:A,x>=5:R,A}
+");
+    }
+}
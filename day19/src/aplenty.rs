@@ -1,32 +1,117 @@
-//use std::cmp::{max, min};
-use std::io::{BufRead, BufReader, Write};
-use regex::Regex;
-use lazy_static::lazy_static;
-use std::collections::HashMap;
-
-#[allow(unused_macros)]
-macro_rules! dprintln {
-    ( $( $x:expr ),* ) => {
-        {
-	    #[cfg(test)]
-            println!($($x), *);
-        }
-    };
+use std::fmt::{self, Display};
+use std::io::BufRead;
+use std::collections::{HashMap, HashSet};
+use aoc::debug;
+use aoc::intervals::{Interval, IntervalBox};
+use aoc::parse::{Line, Lines, Parser, Result};
+use aoc::solution::Solution;
+
+/// Parts with a rating of each category in the matching interval.
+pub(crate) type RangeND = IntervalBox<i64>;
+
+/// The number of parts in `range`, which can be more than an `i64` holds
+/// with more categories.
+pub(crate) fn volume(range: &RangeND) -> i128 {
+    range.dims.iter().map(|d| d.len() as i128).product()
+}
+
+/// The categories parts are rated in, and the lowest and highest rating. The
+/// input can give them in a first line like `ratings from 1 to 20 in p,q`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Ratings {
+    /// When empty, the categories of the first part of the input in its
+    /// order, or `x`, `m`, `a` and `s` if there are no parts.
+    pub categories: Vec<String>,
+    pub min: i64,
+    pub max: i64,
 }
 
-fn category(s: &str) -> usize {
-    match s {
-        "x" => 0,
-        "m" => 1,
-        "a" => 2,
-        "s" => 3,
-        _ => panic!("No such category"),
+impl Default for Ratings {
+    fn default() -> Ratings {
+        Ratings {
+            categories: Vec::new(),
+            min: 1,
+            max: 4000,
+        }
     }
 }
 
+impl Ratings {
+    /// Parses a line like `ratings from 1 to 20 in p,q`, where `in` and the
+    /// categories can be left out to take them from the parts.
+    fn parse(line: &Line) -> Result<Ratings> {
+        let mut parser = line.parser();
+        parser.expect("ratings")?;
+        parser.expect("from")?;
+        let min: i64 = parser.number()?;
+        parser.expect("to")?;
+        let max_error = parser.error(&format!("a number from {} to {}", min, i64::MAX - 1));
+        let max: i64 = parser.number()?;
+        if max < min || max == i64::MAX {
+            return Err(max_error);
+        }
+        let mut categories = Vec::new();
+        if !parser.is_done() {
+            let in_error = parser.error("`in` or end of line");
+            if parser.word().ok() != Some("in") {
+                return Err(in_error);
+            }
+            categories = parser.separated(",", |p| p.word().map(String::from))?;
+            parser.end()?;
+        }
+        Ok(Ratings { categories, min, max })
+    }
+
+    fn bounds(&self) -> Interval<i64> { Interval::new(self.min, self.max + 1) }
+
+    /// Parts with any ratings.
+    pub(crate) fn full(&self) -> RangeND {
+        IntervalBox::new(vec![self.bounds(); self.categories.len()])
+    }
+
+    fn category(&self, name: &str) -> Option<usize> {
+        self.categories.iter().position(|c| c == name)
+    }
+
+    /// The categories as what a parse error expected, like `` `x`, `m`, `a` or `s` ``.
+    fn expected(&self) -> String {
+        let names: Vec<String> = self.categories.iter().map(|c| format!("`{}`", c)).collect();
+        match names.split_last() {
+            Some((last, rest)) if !rest.is_empty() => format!("{} or {}", rest.join(", "), last),
+            _ => names.join(""),
+        }
+    }
+
+    /// These ratings with the categories filled in from the first of `parts`
+    /// if they are not given.
+    fn resolve(&self, parts: &[Line]) -> Result<Ratings> {
+        let mut ratings = self.clone();
+        if !ratings.categories.is_empty() {
+            return Ok(ratings);
+        }
+        let Some(first) = parts.first() else {
+            ratings.categories = ["x", "m", "a", "s"].map(String::from).to_vec();
+            return Ok(ratings);
+        };
+
+        let mut parser = first.parser();
+        parser.expect("{")?;
+        ratings.categories = parser.separated(",", |p| {
+            let name = p.word()?;
+            p.expect("=")?;
+            p.number::<i64>()?;
+            Ok(name.to_string())
+        })?;
+        let mut seen = HashSet::new();
+        if let Some(twice) = ratings.categories.iter().find(|c| !seen.insert(*c)) {
+            return Err(System::invalid(&format!("the first part is rated twice in `{}`", twice)));
+        }
+        Ok(ratings)
+    }
+}
 
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
-enum Decision {
+pub(crate) enum Decision {
     Next(String),
     Rejected,
     Accepted,
@@ -42,130 +127,236 @@ impl Decision {
     }
 }
 
+impl Display for Decision {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Decision::Next(label) => write!(f, "{}", label),
+            Decision::Rejected => write!(f, "R"),
+            Decision::Accepted => write!(f, "A"),
+        }
+    }
+}
+
+/// Longer operators first, as `<` starts `<=`.
+const OPERATORS: [&str; 5] = ["<=", ">=", "==", "<", ">"];
+
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
-struct Rule {
-    min_conditions: Vec<i64>,
-    max_conditions: Vec<i64>,
+pub(crate) struct Rule {
+    /// As written, like `a<2006`.
+    comparison: Option<String>,
+    pub(crate) condition: RangeND,
 
-    matched: Decision,
+    pub(crate) matched: Decision,
 }
 
 impl Rule {
-    fn from_str(s: &str) -> Rule {
-        lazy_static! {
-            static ref RULE_RE: Regex = Regex::new(
-                r"((?P<cat>[xmas])(?P<kind>[><])(?P<val>\d+):)?(?P<label>.*)"
-            ).unwrap();
+    /// Parses either a bare label, or a condition like `a<2006:qkq` comparing
+    /// with any of `OPERATORS`.
+    fn parse(parser: &mut Parser, ratings: &Ratings) -> Result<Rule> {
+        let mut condition = ratings.full();
+        let mut comparison = None;
+
+        let category_error = parser.error(&ratings.expected());
+        let mut label = parser.word()?;
+        if let Some(op) = OPERATORS.iter().find(|op| parser.skip(op)) {
+            let cat_idx = ratings.category(label).ok_or(category_error)?;
+            let number_error = parser.error(&format!("a number below {}", i64::MAX));
+            let val: i64 = parser.number()?;
+            let Some(after) = val.checked_add(1) else {
+                return Err(number_error);
+            };
+            parser.expect(":")?;
+            let bounds = ratings.bounds();
+            condition.dims[cat_idx] = match *op {
+                "<" => Interval::new(bounds.start, val),
+                "<=" => Interval::new(bounds.start, after),
+                ">" => Interval::new(after, bounds.end),
+                ">=" => Interval::new(val, bounds.end),
+                _ => Interval::new(val, after),
+            }.intersection(&bounds);
+            comparison = Some(format!("{}{}{}", label, op, val));
+            label = parser.word()?;
         }
-        let caps = RULE_RE.captures(s).unwrap();
 
-        let mut min_conditions = vec![i64::MIN; 4];
-        let mut max_conditions = vec![i64::MAX; 4];
+        Ok(Rule {
+            comparison,
+            condition,
 
-        if let Some(c) = caps.name("cat") {
-            let cat = c.as_str();
-            let kind = caps.name("kind").unwrap().as_str();
-            let val: i64 = caps.name("val").unwrap().as_str().parse().unwrap();
+            matched: Decision::from_label(label),
+        })
+    }
 
-            let cat_idx = category(cat);
-            match kind {
-                ">" => min_conditions[cat_idx] = val,
-                "<" => max_conditions[cat_idx] = val,
-                _ => panic!("Don't know this kind"),
-            }
-        }
-        let label = caps.name("label").unwrap().as_str();
+    pub(crate) fn comparison(&self) -> Option<&str> {
+        self.comparison.as_deref()
+    }
 
-        Rule {
-            min_conditions,
-            max_conditions,
+    fn matches(&self, part: &Part) -> bool {
+        self.condition.contains(&part.vals)
+    }
 
-            matched: Decision::from_label(&label),
-        }
+    // Everything that matches
+    pub(crate) fn matching(&self, range: &RangeND) -> RangeND {
+        self.condition.intersection(range)
     }
 
-    fn matches(&self, part: &Part) -> bool {
-        let greater = self.min_conditions.iter().enumerate().
-            all(|(i, x)| part.vals[i] > *x);
-        let less = self.max_conditions.iter().enumerate().
-            all(|(i, x)| part.vals[i] < *x);
-        greater && less
+    // Everything that doesn't match
+    pub(crate) fn remaining(&self, range: &RangeND) -> Vec<RangeND> {
+        range.difference(&self.condition)
+    }
+}
+
+/// Writes the rule back the way it is parsed.
+impl Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.comparison {
+            Some(comparison) => write!(f, "{}:{}", comparison, self.matched),
+            None => write!(f, "{}", self.matched),
+        }
     }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
-struct Workflow {
-    label: String,
-    rules: Vec<Rule>,
+pub(crate) struct Workflow {
+    pub(crate) label: String,
+    pub(crate) rules: Vec<Rule>,
 }
 
 impl Workflow {
-    fn from_line(line: &str) -> Workflow {
-        lazy_static! {
-            static ref WORKFLOW_RE: Regex = Regex::new(
-                r"(?P<label>.*)\{(?P<rules>.*)\}"
-            ).unwrap();
+    fn parse(line: &Line, ratings: &Ratings) -> Result<Workflow> {
+        let mut parser = line.parser();
+        let label = parser.word()?.to_string();
+        parser.expect("{")?;
+        let rules: Vec<Rule> = parser.separated(",", |p| Rule::parse(p, ratings))?;
+        // Whatever no other rule matches has to go somewhere.
+        if rules[rules.len() - 1].comparison.is_some() {
+            return Err(parser.error("a last rule without a condition"));
         }
-        let caps = WORKFLOW_RE.captures(line).unwrap();
-
-        let mut rules = Vec::new();
+        parser.expect("}")?;
+        parser.end()?;
 
-        for r in caps.name("rules").unwrap().as_str().split(',') {
-            rules.push(Rule::from_str(r));
-        }
-
-        Workflow {
-            label: caps.name("label").unwrap().as_str().to_string(),
+        Ok(Workflow {
+            label,
             rules,
-        }
+        })
     }
 
     fn decide(&self, part: &Part) -> Decision {
-        for rule in &self.rules {
+        let (last, rules) = self.rules.split_last().unwrap();
+        for rule in rules {
             if rule.matches(part) {
                 return rule.matched.clone();
             }
         }
-        panic!("No rule matches! Workflow: {:?}, part: {:?}", self, part);
+        last.matched.clone()
+    }
+}
+
+impl Display for Workflow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let rules: Vec<String> = self.rules.iter().map(|r| r.to_string()).collect();
+        write!(f, "{}{{{}}}", self.label, rules.join(","))
     }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
-struct Workflows {
-    workflows: HashMap<String, Workflow>,
+pub(crate) struct Workflows {
+    pub(crate) workflows: HashMap<String, Workflow>,
+    pub(crate) ratings: Ratings,
 }
 
 impl Workflows {
-    fn from_input<I>(lines: &mut I) -> Workflows
-        where I: Iterator<Item = String>
-    {
+    fn parse(lines: &[Line], ratings: Ratings) -> Result<Workflows> {
         let mut workflows = HashMap::new();
-        
-        while let Some(l) = lines.next() {
-            let line = l.trim();
-            if line == "" {
-                break;
-            }
-            let w = Workflow::from_line(line);
+
+        for line in lines {
+            let w = Workflow::parse(line, &ratings)?;
             workflows.insert(w.label.to_string(), w);
         }
 
-        Workflows {
-            workflows,
+        if !workflows.contains_key("in") {
+            return Err(System::invalid("there is no workflow `in`"));
+        }
+        for workflow in workflows.values() {
+            for rule in &workflow.rules {
+                if let Decision::Next(label) = &rule.matched {
+                    if !workflows.contains_key(label) {
+                        return Err(System::invalid(&format!(
+                            "workflow `{}` sends parts to unknown workflow `{}`",
+                            workflow.label, label)));
+                    }
+                }
+            }
         }
+
+        Ok(Workflows {
+            workflows,
+            ratings,
+        })
     }
 
-    fn decide(&self, part: &Part) -> Decision {
+    fn decide(&self, part: &Part) -> Result<Decision> {
         let mut next = Decision::Next("in".to_string());
+        let mut path = Vec::new();
 
-        dprintln!("Processing part: {:?}", part);
+        debug!("Processing part: {:?}", part);
         while let Decision::Next(label) = next {
-            dprintln!(" - label: {:?}", label);
-            let workflow = self.workflows.get(&label).unwrap();
-            next = workflow.decide(part);
+            debug!(" - label: {:?}", label);
+            check_not_on(&path, &label)?;
+            next = self.workflows[&label].decide(part);
+            path.push(label);
         }
-        dprintln!(" - decision: {:?}", next);
-        next
+        debug!(" - decision: {:?}", next);
+        Ok(next)
+    }
+
+    /// The parts of `range` accepted once they are sent to `dec` by the
+    /// workflows on `path`.
+    fn accepted_ranges_for(&self, dec: &Decision, range: RangeND, path: &mut Vec<String>) -> Result<Vec<RangeND>> {
+        let label = match dec {
+            Decision::Accepted => return Ok(vec![range]),
+            Decision::Rejected => return Ok(Vec::new()),
+            Decision::Next(l) => l,
+        };
+        debug!("Ranges for {:?}", label);
+        check_not_on(path, label)?;
+        path.push(label.to_string());
+        let workflow = &self.workflows[label];
+
+        let mut remaining = vec![range];
+        let mut result = Vec::new();
+
+        for rule in &workflow.rules {
+            debug!("--{}-- rule {:?}", label, rule);
+            let matched: Vec<RangeND> = remaining.iter()
+                .map(|r| rule.matching(r))
+                .filter(|r| !r.is_empty())
+                .collect();
+            remaining = remaining.iter().flat_map(|r| rule.remaining(r)).collect();
+            debug!("--{}-- matching {:?}", label, matched);
+            debug!("--{}-- remaining {:?}", label, remaining);
+
+            for m in matched {
+                result.append(&mut self.accepted_ranges_for(&rule.matched, m, path)?);
+            }
+        }
+        path.pop();
+        debug!(">>{}<< result{:?}", label, result);
+        Ok(result)
+    }
+
+    pub(crate) fn count_combinations(&self) -> Result<i128> {
+        let ranges = self.accepted_ranges_for(&Decision::Next("in".to_string()), self.ratings.full(), &mut Vec::new())?;
+        Ok(ranges.iter().map(volume).sum())
+    }
+}
+
+/// Parts sent back to a workflow they went through would go round forever,
+/// so they are never decided on. Workflows no part follows round are fine.
+fn check_not_on(path: &[String], label: &str) -> Result<()> {
+    match path.iter().position(|l| l == label) {
+        Some(start) => Err(System::invalid(&format!(
+            "the workflows send parts round in a circle: {} -> {}", path[start..].join(" -> "), label))),
+        None => Ok(()),
     }
 }
 
@@ -175,23 +366,33 @@ struct Part {
 }
 
 impl Part {
-    fn from_line(line: &str) -> Part {
-        lazy_static! {
-            static ref PART_RE: Regex = Regex::new(
-                r"\{x=(?P<x>\d+),m=(?P<m>\d+),a=(?P<a>\d+),s=(?P<s>\d+)\}"
-            ).unwrap();
+    fn parse(line: &Line, ratings: &Ratings) -> Result<Part> {
+        let mut parser = line.parser();
+        parser.expect("{")?;
+        let mut vals = vec![0; ratings.categories.len()];
+        for (i, cat) in ratings.categories.iter().enumerate() {
+            if i > 0 {
+                parser.expect(",")?;
+            }
+            // The whole name, as one category can start another.
+            let category_error = parser.error(&format!("`{}`", cat));
+            if parser.word().ok() != Some(cat.as_str()) {
+                return Err(category_error);
+            }
+            parser.expect("=")?;
+            // Ratings outside of those would escape the conditions of part 2.
+            let error = parser.error(&format!("a rating from {} to {}", ratings.min, ratings.max));
+            vals[i] = parser.number()?;
+            if !(ratings.min..=ratings.max).contains(&vals[i]) {
+                return Err(error);
+            }
         }
-        let caps = PART_RE.captures(line).unwrap();
+        parser.expect("}")?;
+        parser.end()?;
 
-        let mut vals = vec![0; 4];
-        vals[0] = caps.name("x").unwrap().as_str().parse().unwrap();
-        vals[1] = caps.name("m").unwrap().as_str().parse().unwrap();
-        vals[2] = caps.name("a").unwrap().as_str().parse().unwrap();
-        vals[3] = caps.name("s").unwrap().as_str().parse().unwrap();
-
-        Part {
+        Ok(Part {
             vals,
-        }
+        })
     }
 
     fn sum(&self) -> i64 {
@@ -199,69 +400,200 @@ impl Part {
     }
 }
 
+pub struct System {
+    pub(crate) workflows: Workflows,
+    parts: Vec<Part>,
+}
 
+impl System {
+    /// Parses the input with the categories and bounds of `ratings`, unless
+    /// its first line gives others.
+    pub fn parse_with<R: BufRead>(mut lines: Lines<R>, ratings: &Ratings) -> Result<System> {
+        let given = match lines.peek()? {
+            Some(line) if line.text.split_whitespace().next() == Some("ratings") => Some(Ratings::parse(line)?),
+            _ => None,
+        };
+        if given.is_some() {
+            lines.next();
+        }
+        let ratings = given.as_ref().unwrap_or(ratings);
 
-fn solve<R: BufRead, W: Write>(input: R, mut output: W) {
-    let mut lines = BufReader::new(input).lines().map(|l| l.unwrap());
-    let workflows = Workflows::from_input(&mut lines);
-    dprintln!("Workflows: {:?}", workflows);
-    
-    let mut parts = Vec::new();
-    for l in lines {
-        parts.push(Part::from_line(l.trim()));
-    }
-    dprintln!("Parts: {:?}", parts);
+        let workflow_lines = lines.block()?;
+        let part_lines = lines.non_blank()?;
 
-    let mut solution: i64 = 0;
-    for part in parts {
-        if workflows.decide(&part) == Decision::Accepted {
-            solution += part.sum();
+        let workflows = Workflows::parse(&workflow_lines, ratings.resolve(&part_lines)?)?;
+        debug!("Workflows: {:?}", workflows);
+
+        let mut parts = Vec::new();
+        for line in &part_lines {
+            parts.push(Part::parse(line, &workflows.ratings)?);
         }
-    }
+        debug!("Parts: {:?}", parts);
 
-    writeln!(output, "{}", solution).unwrap();
+        Ok(System {
+            workflows,
+            parts,
+        })
+    }
 }
 
-pub fn main() {
-    let stdin = std::io::stdin();
-    let stdout = std::io::stdout();
-    solve(stdin.lock(), stdout.lock());
+impl Solution for System {
+    const DAY: u32 = crate::DAY;
+
+    fn parse<R: BufRead>(lines: Lines<R>) -> Result<System> {
+        System::parse_with(lines, &Ratings::default())
+    }
+
+    fn part1(&self) -> Result<impl Display> {
+        let mut solution: i64 = 0;
+        for part in &self.parts {
+            if self.workflows.decide(part)? == Decision::Accepted {
+                solution += part.sum();
+            }
+        }
+        Ok(solution)
+    }
+
+    fn part2(&self) -> Result<impl Display> {
+        self.workflows.count_combinations()
+    }
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
+    use aoc::solution::{solve_part1, solve_part2};
+
+    fn parse_with(input: &str, ratings: &Ratings) -> System {
+        System::parse_with(Lines::new(input.as_bytes(), crate::DAY), ratings).unwrap()
+    }
 
-    fn test_ignore_whitespaces(input: &str, output: &str) {
-        let mut actual_out: Vec<u8> = Vec::new();
-        solve(input.as_bytes(), &mut actual_out);
-        let actual_out_str = String::from_utf8(actual_out).unwrap();
-        let actual_outs = actual_out_str.split_whitespace().collect::<Vec<&str>>();
-        let expected_outs = output.split_whitespace().collect::<Vec<&str>>();
-        assert_eq!(actual_outs, expected_outs);
+    fn test_error(input: &str, error: &str) {
+        aoc::testing::test_error(|i, o| solve_part1::<System, _, _>(i, o), input, error);
     }
 
+    pub(crate) const SAMPLE: &str = include_str!("../examples/aplenty.sample.in");
+
+    include!(concat!(env!("OUT_DIR"), "/examples/aplenty.rs"));
+
     #[test]
-    fn sample() {
-        test_ignore_whitespaces(
-            "px{a<2006:qkq,m>2090:A,rfg}
-            pv{a>1716:R,A}
-            lnx{m>1548:A,A}
-            rfg{s<537:gd,x>2440:R,A}
-            qs{s>3448:A,lnx}
-            qkq{x<1416:A,crn}
-            crn{x>2662:A,R}
-            in{s<1351:px,qqz}
-            qqz{s>2770:qs,m<1801:hdj,R}
-            gd{a>3333:R,R}
-            hdj{m>838:A,pv}
-
-            {x=787,m=2655,a=1222,s=2876}
-            {x=1679,m=44,a=2067,s=496}
-            {x=2036,m=264,a=79,s=2244}
-            {x=2461,m=1339,a=466,s=291}
-            {x=2127,m=1623,a=2188,s=1013}",
-            "19114",
+    fn malformed() {
+        test_error(
+            "in{q<5:A,R}
+
+            {x=1,m=2,a=3,s=4}",
+            "day 19, line 1, column 4: expected `x`, `m`, `a` or `s`, found `q<5:A,R}`",
+        );
+        test_error(
+            "in{x<5:A,R}
+
+            {x=1,m=2,a=3,s=4}
+            {x=1,m=2,s=4}",
+            "day 19, line 4, column 22: expected `a`, found `s=4}`",
+        );
+        test_error(
+            "in{x<5:A,R}
+
+            {x=1,m=2,x=4}",
+            "day 19: the first part is rated twice in `x`",
+        );
+        test_error(
+            "in{x<5:px,R}
+
+            {x=1,m=2,a=3,s=4}",
+            "day 19: workflow `in` sends parts to unknown workflow `px`",
+        );
+        test_error(
+            "in{x<9223372036854775807:A,R}
+
+            {x=1,m=2,a=3,s=4}",
+            "day 19, line 1, column 6: expected a number below 9223372036854775807, found `9223372036854775807:A,R}`",
+        );
+        test_error(
+            "in{x<5:A,xa>2:R,A}
+
+            {x=1,xa=2}
+            {xa=1,x=2}",
+            "day 19, line 4, column 14: expected `x`, found `xa=1,x=2}`",
+        );
+        test_error(
+            "in{x<5:A,R}
+
+            {x=1,m=0,a=3,s=4}",
+            "day 19, line 3, column 20: expected a rating from 1 to 4000, found `0,a=3,s=4}`",
+        );
+        test_error(
+            "in{x<5:A}
+
+            {x=6,m=2,a=3,s=4}",
+            "day 19, line 1, column 9: expected a last rule without a condition, found `}`",
         );
+        test_error(
+            "in{x<10:a,R}
+            a{m<5:c,A}
+            c{a>5:a,A}
+
+            {x=1,m=2,a=6,s=4}",
+            "day 19: the workflows send parts round in a circle: a -> c -> a",
+        );
+    }
+
+    #[test]
+    fn circles() {
+        // Only parts with a>5 go round, and there are none of them in part 1.
+        let input = "in{x<10:a,R}
+            a{m<5:c,A}
+            c{a>5:a,A}
+
+            {x=1,m=2,a=3,s=4}";
+        aoc::testing::test_ignore_whitespaces(|i, o| solve_part1::<System, _, _>(i, o), input, "10");
+        aoc::testing::test_error(|i, o| solve_part2::<System, _, _>(i, o), input,
+                                 "day 19: the workflows send parts round in a circle: a -> c -> a");
+
+        // No part can go all the way round, nor get to u and v.
+        let system = parse_with("in{x<5:a,A}
+            a{x>10:in,A}
+            u{v}
+            v{u}
+
+            {x=1,m=2,a=3,s=4}", &Ratings::default());
+        assert_eq!(system.part1().unwrap().to_string(), "10");
+        assert_eq!(system.part2().unwrap().to_string(), "256000000000000");
+    }
+
+    #[test]
+    fn operators() {
+        let system = parse_with("in{a<=2:x,b>=3:A,R}
+            x{b==1:R,A}
+
+            {a=1,b=1}
+            {a=2,b=2}
+            {a=3,b=3}
+            {a=4,b=1}", &Ratings { max: 5, ..Ratings::default() });
+        assert_eq!(system.part1().unwrap().to_string(), "10");
+        assert_eq!(system.part2().unwrap().to_string(), "17");
+    }
+
+    #[test]
+    fn categories() {
+        let ratings = Ratings { categories: vec!["p".to_string(), "q".to_string()], min: 1, max: 4 };
+        assert_eq!(parse_with("in{p>2:A,q==1:A,R}", &ratings).part2().unwrap().to_string(), "10");
+        let input = "ratings from 1 to 4 in p,q
+            in{p>2:A,q==1:A,R}";
+        aoc::testing::test_ignore_whitespaces(|i, o| solve_part2::<System, _, _>(i, o), input, "10");
+        let input = "ratings from 0 to 9
+            in{p>2:A,R}
+
+            {p=0,q=9}
+            {p=9,q=0}";
+        aoc::testing::test_ignore_whitespaces(|i, o| solve_part1::<System, _, _>(i, o), input, "9");
+        aoc::testing::test_ignore_whitespaces(|i, o| solve_part2::<System, _, _>(i, o), input, "70");
+        test_error("ratings from 5 to 1\nin{A}",
+                   "day 19, line 1, column 19: expected a number from 5 to 9223372036854775806, found `1`");
+        test_error("ratings from 1 to 5 of p\nin{A}",
+                   "day 19, line 1, column 21: expected `in` or end of line, found `of`");
+        // More combinations than an i64 holds.
+        let system = parse_with("in{A}\n\n{a=1,b=1,c=1,d=1,e=1,f=1}", &Ratings::default());
+        assert_eq!(system.part2().unwrap().to_string(), "4096000000000000000000");
     }
 }
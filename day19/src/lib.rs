@@ -0,0 +1,7 @@
+pub const DAY: u32 = 19;
+
+pub mod analysis;
+pub mod aplenty;
+pub mod tree;
+
+pub type Puzzle = aplenty::System;
@@ -1,5 +1,3 @@
-mod aplenty2;
-
 fn main() {
-    aplenty2::main();
+    aoc::solution::main::<day19::Puzzle>();
 }
@@ -0,0 +1,229 @@
+//! The workflows unfolded from `in` into one decision tree over the ratings,
+//! to see which parts end up where and check `count_combinations` against it.
+
+use std::fmt::Write as _;
+use std::io::{BufRead, Write};
+use aoc::parse::{Lines, Result};
+use crate::aplenty::{volume, Decision, RangeND, Ratings, System, Workflows};
+
+/// What happens to some parts in the end.
+#[derive(Debug, PartialEq, Eq, Clone)]
+enum Outcome {
+    Accepted,
+    Rejected,
+    /// Sent back to a workflow they already went through, so round forever.
+    Cycle(String),
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+enum Node {
+    /// A rule which some of the parts getting to it match, and some do not.
+    Test {
+        workflow: String,
+        comparison: String,
+        matched: Box<Node>,
+        unmatched: Box<Node>,
+    },
+    /// The parts in `ranges`, after going through the workflows on `path`.
+    Leaf {
+        outcome: Outcome,
+        ranges: Vec<RangeND>,
+        path: Vec<String>,
+    },
+}
+
+/// Every way through the workflows, one path at a time, so it is as large as
+/// the workflows only when each is sent parts from a single rule.
+#[derive(Debug)]
+pub(crate) struct Tree {
+    root: Node,
+    ratings: Ratings,
+}
+
+impl Tree {
+    pub(crate) fn new(workflows: &Workflows) -> Tree {
+        let all = vec![workflows.ratings.full()];
+        Tree {
+            root: Self::decide(workflows, &Decision::Next("in".to_string()), all, &mut Vec::new()),
+            ratings: workflows.ratings.clone(),
+        }
+    }
+
+    /// The tree of `ranges` sent to `decision` by the workflows on `path`.
+    fn decide(workflows: &Workflows, decision: &Decision, ranges: Vec<RangeND>, path: &mut Vec<String>) -> Node {
+        let outcome = match decision {
+            Decision::Accepted => Outcome::Accepted,
+            Decision::Rejected => Outcome::Rejected,
+            Decision::Next(label) if path.contains(label) => Outcome::Cycle(label.to_string()),
+            Decision::Next(label) => {
+                path.push(label.to_string());
+                let node = Self::rule(workflows, label, 0, ranges, path);
+                path.pop();
+                return node;
+            },
+        };
+        Node::Leaf { outcome, ranges, path: path.clone() }
+    }
+
+    /// The tree of `ranges` getting to rule `i` of workflow `label`. Rules
+    /// matching all of them or none are no choice, so they are no `Test`.
+    /// The last rule has no condition, so nothing gets past it.
+    fn rule(workflows: &Workflows, label: &str, i: usize, ranges: Vec<RangeND>, path: &mut Vec<String>) -> Node {
+        let rule = &workflows.workflows[label].rules[i];
+        let matched: Vec<RangeND> = ranges.iter()
+            .map(|r| rule.matching(r))
+            .filter(|r| !r.is_empty())
+            .collect();
+        let unmatched: Vec<RangeND> = ranges.iter().flat_map(|r| rule.remaining(r)).collect();
+
+        if matched.is_empty() {
+            return Self::rule(workflows, label, i + 1, unmatched, path);
+        }
+        if unmatched.is_empty() {
+            return Self::decide(workflows, &rule.matched, matched, path);
+        }
+        Node::Test {
+            workflow: label.to_string(),
+            comparison: rule.comparison().unwrap_or_default().to_string(),
+            matched: Box::new(Self::decide(workflows, &rule.matched, matched, path)),
+            unmatched: Box::new(Self::rule(workflows, label, i + 1, unmatched, path)),
+        }
+    }
+
+    /// Like `x=1..1415 m=1..4000 a=1..2005 s=1..1350`, both ends included.
+    fn range_text(&self, range: &RangeND) -> String {
+        let dims: Vec<String> = self.ratings.categories.iter().zip(&range.dims)
+            .map(|(category, dim)| format!("{}={}..{}", category, dim.start, dim.end - 1))
+            .collect();
+        dims.join(" ")
+    }
+
+    /// The tree in Graphviz DOT, each leaf with the ranges of parts ending
+    /// there and how many parts that is.
+    pub(crate) fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph workflows {\n");
+        self.write_node(&self.root, &mut 0, &mut dot);
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Writes `node` and what is below it, numbered from `next_id`, and
+    /// returns its number.
+    fn write_node(&self, node: &Node, next_id: &mut usize, dot: &mut String) -> usize {
+        let id = *next_id;
+        *next_id += 1;
+        match node {
+            Node::Test { workflow, comparison, matched, unmatched } => {
+                writeln!(dot, "  n{} [label=\"{}: {}\"];", id, workflow, comparison).unwrap();
+                for (child, answer) in [(matched, "yes"), (unmatched, "no")] {
+                    let child_id = self.write_node(child, next_id, dot);
+                    writeln!(dot, "  n{} -> n{} [label=\"{}\"];", id, child_id, answer).unwrap();
+                }
+            },
+            Node::Leaf { outcome, ranges, .. } => {
+                let (mut label, color) = match outcome {
+                    Outcome::Accepted => ("A".to_string(), "palegreen"),
+                    Outcome::Rejected => ("R".to_string(), "lightpink"),
+                    Outcome::Cycle(label) => (format!("cycle back to {}", label), "lightgrey"),
+                };
+                for range in ranges {
+                    write!(label, "\\n{}", self.range_text(range)).unwrap();
+                }
+                write!(label, "\\nvolume {}", ranges.iter().map(volume).sum::<i128>()).unwrap();
+                writeln!(dot, "  n{} [shape=box, style=filled, fillcolor={}, label=\"{}\"];",
+                         id, color, label).unwrap();
+            },
+        }
+        id
+    }
+
+    fn accepted<'a>(node: &'a Node, found: &mut Vec<(&'a [String], &'a RangeND)>) {
+        match node {
+            Node::Test { matched, unmatched, .. } => {
+                Self::accepted(matched, found);
+                Self::accepted(unmatched, found);
+            },
+            Node::Leaf { outcome: Outcome::Accepted, ranges, path } => {
+                found.extend(ranges.iter().map(|r| (&path[..], r)));
+            },
+            Node::Leaf { .. } => {},
+        }
+    }
+
+    /// Every disjoint range of accepted parts on a line, with the workflows
+    /// it went through, and the number of all of them at the end.
+    pub(crate) fn accepted_text(&self) -> String {
+        let mut found = Vec::new();
+        Self::accepted(&self.root, &mut found);
+
+        let mut text = String::new();
+        for (path, range) in &found {
+            writeln!(text, "{}: {} ({})", path.join(" -> "), self.range_text(range), volume(range)).unwrap();
+        }
+        writeln!(text, "total: {}", found.iter().map(|(_, r)| volume(r)).sum::<i128>()).unwrap();
+        text
+    }
+}
+
+/// Writes the decision tree of the workflows in `input` as Graphviz DOT.
+pub fn export_dot<R: BufRead, W: Write>(input: R, mut output: W, ratings: &Ratings) -> Result<()> {
+    let system = System::parse_with(Lines::new(input, crate::DAY), ratings)?;
+    write!(output, "{}", Tree::new(&system.workflows).to_dot())?;
+    Ok(())
+}
+
+/// Writes every range of parts the workflows in `input` accept.
+pub fn list_accepted<R: BufRead, W: Write>(input: R, mut output: W, ratings: &Ratings) -> Result<()> {
+    let system = System::parse_with(Lines::new(input, crate::DAY), ratings)?;
+    write!(output, "{}", Tree::new(&system.workflows).accepted_text())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aplenty::tests::SAMPLE;
+
+    fn parse_tree(input: &str, ratings: &Ratings) -> Tree {
+        Tree::new(&System::parse_with(Lines::new(input.as_bytes(), crate::DAY), ratings).unwrap().workflows)
+    }
+
+    fn small() -> Ratings {
+        Ratings { categories: vec!["x".to_string(), "m".to_string()], min: 1, max: 20 }
+    }
+
+    #[test]
+    fn dot() {
+        let tree = parse_tree("in{x<5:A,x<3:R,px}
+            px{m>10:in,m==3:R,A}", &small());
+        assert_eq!(tree.to_dot(), r#"digraph workflows {
+  n0 [label="in: x<5"];
+  n1 [shape=box, style=filled, fillcolor=palegreen, label="A\nx=1..4 m=1..20\nvolume 80"];
+  n0 -> n1 [label="yes"];
+  n2 [label="px: m>10"];
+  n3 [shape=box, style=filled, fillcolor=lightgrey, label="cycle back to in\nx=5..20 m=11..20\nvolume 160"];
+  n2 -> n3 [label="yes"];
+  n4 [label="px: m==3"];
+  n5 [shape=box, style=filled, fillcolor=lightpink, label="R\nx=5..20 m=3..3\nvolume 16"];
+  n4 -> n5 [label="yes"];
+  n6 [shape=box, style=filled, fillcolor=palegreen, label="A\nx=5..20 m=1..2\nx=5..20 m=4..10\nvolume 144"];
+  n4 -> n6 [label="no"];
+  n2 -> n4 [label="no"];
+  n0 -> n2 [label="no"];
+}
+"#);
+    }
+
+    #[test]
+    fn accepted() {
+        let tree = parse_tree("in{x<5:A,px}
+            px{m>10:R,m<=3:R,A}", &small());
+        assert_eq!(tree.accepted_text(), "in: x=1..4 m=1..20 (80)
+in -> px: x=5..20 m=4..10 (112)
+total: 192
+");
+
+        let sample = parse_tree(SAMPLE, &Ratings::default());
+        assert!(sample.accepted_text().ends_with("\ntotal: 167409079868000\n"));
+    }
+}
@@ -1,17 +1,9 @@
 //use std::cmp::{max, min};
-use std::io::{BufRead, BufReader, Write};
-use regex::Regex;
-use lazy_static::lazy_static;
-//use std::fmt;
-
-macro_rules! dprintln {
-    ( $( $x:expr ),* ) => {
-        {
-	    #[cfg(test)]
-            println!($($x), *);
-        }
-    };
-}
+use std::fmt::Display;
+use std::io::BufRead;
+use aoc::debug;
+use aoc::parse::{Line, Lines, Parser, Result};
+use aoc::solution::Solution;
 
 #[derive(Debug)]
 struct Set {
@@ -21,33 +13,28 @@ struct Set {
 }
 
 impl Set {
-    fn from_string(st: &str) -> Set {
-        dprintln!("string for Set: {}", st);
-
+    fn parse(parser: &mut Parser) -> Result<Set> {
         let mut set = Set {
             red: 0,
             green: 0,
             blue: 0,
         };
 
-        for color in st.trim().split(",").collect::<Vec<_>>() {
-            let c = color.trim();
-
-
-            let split_space = c.split(" ").collect::<Vec<_>>();
-
-            let num = split_space[0].parse::<i64>().unwrap();
-            if c.ends_with("red") {
-                set.red += num;
-            } else if c.ends_with("green") {
-                set.green += num;
-            } else if c.ends_with("blue") {
-                set.blue += num;
+        let colors = parser.separated(",", |p| {
+            let num = p.number::<i64>()?;
+            let color = p.one_of(&[("red", 0), ("green", 1), ("blue", 2)])?;
+            Ok((num, color))
+        })?;
+        for (num, color) in colors {
+            match color {
+                0 => set.red += num,
+                1 => set.green += num,
+                _ => set.blue += num,
             }
         }
-        dprintln!("final set: {:?}", set);
+        debug!("final set: {:?}", set);
 
-        return set;
+        Ok(set)
     }
 }
 
@@ -58,26 +45,18 @@ struct Game {
 }
 
 impl Game {
-    fn from_string(st: &str) -> Game {
-        let s = st.trim();
-
-        lazy_static! {
-            static ref GAME_RE : Regex = Regex::new(
-                r"Game (?P<game>\d+): (?P<sets>.*)"
-            ).unwrap();
-        }
-
-        let caps = GAME_RE.captures(s).unwrap();
-        let mut sets = Vec::<Set>::new();
-
-        for set in caps.name("sets").unwrap().as_str().split(";").collect::<Vec<_>>() {
-            sets.push(Set::from_string(set));
-        }
-
-        Game {
-            number: caps.name("game").unwrap().as_str().parse::<i64>().unwrap(),
-            sets: sets,
-        }
+    fn parse(line: &Line) -> Result<Game> {
+        let mut parser = line.parser();
+        parser.expect("Game")?;
+        let number = parser.number()?;
+        parser.expect(":")?;
+        let sets = parser.separated(";", Set::parse)?;
+        parser.end()?;
+
+        Ok(Game {
+            number,
+            sets,
+        })
     }
 
     fn is_possible(&self, total_red: i64, total_green: i64, total_blue: i64) -> bool {
@@ -86,72 +65,77 @@ impl Game {
                 return false;
             }
         }
-        return true;
+        true
     }
-}
 
-fn solve<R: BufRead, W: Write>(input: R, mut output: W) {
-    let mut solution: i64 = 0;
+    fn power(&self) -> i64 {
+        let max_red = self.sets.iter().map(|s| s.red).max().unwrap();
+        let max_green = self.sets.iter().map(|s| s.green).max().unwrap();
+        let max_blue = self.sets.iter().map(|s| s.blue).max().unwrap();
 
-    let max_red = 12;
-    let max_green = 13;
-    let max_blue = 14;
+        max_red * max_green * max_blue
+    }
+}
+
+pub struct Games {
+    games: Vec<Game>,
+}
 
-    for line_res in BufReader::new(input).lines() {
-        let line = line_res.unwrap();
-        let game = Game::from_string(&line);
-        dprintln!("Game: {:?}", game);
+impl Solution for Games {
+    const DAY: u32 = crate::DAY;
 
-        if game.is_possible(max_red, max_green, max_blue) {
-            solution += game.number;
+    fn parse<R: BufRead>(lines: Lines<R>) -> Result<Games> {
+        let mut games = Vec::new();
+        for line in lines {
+            let game = Game::parse(&line?)?;
+            debug!("Game: {:?}", game);
+            games.push(game);
         }
+        Ok(Games { games })
     }
 
-    writeln!(output, "{}", solution).unwrap();
-}
+    fn part1(&self) -> Result<impl Display> {
+        let max_red = 12;
+        let max_green = 13;
+        let max_blue = 14;
 
-pub fn main() {
-    let stdin = std::io::stdin();
-    let stdout = std::io::stdout();
-    solve(stdin.lock(), stdout.lock());
+        Ok(self.games.iter()
+            .filter(|g| g.is_possible(max_red, max_green, max_blue))
+            .map(|g| g.number)
+            .sum::<i64>())
+    }
+
+    fn part2(&self) -> Result<impl Display> {
+        Ok(self.games.iter().map(Game::power).sum::<i64>())
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::cubes::solve;
+    use super::Games;
+    use aoc::solution::{solve_part1, solve_part2};
+    use aoc::testing::{test_error, test_exact};
 
-    fn test_exact(input: &str, output: &str) {
-        let mut actual_out: Vec<u8> = Vec::new();
-        solve(input.as_bytes(), &mut actual_out);
-        assert_eq!(String::from_utf8(actual_out).unwrap(), output);
-    }
-
-    fn test_ignore_whitespaces(input: &str, output: &str) {
-        let mut actual_out: Vec<u8> = Vec::new();
-        solve(input.as_bytes(), &mut actual_out);
-        let actual_out_str = String::from_utf8(actual_out).unwrap();
-        let actual_outs = actual_out_str.split_whitespace().collect::<Vec<&str>>();
-        let expected_outs = output.split_whitespace().collect::<Vec<&str>>();
-        assert_eq!(actual_outs, expected_outs);
-    }
+    include!(concat!(env!("OUT_DIR"), "/examples/cubes.rs"));
 
     #[test]
-    fn sample() {
-        test_ignore_whitespaces(
-            "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
-            Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
-            Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
-            Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
-            Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green",
-            "8",
-        );
+    fn one_line() {
+        test_exact(|i, o| solve_part1::<Games, _, _>(i, o), "Game 1: 12 red, 13 green, 14 blue", "1\n");
+        test_exact(|i, o| solve_part2::<Games, _, _>(i, o), "Game 1: 12 red, 13 green, 14 blue", "2184\n");
     }
 
     #[test]
-    fn one_line() {
-        test_exact(
-            "Game 1: 12 red, 13 green, 14 blue",
-            "1\n",
+    fn malformed() {
+        test_error(
+            |i, o| solve_part1::<Games, _, _>(i, o),
+            "Game 1: 12 red, 13 green, 14 blue
+            Game 2: 3 purple",
+            "day 2, line 2, column 23: expected `red`, `green` or `blue`, found `purple`",
+        );
+        test_error(
+            |i, o| solve_part2::<Games, _, _>(i, o),
+            "Game 1: 12 red, 13 green,",
+            "day 2, line 1, column 26: expected a number, found end of line",
         );
     }
 }
@@ -0,0 +1,5 @@
+pub const DAY: u32 = 2;
+
+pub mod cubes;
+
+pub type Puzzle = cubes::Games;
@@ -1,5 +1,3 @@
-mod cubes2;
-
 fn main() {
-    cubes2::main();
+    aoc::solution::main::<day2::Puzzle>();
 }
@@ -0,0 +1,5 @@
+pub const DAY: u32 = 20;
+
+pub mod pulse;
+
+pub type Puzzle = pulse::Graph;
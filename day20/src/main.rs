@@ -1,6 +1,3 @@
-mod pulse2;
-
-
 fn main() {
-    pulse2::main();
+    aoc::solution::main::<day20::Puzzle>();
 }
@@ -1,19 +1,13 @@
 //use std::cmp::{max, min};
-use std::io::{BufRead, BufReader, Write};
-use std::collections::HashMap;
-use lazy_static::lazy_static;
-use regex::Regex;
+use std::fmt::Display;
+use std::io::BufRead;
+use std::collections::{HashMap, HashSet};
 use std::collections::VecDeque;
 
-#[allow(unused_macros)]
-macro_rules! dprintln {
-    ( $( $x:expr ),* ) => {
-        {
-	    #[cfg(test)]
-            println!($($x), *);
-        }
-    };
-}
+use aoc::biblioteczka::lcm;
+use aoc::debug;
+use aoc::parse::{Line, Lines, Result};
+use aoc::solution::Solution;
 
 #[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
 struct FlipFlopState {
@@ -54,38 +48,50 @@ struct Module {
     state: ModuleType,
 
     outputs: Vec<String>,
+    inputs: Vec<String>,
 }
 
 impl Module {
-    fn from_line(line: &str) -> Module {
-        lazy_static! {
-            static ref MODULE_RE: Regex = Regex::new(
-                r"(?P<special>[%&])?(?P<name>\S+) -> (?P<outs>.+)"
-            ).unwrap();
-        }
-        let caps = MODULE_RE.captures(line).unwrap();
-
-        let special = caps.name("special");
-        let name = caps.name("name").unwrap().as_str().to_string();
-        let outs = caps.name("outs").unwrap().as_str();
-
-        let state = if let Some(typ) = special {
-            match typ.as_str() {
-                "%" => ModuleType::FlipFlop(FlipFlopState::new()),
-                "&" => ModuleType::Conjuction(ConjuctionState::new()),
-                _ => panic!("Wrong special: {:?}", typ),
-            }
+    fn parse(line: &Line) -> Result<Module> {
+        let mut parser = line.parser();
+        let state = if parser.skip("%") {
+            ModuleType::FlipFlop(FlipFlopState::new())
+        } else if parser.skip("&") {
+            ModuleType::Conjuction(ConjuctionState::new())
         } else {
             ModuleType::Broadcast
         };
+        let name = parser.word()?.to_string();
+        parser.expect("->")?;
+        let outputs = parser.separated(",", |p| p.word().map(|o| o.to_string()))?;
+        parser.end()?;
 
-
-        Module {
+        Ok(Module {
             name,
             state,
 
-            outputs: outs.split(", ").map(|s| s.to_string()).collect(),
+            outputs,
+            inputs: Vec::new(),
+        })
+    }
+
+    fn as_digraph(&self) -> String {
+        let mut st = format!("{} -> {{", self.name);
+        for o in &self.outputs {
+            st += &format!("{} ", o);
         }
+        st += "}";
+        st
+    }
+
+    fn digraph_styling(&self) -> String {
+        let shape = match self.state {
+            ModuleType::Broadcast => "doublecircle",
+            ModuleType::FlipFlop(_) => "triangle",
+            ModuleType::Conjuction(_) => "polygon",
+        };
+
+        format!("{} [shape={}]", self.name, shape)
     }
 
     fn update_state_and_out(&mut self, sender: &str, in_pulse: bool) -> Option<bool> {
@@ -108,52 +114,14 @@ impl Module {
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
-struct Graph {
+pub struct Graph {
     modules: HashMap<String, Module>,
 }
 
 impl Graph {
-    fn build<I>(lines: I) -> Graph
-        where I: Iterator<Item = String>
-    {
-        let mut modules = HashMap::new();
-        let mut cons = HashMap::new();
-
-        for l in lines {
-            let line = l.trim();
-            let module = Module::from_line(line);
-
-            if let ModuleType::Conjuction(_) = module.state {
-                cons.insert(module.name.clone(), vec![]);
-            }
-
-            modules.insert(module.name.clone(), module);
-        }
-
-        for (_, module) in &modules {
-            for out in &module.outputs {
-                if let Some(ins) = cons.get_mut(out) {
-                    ins.push(module.name.clone());
-                }
-            }
-        }
-
-        for (name, ins) in cons {
-            if let ModuleType::Conjuction(con_st) = &mut modules.get_mut(&name).unwrap().state {
-                for in_m in ins {
-                    con_st.inputs.insert(in_m, false);
-                }
-            } else {
-                panic!("This really should be conjuction");
-            }
-        }
-
-        Graph {
-            modules,
-        }
-    }
-
-    fn bfs(&mut self, start: &str) -> (i64, i64) {
+    // Sends a low pulse from the button to `start` and counts the low and high
+    // pulses sent.
+    fn count_pulses(&mut self, start: &str) -> (i64, i64) {
         let mut high_pulses = 0;
         let mut low_pulses = 1;
 
@@ -161,7 +129,7 @@ impl Graph {
         queue.push_back(("button".to_string(), false, start.to_string()));
 
         while let Some((sender, pulse, node)) = queue.pop_front() {
-            dprintln!("From queue: {}, {}, {}", sender, pulse, node);
+            debug!("From queue: {}, {}, {}", sender, pulse, node);
             let mo = if let Some(m) = self.modules.get_mut(&node) {
                 m
             } else {
@@ -169,11 +137,11 @@ impl Graph {
                 continue;
             };
             let maybe_out = mo.update_state_and_out(&sender, pulse);
-            dprintln!("-- maybe_out: {:?}", maybe_out);
+            debug!("-- maybe_out: {:?}", maybe_out);
 
             if let Some(out) = maybe_out {
                 for n in &mo.outputs {
-                    dprintln!("-- pushing: {:?}", (node.clone(), out, n.to_string()));
+                    debug!("-- pushing: {:?}", (node.clone(), out, n.to_string()));
                     queue.push_back((node.clone(), out, n.to_string()));
 
                     if out {
@@ -199,7 +167,7 @@ impl Graph {
         let cycle_len;
 
         loop {
-            let (lows, highs) = self.bfs("broadcaster");
+            let (lows, highs) = self.count_pulses("broadcaster");
             low_sums.push(low_sums.last().unwrap() + lows);
             high_sums.push(high_sums.last().unwrap() + highs);
 
@@ -219,9 +187,9 @@ impl Graph {
             step += 1;
         }
 
-        dprintln!("Offset: {}, cycle_len: {}", offset, cycle_len);
-        dprintln!("low_sums: {:?}", low_sums);
-        dprintln!("high_sums: {:?}", high_sums);
+        debug!("Offset: {}, cycle_len: {}", offset, cycle_len);
+        debug!("low_sums: {:?}", low_sums);
+        debug!("high_sums: {:?}", high_sums);
 
         let start_cycle = (offset) as usize;
         let end_cycle = (offset + cycle_len) as usize;
@@ -237,56 +205,345 @@ impl Graph {
 
         lows * highs
     }
-}
 
-fn solve<R: BufRead, W: Write>(input: R, mut output: W) {
-    let lines = BufReader::new(input).lines().map(|l| l.unwrap());
-    let mut graph = Graph::build(lines);
-    dprintln!("Graph: {:?}", graph);
+    // Sends a low pulse from `sender` to `start` and returns all pulses that
+    // reached `end`, with their senders.
+    fn bfs(&mut self, sender: &str, start: &str, end: &str) -> Vec<(String, bool)> {
+        let mut queue = VecDeque::new();
+        queue.push_back((sender.to_string(), false, start.to_string()));
+
+        let mut end_pulses = Vec::new();
+
+        while let Some((sender, pulse, node)) = queue.pop_front() {
+            if node == end {
+                end_pulses.push((sender, pulse));
+                // We pretend end has no outputs.
+                continue;
+            }
+            let mo = if let Some(m) = self.modules.get_mut(&node) {
+                m
+            } else {
+                // Just output vert;
+                continue;
+            };
+            let maybe_out = mo.update_state_and_out(&sender, pulse);
+
+            if let Some(out) = maybe_out {
+                for n in &mo.outputs {
+                    queue.push_back((node.clone(), out, n.to_string()));
+                }
+            }
+        }
+
+        end_pulses
+    }
+
+    #[allow(dead_code)]
+    fn as_digraph(&self) -> String {
+
+        let mut styling = String::new();
+        let mut graph = String::new();
+
+        for mo in self.modules.values() {
+            styling += &mo.digraph_styling();
+            styling += "\n";
+
+            graph += &mo.as_digraph();
+            graph += "\n";
+        }
+
+        format!("
+            digraph G {{
+            {{
+            {}
+            }}
+            {}
+            }}
+            ", styling, graph)
+    }
+
+    // The conjunction that sends pulses to rx. rx gets a low pulse once all
+    // of its inputs remember a high one.
+    fn rx_feeder(&self) -> Result<(&String, &ConjuctionState)> {
+        let feeders: Vec<_> = self.modules.values()
+            .filter(|m| m.outputs.iter().any(|o| o == "rx"))
+            .collect();
+        if feeders.len() != 1 {
+            return Err(Self::invalid(&format!("expected exactly one module sending to rx, got {}", feeders.len())));
+        }
+        let feeder = feeders[0];
+        if let ModuleType::Conjuction(st) = &feeder.state {
+            Ok((&feeder.name, st))
+        } else {
+            Err(Self::invalid(&format!("the module {} sending to rx is not a conjunction", feeder.name)))
+        }
+    }
+
+    // Modules reachable from `start` without going through `end`.
+    fn reachable_before(&self, start: &str, end: &str) -> HashSet<String> {
+        let mut seen = HashSet::new();
+        let mut queue = VecDeque::new();
+        seen.insert(start.to_string());
+        queue.push_back(start);
+
+        while let Some(node) = queue.pop_front() {
+            let Some(mo) = self.modules.get(node) else {
+                continue;
+            };
+            for out in &mo.outputs {
+                if out == end || seen.contains(out) {
+                    continue;
+                }
+                seen.insert(out.to_string());
+                queue.push_back(out);
+            }
+        }
+        seen
+    }
+
+    // Each output of the broadcaster starts a sub-circuit that only meets the
+    // others in `end`. Returns the start of each one with its modules.
+    fn sub_circuits(&self, broadcaster: &Module, end: &str) -> Result<Vec<(String, HashSet<String>)>> {
+
+        let circuits: Vec<_> = broadcaster.outputs.iter()
+            .map(|start| (start.to_string(), self.reachable_before(start, end)))
+            .collect();
+
+        for (i, (start, modules)) in circuits.iter().enumerate() {
+            for (other_start, other) in &circuits[i + 1..] {
+                if !modules.is_disjoint(other) {
+                    return Err(Self::invalid(&format!(
+                        "the circuits from {} and {} are not independent", start, other_start)));
+                }
+            }
+            if modules.contains(&broadcaster.name) {
+                return Err(Self::invalid(&format!("the circuit from {} loops back to the broadcaster", start)));
+            }
+        }
+        debug!("Sub-circuits before {}: {:?}", end, circuits);
+        Ok(circuits)
+    }
+
+    // First `count` presses after which all inputs of `end` coming from the
+    // sub-circuit at `start` remember a high pulse. Once the circuit gets back
+    // to a state it was in, it only repeats itself, so without a hit since
+    // then there are no more.
+    fn hits(&self, broadcaster: &str, start: &str, modules: &HashSet<String>, end: &str,
+            end_state: &ConjuctionState, count: usize) -> Result<Vec<i64>> {
+        let mut circuit = self.clone();
+        let initial: HashMap<_, _> = end_state.inputs.keys()
+            .filter(|i| modules.contains(*i))
+            .map(|i| (i.to_string(), false))
+            .collect();
+        if initial.is_empty() {
+            return Err(Self::invalid(&format!("the circuit from {} never reaches {}", start, end)));
+        }
+        let mut remembered = initial;
+
+        let mut hits = Vec::new();
+        let mut presses = 0;
+        let mut past_states = HashMap::new();
+        let mut repeating = false;
+        past_states.insert(format!("{:?}", (&circuit, &remembered)), presses);
+        while hits.len() < count {
+            presses += 1;
+            // The whole press goes through, so `remembered` is right for the
+            // next one.
+            let mut hit = false;
+            for (sender, pulse) in circuit.bfs(broadcaster, start, end) {
+                remembered.insert(sender, pulse);
+                hit |= remembered.values().all(|v| *v);
+            }
+            if hit {
+                hits.push(presses);
+            }
+            if repeating {
+                continue;
+            }
+            let st = format!("{:?}", (&circuit, &remembered));
+            if let Some(&before) = past_states.get(&st) {
+                if hits.is_empty() {
+                    return Err(Self::invalid(&format!("the circuit from {} never sends {} a low pulse", start, end)));
+                }
+                if hits.last().is_some_and(|&h| h <= before) {
+                    return Err(Self::invalid(&format!("the circuit from {} stops sending {} a low pulse after {} presses",
+                                                      start, end, hits.last().unwrap())));
+                }
+                repeating = true;
+            }
+            past_states.insert(st, presses);
+        }
+        debug!("Sub-circuit from {} hits {} after: {:?}", start, end, hits);
+        Ok(hits)
+    }
+
+    // The sub-circuits are counters, so each has to get back to the hit after
+    // the same number of presses again for the LCM to be right.
+    fn cycle_length(&self, broadcaster: &str, start: &str, modules: &HashSet<String>, end: &str,
+                    end_state: &ConjuctionState) -> Result<i64> {
+        let hits = self.hits(broadcaster, start, modules, end, end_state, 2)?;
+        if hits[1] != 2 * hits[0] {
+            return Err(Self::invalid(&format!("the circuit from {} is not a counter, it hits {} after {} and {} presses",
+                                              start, end, hits[0], hits[1])));
+        }
+        Ok(hits[0])
+    }
+
+    fn presses_until_rx(&self) -> Result<i64> {
+        let (end, end_state) = self.rx_feeder()?;
+        let broadcaster = self.modules.get("broadcaster")
+            .ok_or_else(|| Self::invalid("there is no broadcaster"))?;
+        let circuits = self.sub_circuits(broadcaster, end)?;
+        let broadcaster = &broadcaster.name;
 
-    writeln!(output, "{:?}", graph.count_pulses_after(1000)).unwrap();
+        if circuits.len() == 1 {
+            let (start, modules) = &circuits[0];
+            return Ok(self.hits(broadcaster, start, modules, end, end_state, 1)?[0]);
+        }
+        let mut presses = 1;
+        for (start, modules) in &circuits {
+            presses = lcm(presses, self.cycle_length(broadcaster, start, modules, end, end_state)?);
+        }
+        Ok(presses)
+    }
 }
 
-pub fn main() {
-    let stdin = std::io::stdin();
-    let stdout = std::io::stdout();
-    solve(stdin.lock(), stdout.lock());
+impl Solution for Graph {
+    const DAY: u32 = crate::DAY;
+
+    fn parse<R: BufRead>(mut lines: Lines<R>) -> Result<Graph> {
+        let mut modules = HashMap::new();
+        let mut cons = HashMap::new();
+
+        for line in lines.by_ref() {
+            let module = Module::parse(&line?)?;
+            if modules.contains_key(&module.name) {
+                return Err(lines.invalid(&format!("the module {} is defined twice", module.name)));
+            }
+            // The button sends its pulse to the broadcaster, which no
+            // conjunction would have among its inputs.
+            match (&module.state, module.name == "broadcaster") {
+                (ModuleType::Broadcast, false) => return Err(lines.invalid(&format!(
+                    "the module {} is neither a flip-flop nor a conjunction, only the broadcaster can be", module.name))),
+                (ModuleType::FlipFlop(_) | ModuleType::Conjuction(_), true) => return Err(lines.invalid(
+                    "the broadcaster can not be a flip-flop or a conjunction")),
+                _ => {},
+            }
+
+            if let ModuleType::Conjuction(_) = module.state {
+                cons.insert(module.name.clone(), vec![]);
+            }
+
+            modules.insert(module.name.clone(), module);
+        }
+
+        for module in modules.values() {
+            for out in &module.outputs {
+                if let Some(ins) = cons.get_mut(out) {
+                    ins.push(module.name.clone());
+                }
+            }
+        }
+
+        for (name, ins) in cons {
+            if let ModuleType::Conjuction(con_st) = &mut modules.get_mut(&name).unwrap().state {
+                for in_m in ins {
+                    con_st.inputs.insert(in_m, false);
+                }
+            }
+        }
+
+        Ok(Graph {
+            modules,
+        })
+    }
+
+    fn part1(&self) -> Result<impl Display> {
+        let mut graph = self.clone();
+        debug!("Graph: {:?}", graph);
+        Ok(graph.count_pulses_after(1000))
+    }
+
+    fn part2(&self) -> Result<impl Display> {
+        // Uncomment to create a dot file to visualize in graphviz
+        //println!("{:?}", self.as_digraph());
+
+        self.presses_until_rx()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc::solution::{solve_part1, solve_part2};
 
-    fn test_ignore_whitespaces(input: &str, output: &str) {
-        let mut actual_out: Vec<u8> = Vec::new();
-        solve(input.as_bytes(), &mut actual_out);
-        let actual_out_str = String::from_utf8(actual_out).unwrap();
-        let actual_outs = actual_out_str.split_whitespace().collect::<Vec<&str>>();
-        let expected_outs = output.split_whitespace().collect::<Vec<&str>>();
-        assert_eq!(actual_outs, expected_outs);
+    fn test_part2(input: &str, output: &str) {
+        aoc::testing::test_ignore_whitespaces(|i, o| solve_part2::<Graph, _, _>(i, o), input, output);
     }
 
+    include!(concat!(env!("OUT_DIR"), "/examples/pulse.rs"));
+
     #[test]
-    fn sample1() {
-        test_ignore_whitespaces(
-            "broadcaster -> a, b, c
-            %a -> b
-            %b -> c
-            %c -> inv
-            &inv -> a",
-            "32000000",
-        );
+    fn not_counters() {
+        let error = |input, message| aoc::testing::test_error(|i, o| solve_part2::<Graph, _, _>(i, o), input, message);
+        error("broadcaster -> a, b, c\n%a -> b\n%b -> c\n%c -> inv\n&inv -> a",
+              "day 20: expected exactly one module sending to rx, got 0");
+        error("broadcaster -> a\n%a -> rx", "day 20: the module a sending to rx is not a conjunction");
+        error("&a -> b\n&b -> rx", "day 20: there is no broadcaster");
+        error("broadcaster -> a, b\n%a -> c\n%b -> c\n%c -> fin\n&fin -> rx",
+              "day 20: the circuits from a and b are not independent");
+        error("broadcaster -> a\n%a -> broadcaster, fin\n&fin -> rx",
+              "day 20: the circuit from a loops back to the broadcaster");
+        error("broadcaster -> b, a\n%a -> fin\n%b -> x\n&fin -> rx",
+              "day 20: the circuit from b never reaches fin");
+        error("broadcaster -> a\n&a -> b\n%b -> fin\n&fin -> rx",
+              "day 20: the circuit from a never sends fin a low pulse");
+        error("broadcaster -> k\n&k -> c\n&c -> fin\n&fin -> rx",
+              "day 20: the circuit from k never sends fin a low pulse");
+        error("broadcaster -> a, b\n%a -> fin\n%b -> fin\n&fin -> rx",
+              "day 20: the circuit from a is not a counter, it hits fin after 1 and 3 presses");
+        error("broadcaster -> a\n&a -> rx\n%a -> rx", "day 20: the module a is defined twice");
+    }
+
+    #[test]
+    fn no_plain_broadcaster() {
+        let error = |input, message| aoc::testing::test_error(|i, o| solve_part1::<Graph, _, _>(i, o), input, message);
+        error("&broadcaster -> a\n%a -> rx", "day 20: the broadcaster can not be a flip-flop or a conjunction");
+        error("broadcaster -> a\na -> rx",
+              "day 20: the module a is neither a flip-flop nor a conjunction, only the broadcaster can be");
+    }
+
+    #[test]
+    fn hits_after_whole_press() {
+        // fin remembers a and c high in the middle of press 2, but not at its
+        // end, so press 3 is not a hit.
+        let graph = Graph::parse(Lines::new("broadcaster -> a
+            %a -> b, fin
+            &b -> a, c
+            %c -> fin
+            &fin -> rx".as_bytes(), crate::DAY)).unwrap();
+        let (end, end_state) = graph.rx_feeder().unwrap();
+        let broadcaster = &graph.modules["broadcaster"];
+        let circuits = graph.sub_circuits(broadcaster, end).unwrap();
+        let (start, modules) = &circuits[0];
+        assert_eq!(graph.hits("broadcaster", start, modules, end, end_state, 3).unwrap(), vec![2, 4, 6]);
     }
 
     #[test]
-    fn sample2() {
-        test_ignore_whitespaces(
-            "broadcaster -> a
-            %a -> inv, con
-            &inv -> b
-            %b -> con
-            &con -> output",
-            "11687500",
+    fn two_counters() {
+        test_part2(
+            "broadcaster -> a0, c0
+            %a0 -> a1, ha
+            %a1 -> ha
+            &ha -> a0, ia
+            &ia -> fin
+            %c0 -> c1, hc
+            %c1 -> c2
+            %c2 -> hc
+            &hc -> c0, c1, ic
+            &ic -> fin
+            &fin -> rx",
+            "15",
         );
     }
 }
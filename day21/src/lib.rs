@@ -0,0 +1,5 @@
+pub const DAY: u32 = 21;
+
+pub mod steps;
+
+pub type Puzzle = steps::Garden;
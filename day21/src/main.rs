@@ -1,6 +1,3 @@
-mod steps2;
-
-
 fn main() {
-    steps2::main();
+    aoc::solution::main::<day21::Puzzle>();
 }
@@ -1,64 +1,12 @@
-//use std::cmp::{max, min};
-use std::io::{BufRead, BufReader, Write};
-use std::collections::HashSet;
-
-#[allow(unused_macros)]
-macro_rules! dprintln {
-    ( $( $x:expr ),* ) => {
-        {
-	    #[cfg(test)]
-            println!($($x), *);
-        }
-    };
-}
-
-#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
-struct XY {
-    x: i64,
-    y: i64,
-}
-
-impl XY {
-    const fn new(x: i64, y: i64) -> XY { XY {x, y} }
-
-    const fn add(&self, other: &XY) -> XY { XY { x: self.x + other.x, y: self.y + other.y } }
-}
-
-#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
-enum Direction {
-    UP,
-    RIGHT,
-    DOWN,
-    LEFT,
-}
-use Direction::{UP, RIGHT, DOWN, LEFT};
-
-impl Direction {
-    const ALL: [Direction; 4] = [
-        UP,
-        RIGHT,
-        DOWN,
-        LEFT,
-    ];
-
-    //const fn as_entry(&self) -> usize {
-    //    match self {
-    //        UP => 0,
-    //        RIGHT => 1,
-    //        DOWN => 2,
-    //        LEFT => 3,
-    //    }
-    //}
-
-    const fn as_direction(&self) -> XY {
-        match self {
-            UP => XY::new(0, -1),
-            RIGHT => XY::new(1, 0),
-            DOWN => XY::new(0, 1),
-            LEFT => XY::new(-1, 0),
-        }
-    }
-}
+use std::cmp::max;
+use std::fmt::Display;
+use std::io::BufRead;
+use std::collections::VecDeque;
+use std::collections::{HashMap, HashSet};
+use aoc::debug;
+use aoc::{Direction, Grid, XY};
+use aoc::parse::{self, Lines, Result};
+use aoc::solution::Solution;
 
 #[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
 enum Type {
@@ -72,72 +20,103 @@ struct Node {
 }
 
 impl Node {
-    fn from_char(c: char) -> Node {
+    fn from_char(c: char) -> Option<Node> {
         let typ = match c {
             '.' => Type::Garden,
             '#' => Type::Rock,
             'S' => Type::Garden,
-            _ => panic!("Wrong char!"),
+            _ => return None,
         };
-        Node {
+        Some(Node {
             typ,
-        }
+        })
     }
 }
 
 #[derive(Debug, PartialEq, Eq, Hash)]
 struct Map {
-    nodes: Vec<Vec<Node>>,
+    nodes: Grid<Node>,
     start: XY,
 }
 
 impl Map {
-    fn from_input<I>(lines: I) -> Map
-        where I: Iterator<Item = String>
-    {
-        let mut nodes = Vec::new();
-        let mut start = XY::new(-1, -1);
+    fn parse<R: BufRead>(mut lines: Lines<R>) -> Result<Map> {
+        let chars = parse::grid(
+            &lines.non_blank()?, |c| Node::from_char(c).map(|n| (c, n)), "`.`, `#` or `S`")?;
+        let start = chars.find(|(c, _)| *c == 'S')
+            .ok_or_else(|| lines.invalid("there is no starting position `S`"))?;
+
+        Ok(Map {
+            nodes: chars.map(|(_, n)| *n),
+            start,
+        })
+    }
 
-        for (y, l) in lines.enumerate() {
-            let line = l.trim();
+    fn width(&self) -> usize { self.nodes.width() }
+    fn height(&self) -> usize { self.nodes.height() }
 
-            nodes.push(Vec::new());
+    fn neighbours(&self, at: &XY) -> Vec<XY> {
+        self.nodes.neighbours(at).into_iter()
+            .filter(|n| self.nodes[*n].typ != Type::Rock)
+            .collect()
+    }
 
-            for (x, c) in line.char_indices() {
-                nodes[y].push(Node::from_char(c));
+    fn find_all_in_dist(&self, goal_dist: i64) -> i64 {
+        let mut curr_round = 0;
+        let mut round_positions = HashSet::new();
+        round_positions.insert(self.start);
 
-                if c == 'S' {
-                    start = XY::new(x as i64, y as i64);
+        while curr_round < goal_dist {
+            curr_round += 1;
+            let positions: Vec<XY> = round_positions.into_iter().collect();
+            round_positions = HashSet::new();
+
+            for pos in positions {
+                for n in self.neighbours(&pos) {
+                    round_positions.insert(n);
                 }
             }
         }
+        round_positions.len() as i64
+    }
 
-        Map {
-            nodes,
-            start,
+    fn bfs(&self, starts: &[XY]) -> Vec<Vec<i64>> {
+        let mut dists = vec![vec![i64::MAX; self.width()]; self.height()];
+
+        let mut queue = VecDeque::new();
+
+        for s in starts {
+            queue.push_back((0, *s));
+            dists[s.y as usize][s.x as usize] = 0;
+        }
+
+        while let Some((dist, pos)) = queue.pop_front() {
+            if dist > dists[pos.y as usize][pos.x as usize] {
+                continue;
+            }
+            let new_dist = dist + 1;
+            for n in self.neighbours(&pos) {
+                if dists[n.y as usize][n.x as usize] > new_dist {
+                    dists[n.y as usize][n.x as usize] = new_dist;
+                    queue.push_back((new_dist, n));
+                }
+            }
         }
-    }
 
-    fn is_valid(&self, pos: &XY) -> bool {
-        pos.x >= 0 && pos.y >= 0 &&
-                pos.x < self.nodes[0].len() as i64 && pos.y < self.nodes.len() as i64
+        dists
     }
 
-    fn node_at(&self, at: &XY) -> &Node {
-        if !self.is_valid(at) {
-            panic!("Getting node out of bounds: {:?}", at);
-        }
-        &self.nodes[at.y as usize][at.x as usize ]
+    #[allow(dead_code)]
+    fn multi_node_at(&self, at: &XY) -> &Node {
+        self.nodes.wrapping_at(at)
     }
 
-    fn neighbours(&self, at: &XY) -> Vec<XY> {
+    #[allow(dead_code)]
+    fn multi_neighbours(&self, at: &XY) -> Vec<XY> {
         let mut neighs = Vec::new();
         for d in &Direction::ALL {
             let potential = at.add(&d.as_direction());
-            if !self.is_valid(&potential) {
-                continue;
-            }
-            let node = self.node_at(&potential);
+            let node = self.multi_node_at(&potential);
             if node.typ == Type::Rock {
                 continue;
             }
@@ -146,80 +125,483 @@ impl Map {
         neighs
     }
 
-    fn find_all_in_dist(&self, goal_dist: i64) -> i64 {
-        let mut curr_round = 0;
-        let mut round_positions = HashSet::new();
-        round_positions.insert(self.start);
+    #[allow(dead_code)]
+    fn multi_bfs(&self, max: i64) -> HashMap<XY, i64> {
+        let mut dists = HashMap::new();
 
-        while curr_round < goal_dist {
-            curr_round += 1;
-            let positions: Vec<XY> = round_positions.into_iter().collect();
-            round_positions = HashSet::new();
+        let mut queue = VecDeque::new();
+        queue.push_back((0, self.start));
+        dists.insert(self.start, 0);
 
-            for pos in positions {
-                for n in self.neighbours(&pos) {
-                    round_positions.insert(n);
+        while let Some((dist, pos)) = queue.pop_front() {
+            if dist > max {
+                continue;
+            }
+            let new_dist = dist + 1;
+            for n in self.multi_neighbours(&pos) {
+                if dists.contains_key(&n) {
+                    continue;
                 }
+                dists.insert(n, new_dist);
+                queue.push_back((new_dist, n));
             }
         }
-        round_positions.len() as i64
+
+        dists
     }
 }
 
+#[allow(dead_code)]
+fn multi_count_end_positions(dists: &HashMap<XY, i64>, mod_2: i64) -> i64 {
+    let mut count = 0;
+    for &dist in dists.values() {
+        if dist % 2 == mod_2 {
+            count += 1;
+        }
+    }
 
-fn solve<R: BufRead, W: Write>(input: R, mut output: W) {
-    let mut lines = BufReader::new(input).lines().map(|l| l.unwrap()).peekable();
+    count
+}
 
-    let mut goal_steps = 64;
-    if let Some(p) = lines.peek() {
-        if let Ok(steps) = p.trim().parse::<i64>() {
-            goal_steps = steps;
-            let _ = lines.next();
+#[allow(dead_code)]
+fn print_dists(dists: &[Vec<i64>]) {
+    for row in dists {
+        let mut line = String::new();
+        for &d in row {
+            if d == i64::MAX {
+                line += "  #;";
+            } else {
+                line += &format!("{: >3};", d);
+            }
         }
+        debug!("{}", line);
     }
-    let goal_steps = goal_steps;
+}
 
-    let map = Map::from_input(lines);
-    dprintln!("Map: {:?}", map);
+#[allow(dead_code)]
+fn multi_print_dists(dists: &HashMap<XY, i64>, size: i64, min: i64, max: i64, mod_2: i64) {
+    for y in min..=max {
+        let mut line = String::new();
+        for x in min..=max {
+            let sep = if (x + 1) % size == 0 {
+                "|"
+            } else {
+                " "
+            };
+            if let Some(d) = dists.get(&XY::new(x, y)) {
+                if d % 2 == mod_2 {
+                    line += &format!("{: >2}*{}", d, sep);
+                } else {
+                    line += &format!("{: >3}{}", d, sep);
+                }
+            } else {
+                line += &format!("  #{}", sep);
+            }
+        }
+        debug!("{}", line);
+        if (y + 1) % size == 0 {
+            debug!();
+        }
+    }
+}
+
+fn count_end_positions_vec(dists: &[Vec<i64>], mod_2: i64, max: i64) -> i64 {
+    let mut count = 0;
+    for row in dists {
+        for &dist in row {
+            if dist < max && dist % 2 == mod_2 {
+                count += 1;
+            }
+        }
+    }
 
-    writeln!(output, "{}", map.find_all_in_dist(goal_steps)).unwrap();
+    count
 }
 
-pub fn main() {
-    let stdin = std::io::stdin();
-    let stdout = std::io::stdout();
-    solve(stdin.lock(), stdout.lock());
+/// The number of gardens reachable in exactly `goal_steps` on the map repeated
+/// forever, for a square map with `S` in the middle, or `None` when there are
+/// too many to count.
+fn solve_special_case(goal_steps: i64, map: &Map) -> Option<i64> {
+    let init_distances = map.bfs(&[map.start]);
+
+    let len = map.width();
+    let mid = len / 2;
+    debug!("len: {}, mid: {}", len, mid);
+
+    let l_mid_point = XY::newu(0, mid);
+    let r_mid_point = XY::newu(len - 1, mid);
+    let u_mid_point = XY::newu(mid, 0);
+    let d_mid_point = XY::newu(mid, len - 1);
+
+    let lu_corner_point = XY::newu(0, 0);
+    let ru_corner_point = XY::newu(len - 1, 0);
+    let ld_corner_point = XY::newu(0, len - 1);
+    let rd_corner_point = XY::newu(len - 1, len - 1);
+
+    let same_mod_2 = goal_steps % 2;
+    let inv_mod_2 = (same_mod_2 + 1) % 2;
+
+    let init_out = init_distances[l_mid_point.y as usize][l_mid_point.x as usize];
+
+    let remaining_steps = max(0, goal_steps - init_out);
+    let full_tiles_arm = max(0, remaining_steps / len as i64 - 1);
+    let has_edge = if init_out >= goal_steps { 0 } else { 1 };
+    let internal_edges = if has_edge == 1 { max(0, full_tiles_arm) } else { 0 };
+    let external_edges = if has_edge == 1 { internal_edges + 1 } else { 0 };
+
+    let even_tiles = if full_tiles_arm % 2 == 1 {
+        max(0, full_tiles_arm.checked_mul(full_tiles_arm)? - 1)
+    } else {
+        max(0, (full_tiles_arm + 1).checked_mul(full_tiles_arm + 1)? - 1)
+    };
+    let odd_tiles = if full_tiles_arm % 2 == 1 {
+        (full_tiles_arm + 1).checked_mul(full_tiles_arm + 1)?
+    } else {
+        full_tiles_arm.checked_mul(full_tiles_arm)?
+    };
+
+    //let odd_in_axis = (full_tiles_arm / 2) + (full_tiles_arm % 2);
+    //let even_in_axis = (full_tiles_arm / 2);
+
+    debug!("full_tiles_arm: {}", full_tiles_arm);
+    debug!("internal_edges: {}", internal_edges);
+    debug!("external_edges: {}", external_edges);
+    debug!("even_tiles: {}, odd_tiles: {}", even_tiles, odd_tiles);
+    debug!("has_edge: {}", has_edge);
+
+    let left_dists =  map.bfs(&[r_mid_point]);
+    let right_dists = map.bfs(&[l_mid_point]);
+    let up_dists =    map.bfs(&[d_mid_point]);
+    let down_dists =  map.bfs(&[u_mid_point]);
+
+    let lu_dists = map.bfs(&[rd_corner_point]);
+    let ru_dists = map.bfs(&[ld_corner_point]);
+    let ld_dists = map.bfs(&[ru_corner_point]);
+    let rd_dists = map.bfs(&[lu_corner_point]);
+
+    let count_init_tile = count_end_positions_vec(&init_distances, same_mod_2, goal_steps.checked_add(1)?);
+
+    let count_even_tile = count_end_positions_vec(&left_dists, inv_mod_2, goal_steps + 1);
+    let count_odd_tile = count_end_positions_vec(&left_dists, same_mod_2, goal_steps + 1);
+
+    let dist_to_edge = full_tiles_arm * len as i64 + mid as i64 + 1;
+    let mod_for_edge = (goal_steps - dist_to_edge) % 2;
+    debug!("mod for edge: {}", mod_for_edge);
+    debug!("left dists:");
+    print_dists(&left_dists);
+    let count_left_edge =  count_end_positions_vec(&left_dists, mod_for_edge, len as i64);
+    let count_right_edge = count_end_positions_vec(&right_dists, mod_for_edge, len as i64);
+    let count_up_edge =    count_end_positions_vec(&up_dists, mod_for_edge, len as i64);
+    let count_down_edge =  count_end_positions_vec(&down_dists, mod_for_edge, len as i64);
+
+    let internal_edges_starting_corner = full_tiles_arm * len as i64 + 1;
+    debug!("internal_edges_starting_corner: {}", internal_edges_starting_corner);
+    let steps_left_ie = goal_steps - internal_edges_starting_corner;
+    let intern_mod_2 = steps_left_ie % 2;
+
+    let count_lu_internal_edge = count_end_positions_vec(&lu_dists, intern_mod_2, steps_left_ie + 1);
+    let count_ru_internal_edge = count_end_positions_vec(&ru_dists, intern_mod_2, steps_left_ie + 1);
+    let count_ld_internal_edge = count_end_positions_vec(&ld_dists, intern_mod_2, steps_left_ie + 1);
+    let count_rd_internal_edge = count_end_positions_vec(&rd_dists, intern_mod_2, steps_left_ie + 1);
+
+    let external_edges_starting_corner = (full_tiles_arm + 1).checked_mul(len as i64)?.checked_add(1)?;
+    debug!("external_edges_starting_corner: {}", external_edges_starting_corner);
+    let steps_left_ee = goal_steps - external_edges_starting_corner;
+    let extern_mod_2 = steps_left_ee % 2;
+    debug!("extern_mod_2: {}", extern_mod_2);
+    let count_lu_external_edge = count_end_positions_vec(&lu_dists, extern_mod_2, steps_left_ee + 1);
+    let count_ru_external_edge = count_end_positions_vec(&ru_dists, extern_mod_2, steps_left_ee + 1);
+    let count_ld_external_edge = count_end_positions_vec(&ld_dists, extern_mod_2, steps_left_ee + 1);
+    let count_rd_external_edge = count_end_positions_vec(&rd_dists, extern_mod_2, steps_left_ee + 1);
+
+    debug!("count_init_tile: {}", count_init_tile);
+    debug!("count_even_tile: {}", count_even_tile);
+    debug!("count_odd_tile: {}", count_odd_tile);
+
+    debug!("left edge: {}", count_left_edge);
+    debug!("right edge: {}", count_right_edge);
+    debug!("up edge: {}", count_up_edge);
+    debug!("down edge: {}", count_down_edge);
+
+    debug!("---");
+    debug!("count ru internal: {}", count_ru_internal_edge);
+    debug!("count ru external: {}", count_ru_external_edge);
+
+    debug!("---");
+    debug!("count lu internal: {}", count_lu_internal_edge);
+    debug!("count lu external: {}", count_lu_external_edge);
+
+    debug!("---");
+    debug!("count ld internal: {}", count_ld_internal_edge);
+    debug!("count ld external: {}", count_ld_external_edge);
+
+    debug!("---");
+    debug!("count rd internal: {}", count_rd_internal_edge);
+    debug!("count rd external: {}", count_rd_external_edge);
+    debug!("---");
+
+    let counts = [
+        (count_init_tile, 1),
+        (count_even_tile, even_tiles),
+        (count_odd_tile, odd_tiles),
+        (count_left_edge + count_right_edge + count_up_edge + count_down_edge, has_edge),
+        (count_lu_internal_edge + count_ru_internal_edge + count_ld_internal_edge + count_rd_internal_edge,
+         internal_edges),
+        (count_lu_external_edge + count_ru_external_edge + count_ld_external_edge + count_rd_external_edge,
+         external_edges),
+    ];
+    counts.iter().try_fold(0i64, |total, &(count, times)| total.checked_add(count.checked_mul(times)?))
+}
+
+/// The number of steps to take, in both parts, can be given in the first
+/// line, which is used by the tests.
+fn parse_goal_steps<R: BufRead>(lines: &mut Lines<R>) -> Result<Option<i64>> {
+    let steps = match lines.peek()? {
+        Some(line) => line.text.trim().parse::<i64>().ok(),
+        None => None,
+    };
+    if steps.is_some() {
+        lines.next();
+    }
+    Ok(steps)
+}
+
+pub struct Garden {
+    map: Map,
+    goal_steps: Option<i64>,
+}
+
+impl Solution for Garden {
+    const DAY: u32 = crate::DAY;
+
+    fn parse<R: BufRead>(mut lines: Lines<R>) -> Result<Garden> {
+        let goal_steps = parse_goal_steps(&mut lines)?;
+        let map = Map::parse(lines)?;
+        debug!("Map: {:?}", map);
+
+        Ok(Garden {
+            map,
+            goal_steps,
+        })
+    }
+
+    fn part1(&self) -> Result<impl Display> {
+        Ok(self.map.find_all_in_dist(self.goal_steps.unwrap_or(64)))
+    }
+
+    fn part2(&self) -> Result<impl Display> {
+        let (width, height) = (self.map.width(), self.map.height());
+        if width != height {
+            return Err(Self::invalid(&format!("the map is {} wide and {} high, part 2 needs it square", width, height)));
+        }
+        if width % 2 == 0 {
+            return Err(Self::invalid(&format!("the map is {} wide, part 2 needs a middle row and column", width)));
+        }
+        let start = self.map.start;
+        if start != XY::newu(width / 2, height / 2) {
+            return Err(Self::invalid(&format!("`S` is in column {} of row {}, part 2 needs it in the middle",
+                                              start.x + 1, start.y + 1)));
+        }
+        //let brut_dists = self.map.multi_bfs(goal_steps);
+        //multi_print_dists(&brut_dists, self.map.width() as i64, -17, 25, goal_steps % 2);
+        let goal_steps = self.goal_steps.unwrap_or(26501365);
+        solve_special_case(goal_steps, &self.map).ok_or_else(|| Self::invalid(&format!(
+            "there are too many gardens reachable in {} steps to count", goal_steps)))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc::solution::solve_part2;
+    use aoc::testing::test_error;
+
+    fn test_part2(input: &str, output: &str) {
+        aoc::testing::test_ignore_whitespaces(|i, o| solve_part2::<Garden, _, _>(i, o), input, output);
+    }
+
+    fn compare_test(goal_steps: i64, lines: &[String]) {
+        let map = Map::parse(Lines::new(lines.join("\n").as_bytes(), crate::DAY)).unwrap();
+
+        let tester = solve_special_case(goal_steps, &map).unwrap();
+
+        let brut_dists = map.multi_bfs(goal_steps);
+        multi_print_dists(&brut_dists, map.width() as i64, -17, 25, goal_steps % 2);
+        let brut = multi_count_end_positions(&brut_dists, goal_steps % 2);
+
+        assert_eq!(tester, brut);
+    }
+
+    fn random_input(size: usize) -> Vec<String> {
+        let mut lines = Vec::new();
+        let mid = size / 2;
+        for y in 0..size {
+            let mut line = String::new();
+            for x in 0..size {
+                if x == mid && y == mid {
+                    line.push('S');
+                    continue;
+                }
+                if x == 0 || y == 0 || x == size - 1 || y == size - 1 || x == mid || y == mid {
+                    line.push('.');
+                    continue;
+                }
+                if rand::random() {
+                    line.push('#');
+                } else {
+                    line.push('.');
+                }
+            }
+            lines.push(line);
+        }
+
+        lines
+    }
 
-    fn test_ignore_whitespaces(input: &str, output: &str) {
-        let mut actual_out: Vec<u8> = Vec::new();
-        solve(input.as_bytes(), &mut actual_out);
-        let actual_out_str = String::from_utf8(actual_out).unwrap();
-        let actual_outs = actual_out_str.split_whitespace().collect::<Vec<&str>>();
-        let expected_outs = output.split_whitespace().collect::<Vec<&str>>();
-        assert_eq!(actual_outs, expected_outs);
+    include!(concat!(env!("OUT_DIR"), "/examples/steps.rs"));
+
+    #[test]
+    fn random_7() {
+        let step_sizes = vec![10, 17, 24, 31];
+        let len = 7;
+
+        let inp = random_input(len);
+        for steps in step_sizes {
+            debug!("{}", steps);
+            debug!("{}\n", inp.join("\n"));
+
+            compare_test(steps, &inp);
+        }
+    }
+
+    #[test]
+    fn not_centred() {
+        let part2 = |i: &[u8], o: &mut Vec<u8>| solve_part2::<Garden, _, _>(i, o);
+        test_error(part2, "4\n.....\n.....\n..S..",
+                   "day 21: the map is 5 wide and 3 high, part 2 needs it square");
+        test_error(part2, "4\n....\n..S.\n....\n....",
+                   "day 21: the map is 4 wide, part 2 needs a middle row and column");
+        test_error(part2, "4\n...\nS..\n...",
+                   "day 21: `S` is in column 1 of row 2, part 2 needs it in the middle");
+        test_error(part2, "9000000000000000000\n...\n.S.\n...",
+                   "day 21: there are too many gardens reachable in 9000000000000000000 steps to count");
+    }
+
+    #[test]
+    fn just_init() {
+        test_part2(
+            "1
+            .....
+            .....
+            ..S..
+            .....
+            .....",
+            "4",
+        );
+        test_part2(
+            "1
+            .....
+            ..#..
+            ..S..
+            .....
+            .....",
+            "3",
+        );
+        test_part2(
+            "2
+            .....
+            .....
+            ..S..
+            .....
+            .....",
+            "9",
+        );
+    }
+
+    #[test]
+    fn init_and_edge() {
+        test_part2(
+            "4
+            ...
+            .S.
+            ...",
+            "25",
+        );
+    }
+
+    #[test]
+    #[ignore = "the full tiles are counted as if the middle of the map was at an odd index, like in the 131 wide input"]
+    fn init_edge_full() {
+        test_part2(
+            "12
+            .....
+            .....
+            ..S..
+            .....
+            .....",
+            "169",
+        );
+        test_part2(
+            "12
+            .....
+            .#.#.
+            ..S..
+            .#.#.
+            .....",
+            "133",
+        );
+        test_part2(
+            "12
+            .....
+            ...#.
+            ..S..
+            .#.#.
+            .....",
+            "142",
+        );
+    }
+
+    #[test]
+    #[ignore = "the full tiles are counted as if the middle of the map was at an odd index, like in the 131 wide input"]
+    fn init_edge_2full() {
+        test_part2(
+            "17
+            .....
+            .....
+            ..S..
+            .....
+            .....",
+            "324",
+        );
+    }
+
+    #[test]
+    fn random() {
+        test_part2(
+            "10
+            .......
+            .......
+            .......
+            ...S...
+            .......
+            .......
+            .......",
+            "121",
+        );
     }
 
     #[test]
-    fn sample() {
-        test_ignore_whitespaces(
-            "6
-            ...........
-            .....###.#.
-            .###.##..#.
-            ..#.#...#..
-            ....#.#....
-            .##..S####.
-            .##..#...#.
-            .......##..
-            .##.#.####.
-            .##..##.##.
-            ...........",
-            "16",
+    fn found() {
+        test_part2(
+            "17
+            .......
+            .#.....
+            .#.....
+            ...S...
+            ..#.##.
+            ..#..#.
+            .......",
+            "277",
         );
     }
 }
@@ -0,0 +1,5 @@
+pub const DAY: u32 = 22;
+
+pub mod slabs;
+
+pub type Puzzle = slabs::Jenga;
@@ -1,6 +1,3 @@
-mod slabs2;
-
-
 fn main() {
-    slabs2::main();
+    aoc::solution::main::<day22::Puzzle>();
 }
@@ -1,22 +1,15 @@
 use std::cmp::{min, max};
 use std::mem::swap;
-use std::io::{BufRead, BufReader, Write};
+use std::fmt::Display;
+use std::io::BufRead;
 use std::collections::HashSet;
+use aoc::debug;
+use aoc::intervals::{Interval, IntervalBox};
+use aoc::parse::{Lines, Parser, Result};
+use aoc::solution::Solution;
 
-macro_rules! dprintln {
-    ( $( $x:expr ),* ) => {
-        {
-	    #[cfg(test)]
-            println!($($x), *);
-        }
-    };
-}
-
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Hash)]
-struct Range(i64, i64);
-
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Hash)]
-struct Range2D(Range, Range);
+/// The x and y a brick takes, seen from above.
+type Footprint = IntervalBox<i64>;
 
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
 struct Intervals2D {
@@ -32,21 +25,17 @@ impl Intervals2D {
         }
     }
 
-    fn get_max(&self, range: Range2D) -> (i64, Vec<usize>) {
+    fn get_max(&self, range: &Footprint) -> (i64, Vec<usize>) {
         let mut result_z = 0;
         let mut result_bricks = HashSet::new();
 
-        let min_y = min(range.0.1, range.1.1);
-        let min_x = min(range.0.0, range.1.0);
-        let max_y = max(range.0.1, range.1.1);
-        let max_x = max(range.0.0, range.1.0);
-        for y in min_y..=max_y {
-            for x in min_x..=max_x {
+        for y in range.dims[1].range() {
+            for x in range.dims[0].range() {
                 result_z = max(result_z, self.space[y as usize][x as usize]); 
             }
         }
-        for y in min_y..=max_y {
-            for x in min_x..=max_x {
+        for y in range.dims[1].range() {
+            for x in range.dims[0].range() {
                 if result_z == self.space[y as usize][x as usize] {
                     result_bricks.insert(self.brick_no[y as usize][x as usize]);
                 }
@@ -55,18 +44,25 @@ impl Intervals2D {
         (result_z, result_bricks.into_iter().collect())
     }
 
-    fn set_range(&mut self, range: Range2D, val: i64, brick_no: usize) {
-        let min_y = min(range.0.1, range.1.1);
-        let min_x = min(range.0.0, range.1.0);
-        let max_y = max(range.0.1, range.1.1);
-        let max_x = max(range.0.0, range.1.0);
-        for y in min_y..=max_y {
-            for x in min_x..=max_x {
+    fn set_range(&mut self, range: &Footprint, val: i64, brick_no: usize) {
+        for y in range.dims[1].range() {
+            for x in range.dims[0].range() {
                 self.space[y as usize][x as usize] = val;
                 self.brick_no[y as usize][x as usize] = brick_no;
             }
         }
     }
+
+    #[allow(dead_code)]
+    fn print(&self) {
+        for y in 0..self.space.len(){
+            let mut row = String::new();
+            for x in 0..self.space[y].len() {
+                row += &format!("{: >3}[{: >3}];", self.space[y][x], self.brick_no[y][x]);
+            }
+            debug!("{}", row);
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Hash)]
@@ -81,13 +77,13 @@ impl XYZ {
         XYZ { x, y, z }
     }
 
-    fn from_str(s: &str) -> XYZ {
-        let mut coords = s.split(',');
-        XYZ {
-            x: coords.next().unwrap().parse().unwrap(),
-            y: coords.next().unwrap().parse().unwrap(),
-            z: coords.next().unwrap().parse().unwrap(),
-        }
+    fn parse(parser: &mut Parser) -> Result<XYZ> {
+        let x = parser.number()?;
+        parser.expect(",")?;
+        let y = parser.number()?;
+        parser.expect(",")?;
+        let z = parser.number()?;
+        Ok(XYZ { x, y, z })
     }
 }
 
@@ -95,8 +91,11 @@ impl XYZ {
 struct Brick(XYZ, XYZ);
 
 impl Brick {
-    fn footprint2D(&self) -> Range2D {
-        Range2D(Range(self.0.x, self.0.y), Range(self.1.x, self.1.y))
+    fn footprint_2d(&self) -> Footprint {
+        IntervalBox::new(vec![
+            Interval::new(min(self.0.x, self.1.x), max(self.0.x, self.1.x) + 1),
+            Interval::new(min(self.0.y, self.1.y), max(self.0.y, self.1.y) + 1),
+        ])
     }
 
     fn arrange(&mut self) {
@@ -105,6 +104,7 @@ impl Brick {
         }
     }
 
+    #[allow(dead_code)]
     fn low_z(&self) -> i64 {
         self.0.z
     }
@@ -120,28 +120,96 @@ impl Brick {
     }
 }
 
+#[allow(dead_code)]
+#[derive(Debug, Clone)]
+struct Cache {
+}
+
 #[derive(Debug, Clone, Hash)]
-struct Jenga {
+pub struct Jenga {
     bricks: Vec<Brick>,
     max_x: usize,
     max_y: usize,
 
     is_structural: Vec<bool>,
+    supporting_directly: Vec<Vec<usize>>,
+    lays_on: Vec<Vec<usize>>,
 }
 
 impl Jenga {
-    fn new<I>(lines: I) -> Jenga
-      where I: Iterator<Item = String>
-    {
+    fn fall_bricks(&mut self) {
+        let mut intervals = Intervals2D::new(self.max_x + 1, self.max_y + 1);
+
+        for (no, brick) in &mut self.bricks.iter_mut().enumerate().skip(1) {
+            let (max_ground, mut holding_bricks) = intervals.get_max(&brick.footprint_2d());
+            brick.put_on_level(max_ground + 1);
+            if holding_bricks.len() == 1 {
+                self.is_structural[holding_bricks[0]] = true;
+            }
+            for &hb in &holding_bricks {
+                self.supporting_directly[hb].push(no);
+            }
+            self.lays_on[no].append(&mut holding_bricks);
+            intervals.set_range(&brick.footprint_2d(), brick.high_z(), no);
+        }
+    }
+
+    fn count_non_structural(&self) -> usize {
+        self.is_structural.iter().filter(|&e| !e).count()
+    }
+
+    fn is_touching_ground(&self, brick_no: usize, missing_brick: usize) -> bool {
+        if brick_no == missing_brick {
+            return false;
+        }
+        if brick_no == 0 {
+            return true;
+        }
+        for &lo in &self.lays_on[brick_no] {
+            if self.is_touching_ground(lo, missing_brick) {
+                return true;
+            }
+        }
+
+        false
+    }
+
+    fn count_total_falls(&self) -> usize {
+        let mut result = 0;
+        for i in 1..self.is_structural.len() {
+            if !self.is_structural[i] {
+                continue;
+            }
+            let falling = (1..self.bricks.len()).map(|b| self.is_touching_ground(b, i)).filter(|x| !x).count();
+            result += falling - 1;
+        }
+        result
+    }
+
+    #[allow(dead_code)]
+    fn print_bricks(&self) {
+        debug!("Bricks:");
+        for b in &self.bricks {
+            debug!("  {:?}", b);
+        }
+    }
+}
+
+impl Solution for Jenga {
+    const DAY: u32 = crate::DAY;
+
+    fn parse<R: BufRead>(lines: Lines<R>) -> Result<Jenga> {
         let mut bricks = Vec::new();
         let mut max_x = 0;
         let mut max_y = 0;
 
-        for l in lines {
-            let line = l.trim();
-            let mut points = line.split('~');
-            let point_a = XYZ::from_str(points.next().unwrap());
-            let point_b = XYZ::from_str(points.next().unwrap());
+        for line in lines {
+            let line = line?;
+            let mut parser = line.parser();
+            let point_a = XYZ::parse(&mut parser)?;
+            parser.expect("~")?;
+            let point_b = XYZ::parse(&mut parser)?;
+            parser.end()?;
             let mut brick = Brick(point_a, point_b);
             brick.arrange();
             bricks.push(brick);
@@ -153,75 +221,84 @@ impl Jenga {
         bricks.push(Brick(XYZ::new(0, 0, 0), XYZ::new(max_x as i64, max_y as i64, 0)));
         bricks.sort_by_key(|b| (b.0.z, b.1.z));
 
-        Jenga {
+        let mut jenga = Jenga {
             is_structural: vec![false; bricks.len()],
+            supporting_directly: vec![vec![]; bricks.len()],
+            lays_on: vec![vec![]; bricks.len()],
 
             bricks,
             max_x,
             max_y,
-        }
+        };
+        debug!("Jenga: {:?}", jenga);
+        jenga.fall_bricks();
+        debug!("Jenga fallen: {:?}", jenga);
+        Ok(jenga)
     }
 
-    fn fall_bricks(&mut self) {
-        let mut intervals = Intervals2D::new(self.max_x + 1, self.max_y + 1);
-
-
-        for (no, brick) in &mut self.bricks.iter_mut().enumerate() {
-            let (max_ground, holding_bricks) = intervals.get_max(brick.footprint2D());
-            brick.put_on_level(max_ground + 1);
-            if holding_bricks.len() == 1 {
-                self.is_structural[holding_bricks[0]] = true;
-            }
-            intervals.set_range(brick.footprint2D(), brick.high_z(), no);
-        }
+    fn part1(&self) -> Result<impl Display> {
+        Ok(self.count_non_structural())
     }
 
-    fn count_non_structural(&self) -> usize {
-        self.is_structural.iter().filter(|&e| !e).count()
+    fn part2(&self) -> Result<impl Display> {
+        Ok(self.count_total_falls())
     }
 }
 
-fn solve<R: BufRead, W: Write>(input: R, mut output: W) {
-
-    let mut jenga = Jenga::new(BufReader::new(input).lines().map(|l| l.unwrap()));
-    dprintln!("Jenga: {:?}", jenga);
-    jenga.fall_bricks();
-    dprintln!("Jenga fallen: {:?}", jenga);
-
-
-    writeln!(output, "{}", jenga.count_non_structural()).unwrap();
-}
-
-pub fn main() {
-    let stdin = std::io::stdin();
-    let stdout = std::io::stdout();
-    solve(stdin.lock(), stdout.lock());
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc::solution::solve_part2;
 
     fn test_ignore_whitespaces(input: &str, output: &str) {
-        let mut actual_out: Vec<u8> = Vec::new();
-        solve(input.as_bytes(), &mut actual_out);
-        let actual_out_str = String::from_utf8(actual_out).unwrap();
-        let actual_outs = actual_out_str.split_whitespace().collect::<Vec<&str>>();
-        let expected_outs = output.split_whitespace().collect::<Vec<&str>>();
-        assert_eq!(actual_outs, expected_outs);
+        aoc::testing::test_ignore_whitespaces(|i, o| solve_part2::<Jenga, _, _>(i, o), input, output);
+    }
+
+    include!(concat!(env!("OUT_DIR"), "/examples/slabs.rs"));
+
+    #[test]
+    fn mine() {
+        test_ignore_whitespaces(
+            "0,0,1~0,2,1
+            0,1,3~2,1,3
+            2,1,9~2,2,9",
+            "3",
+        );
+    }
+
+    #[test]
+    fn mine2() {
+        test_ignore_whitespaces(
+            "0,0,1~0,2,1
+            0,1,3~2,1,3
+            2,1,3~2,1,5
+            2,1,9~2,2,9",
+            "6",
+        );
+    }
+
+    #[test]
+    fn two_legs() {
+        test_ignore_whitespaces(
+            "0,0,1~0,0,5
+            0,1,1~0,1,5
+            0,0,7~0,1,7
+            0,1,9~0,1,10",
+            "1",
+        );
     }
 
     #[test]
-    fn sample() {
+    fn one_leg() {
         test_ignore_whitespaces(
-            "1,0,1~1,2,1
-            0,0,2~2,0,2
-            0,2,3~2,2,3
-            0,0,4~0,2,4
-            2,0,5~2,2,5
-            0,1,6~2,1,6
-            1,1,8~1,1,9",
-            "5",
+            "0,1,1~0,1,5
+            0,0,6~0,2,6
+            0,0,7~0,0,8
+            0,2,7~0,2,8
+            1,1,1~1,1,7
+            0,1,8~1,1,8
+            0,0,9~0,2,9",
+            "6",
         );
     }
 }
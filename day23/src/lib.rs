@@ -0,0 +1,5 @@
+pub const DAY: u32 = 23;
+
+pub mod walk;
+
+pub type Puzzle = walk::Map;
@@ -1,6 +1,3 @@
-mod walk2;
-
-
 fn main() {
-    walk2::main();
+    aoc::solution::main::<day23::Puzzle>();
 }
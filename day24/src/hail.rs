@@ -1,15 +1,9 @@
 //use std::cmp::{max, min};
-use std::io::{BufRead, BufReader, Write};
-
-#[allow(unused_macros)]
-macro_rules! dprintln {
-    ( $( $x:expr ),* ) => {
-        {
-	    #[cfg(test)]
-            println!($($x), *);
-        }
-    };
-}
+use std::fmt::Display;
+use std::io::BufRead;
+use aoc::debug;
+use aoc::parse::{Line, Lines, Parser, Result};
+use aoc::solution::Solution;
 
 #[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Copy, Clone)]
 struct XYZ {
@@ -18,18 +12,36 @@ struct XYZ {
     z: i128,
 }
 
+// The vector arithmetic is checked and gives None when it overflows, as it
+// does for velocities lifted from residues that are no real solution.
 #[allow(dead_code)]
 impl XYZ {
     const fn new(x: i128, y: i128, z: i128) -> XYZ { XYZ {x, y, z} }
 
-    fn add(&self, other: &XYZ) -> XYZ {
-        XYZ { x: self.x + other.x, y: self.y + other.y , z: self.z + other.z }
+    fn add(&self, other: &XYZ) -> Option<XYZ> {
+        Some(XYZ {
+            x: self.x.checked_add(other.x)?,
+            y: self.y.checked_add(other.y)?,
+            z: self.z.checked_add(other.z)?,
+        })
     }
-    fn sub(&self, other: &XYZ) -> XYZ {
-        XYZ { x: self.x - other.x, y: self.y - other.y , z: self.z - other.z }
+    fn sub(&self, other: &XYZ) -> Option<XYZ> {
+        Some(XYZ {
+            x: self.x.checked_sub(other.x)?,
+            y: self.y.checked_sub(other.y)?,
+            z: self.z.checked_sub(other.z)?,
+        })
     }
-    fn mul(&self, scalar: i128) -> XYZ {
-        XYZ { x: self.x * scalar, y: self.y * scalar , z: self.z * scalar }
+    fn mul(&self, scalar: i128) -> Option<XYZ> {
+        Some(XYZ {
+            x: self.x.checked_mul(scalar)?,
+            y: self.y.checked_mul(scalar)?,
+            z: self.z.checked_mul(scalar)?,
+        })
+    }
+
+    fn sum(&self) -> i128 {
+        self.x + self.y + self.z
     }
 
     fn in_rect_2d(&self, rect: &Rectangle) -> bool {
@@ -41,13 +53,32 @@ impl XYZ {
         other.x * self.y - self.x * other.y
     }
 
-    fn from_str(st: &str) -> XYZ {
-        let parts: Vec<_>  = st.split(", ").collect();
-        XYZ {
-            x: parts[0].trim().parse::<i128>().unwrap(),
-            y: parts[1].trim().parse::<i128>().unwrap(),
-            z: parts[2].trim().parse::<i128>().unwrap(),
-        }
+    fn cross(&self, other: &XYZ) -> Option<XYZ> {
+        let minor = |a: i128, b: i128, c: i128, d: i128| a.checked_mul(b)?.checked_sub(c.checked_mul(d)?);
+        Some(XYZ {
+            x: minor(self.y, other.z, self.z, other.y)?,
+            y: minor(self.z, other.x, self.x, other.z)?,
+            z: minor(self.x, other.y, self.y, other.x)?,
+        })
+    }
+
+    fn dot(&self, other: &XYZ) -> Option<i128> {
+        self.x.checked_mul(other.x)?
+            .checked_add(self.y.checked_mul(other.y)?)?
+            .checked_add(self.z.checked_mul(other.z)?)
+    }
+
+    fn coords(&self) -> [i128; 3] {
+        [self.x, self.y, self.z]
+    }
+
+    fn parse(parser: &mut Parser) -> Result<XYZ> {
+        let x = parser.number()?;
+        parser.expect(",")?;
+        let y = parser.number()?;
+        parser.expect(",")?;
+        let z = parser.number()?;
+        Ok(XYZ { x, y, z })
     }
 }
 
@@ -58,14 +89,47 @@ struct Ray {
 }
 
 impl Ray {
-    fn from_line(line: &str) -> Ray {
-        let mut parts = line.split(" @ ");
-        Ray {
-            start: XYZ::from_str(parts.next().unwrap()),
-            velocity: XYZ::from_str(parts.next().unwrap()),
+    fn parse(line: &Line) -> Result<Ray> {
+        let mut parser = line.parser();
+        let start = XYZ::parse(&mut parser)?;
+        parser.expect("@")?;
+        let velocity = XYZ::parse(&mut parser)?;
+        parser.end()?;
+        Ok(Ray {
+            start,
+            velocity,
+        })
+    }
+
+    // Time at which a stone thrown along `stone` hits this ray, if it does at
+    // a whole, non-negative time.
+    fn hit_time(&self, stone: &Ray) -> Option<i128> {
+        // stone.start + t * stone.velocity = self.start + t * self.velocity
+        let diff = stone.start.sub(&self.start)?.coords();
+        let rel_vel = self.velocity.sub(&stone.velocity)?.coords();
+
+        let mut time = None;
+        for i in 0..3 {
+            if rel_vel[i] == 0 {
+                if diff[i] != 0 {
+                    return None;
+                }
+                continue;
+            }
+            if diff[i] % rel_vel[i] != 0 {
+                return None;
+            }
+            let t = diff[i] / rel_vel[i];
+            if t < 0 || time.is_some_and(|prev| prev != t) {
+                return None;
+            }
+            time = Some(t);
         }
+        // Parallel with the same start; any time works.
+        Some(time.unwrap_or(0))
     }
 
+    #[allow(dead_code)]
     fn crosses_with(&self, other: &Ray, rect: &Rectangle) -> bool {
         let mut vec_pro = self.velocity.vector_product_2d(&other.velocity);
         // 1. Check if parallel.
@@ -76,7 +140,7 @@ impl Ray {
         // 2. Check if t > 0
         let mut t_self_numerator = (self.start.x - other.start.x) * other.velocity.y +
             (other.start.y - self.start.y) * other.velocity.x;
-        let mut t_other_numerator = (self.start.x - other.start.x) * self.velocity.y +
+        let t_other_numerator = (self.start.x - other.start.x) * self.velocity.y +
             (other.start.y - self.start.y) * self.velocity.x;
 
         if vec_pro > 0 {
@@ -135,11 +199,64 @@ impl Rectangle {
 }
 
 #[derive(Debug, PartialEq, Clone)]
-struct Solution {
+pub struct Hailstorm {
     rays: Vec<Ray>,
 }
 
-impl Solution {
+// Mersenne prime 2^61 - 1, so that a product of two residues fits in i128.
+const MODULUS: i128 = (1 << 61) - 1;
+
+fn mod_mul(a: i128, b: i128) -> i128 {
+    (a.rem_euclid(MODULUS) * b.rem_euclid(MODULUS)) % MODULUS
+}
+
+fn mod_inverse(a: i128) -> i128 {
+    // Fermat's little theorem: a^(p-2) = a^-1 (mod p).
+    let mut res = 1;
+    let mut base = a.rem_euclid(MODULUS);
+    let mut exp = MODULUS - 2;
+    while exp > 0 {
+        if exp % 2 == 1 {
+            res = mod_mul(res, base);
+        }
+        base = mod_mul(base, base);
+        exp /= 2;
+    }
+    res
+}
+
+// Solves three linear equations `coeffs . v = rhs` for v modulo MODULUS and
+// returns the residues mapped to (-MODULUS/2, MODULUS/2]. Exact as long as the
+// real solution is a small enough integer vector.
+fn solve_3x3_modulo(equations: &[(XYZ, i128); 3]) -> Option<XYZ> {
+    let mut rows: Vec<[i128; 4]> = equations.iter()
+        .map(|(c, r)| [c.x, c.y, c.z, *r].map(|v| v.rem_euclid(MODULUS)))
+        .collect();
+
+    for col in 0..3 {
+        let pivot = (col..3).find(|&r| rows[r][col] != 0)?;
+        rows.swap(col, pivot);
+        let inv = mod_inverse(rows[col][col]);
+        for v in rows[col].iter_mut() {
+            *v = mod_mul(*v, inv);
+        }
+        for r in 0..3 {
+            if r == col || rows[r][col] == 0 {
+                continue;
+            }
+            let factor = rows[r][col];
+            let pivot_row = rows[col];
+            for (v, p) in rows[r].iter_mut().zip(pivot_row) {
+                *v = (*v - mod_mul(factor, p)).rem_euclid(MODULUS);
+            }
+        }
+    }
+
+    let symmetric = |v: i128| if v > MODULUS / 2 { v - MODULUS } else { v };
+    Some(XYZ::new(symmetric(rows[0][3]), symmetric(rows[1][3]), symmetric(rows[2][3])))
+}
+
+impl Hailstorm {
     fn collisions_inside(&self, min_coord: i128, max_coord: i128) -> i128 {
         let mut collisions = 0;
         let rect = Rectangle::new(min_coord, max_coord);
@@ -153,51 +270,152 @@ impl Solution {
         }
         collisions
     }
-}
 
+    // In the stone's frame of reference every hailstone moves with velocity
+    // v_i - V and they all pass through the stone's start. So any two of those
+    // lines have to be coplanar:
+    //   (p_j - p_i) . ((v_i - V) x (v_j - V)) = 0
+    // The V x V term cancels, which leaves an equation linear in V:
+    //   V . ((p_j - p_i) x (v_j - v_i)) = -(p_j - p_i) . (v_i x v_j)
+    fn velocity_equation(a: &Ray, b: &Ray) -> Option<(XYZ, i128)> {
+        let start_diff = b.start.sub(&a.start)?;
+        let coeffs = start_diff.cross(&b.velocity.sub(&a.velocity)?)?;
+        let rhs = start_diff.dot(&a.velocity.cross(&b.velocity)?)?.checked_neg()?;
+        Some((coeffs, rhs))
+    }
 
-fn parse_input<R: BufRead>(input: R) -> Solution {
-    let mut rays = Vec::new();
-    for line in BufReader::new(input).lines().map(|l| l.unwrap()) {
-        rays.push(Ray::from_line(line.trim()));
+    // Start of the stone, given its velocity: where the first hailstone's line
+    // (in the stone's frame) meets any other non-parallel one.
+    fn start_for_velocity(&self, velocity: &XYZ) -> Option<XYZ> {
+        let first = self.rays.first()?;
+        let first_dir = first.velocity.sub(velocity)?;
+        for other in &self.rays[1..] {
+            let other_dir = other.velocity.sub(velocity)?;
+            let normal = first_dir.cross(&other_dir)?;
+            let norm_sq = normal.dot(&normal)?;
+            if norm_sq == 0 {
+                continue;
+            }
+            let t_num = other.start.sub(&first.start)?.cross(&other_dir)?.dot(&normal)?;
+            if t_num % norm_sq != 0 {
+                return None;
+            }
+            return first.start.add(&first_dir.mul(t_num / norm_sq)?);
+        }
+        None
     }
-    dprintln!("Rays: {:?}", rays);
-    Solution {
-        rays,
+
+    fn throwing_ray(&self) -> Option<Ray> {
+        let considered = self.rays.len().min(6);
+        let mut equations = Vec::new();
+        for i in 0..considered {
+            for j in (i + 1)..considered {
+                equations.extend(Hailstorm::velocity_equation(&self.rays[i], &self.rays[j]));
+            }
+        }
+
+        for a in 0..equations.len() {
+            for b in (a + 1)..equations.len() {
+                for c in (b + 1)..equations.len() {
+                    let picked = [equations[a], equations[b], equations[c]];
+                    let Some(velocity) = solve_3x3_modulo(&picked) else {
+                        continue;
+                    };
+                    let Some(start) = self.start_for_velocity(&velocity) else {
+                        continue;
+                    };
+                    let stone = Ray { start, velocity };
+                    debug!("Candidate stone: {:?}", stone);
+                    if self.rays.iter().all(|r| r.hit_time(&stone).is_some()) {
+                        return Some(stone);
+                    }
+                }
+            }
+        }
+        None
     }
+
 }
 
-fn solve<R: BufRead, W: Write>(input: R, mut output: W) {
-    let sol = parse_input(input);
+impl Solution for Hailstorm {
+    const DAY: u32 = crate::DAY;
 
-    writeln!(output, "{}", sol.collisions_inside(200000000000000, 400000000000000)).unwrap();
-}
+    fn parse<R: BufRead>(lines: Lines<R>) -> Result<Hailstorm> {
+        let mut rays = Vec::new();
+        for line in lines {
+            rays.push(Ray::parse(&line?)?);
+        }
+        debug!("Rays: {:?}", rays);
+        Ok(Hailstorm {
+            rays,
+        })
+    }
+
+    fn part1(&self) -> Result<impl Display> {
+        Ok(self.collisions_inside(200000000000000, 400000000000000))
+    }
+
+    fn part2(&self) -> Result<impl Display> {
+        let stone = self.throwing_ray()
+            .ok_or_else(|| Self::invalid("no throw hits every hailstone"))?;
 
-pub fn main() {
-    let stdin = std::io::stdin();
-    let stdout = std::io::stdout();
-    solve(stdin.lock(), stdout.lock());
+        Ok(stone.start.sum())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc::solution::solve_part2;
+
+    const SAMPLE: &str = include_str!("../examples/hail.sample.in");
 
-    fn test_solution(input: &str, min: i128, max: i128, expected_out: i128) {
-        let sol = parse_input(input.as_bytes());
-        assert_eq!(sol.collisions_inside(min, max), expected_out);
+    fn parse_input(input: &str) -> Hailstorm {
+        Hailstorm::parse(Lines::new(input.as_bytes(), crate::DAY)).unwrap()
     }
 
+    fn test_error(input: &str, error: &str) {
+        aoc::testing::test_error(|i, o| solve_part2::<Hailstorm, _, _>(i, o), input, error);
+    }
+
+    include!(concat!(env!("OUT_DIR"), "/examples/hail.rs"));
+
+    #[test]
+    fn collisions_sample() {
+        assert_eq!(parse_input(SAMPLE).collisions_inside(7, 27), 2);
+    }
+
+    #[test]
+    fn throwing_ray_sample() {
+        assert_eq!(
+            parse_input(SAMPLE).throwing_ray(),
+            Some(Ray { start: XYZ::new(24, 13, 10), velocity: XYZ::new(-3, 1, 2) }),
+        );
+    }
+
+    #[test]
+    fn malformed() {
+        test_error(
+            "19, 13, 30 @ -2,  1, -2
+            18, 19 @ -1, -1, -2",
+            "day 24, line 2, column 20: expected `,`, found `@`",
+        );
+        test_error(
+            "19, 13, 30 @ -2,  1",
+            "day 24, line 1, column 20: expected `,`, found end of line",
+        );
+    }
     #[test]
-    fn sample() {
-        test_solution(
+    fn no_throw() {
+        // The residues of the velocity lift to huge numbers, with no whole
+        // solution to check them against.
+        test_error(
             "19, 13, 30 @ -2,  1, -2
-            18, 19, 22 @ -1, -1, -2
+            18,719, 22 @ -1, -1, -2
             20, 25, 34 @ -2, -2, -4
             12, 31, 28 @ -1, -2, -1
             20, 19, 15 @  1, -5, -3",
-            7, 27,
-            2,
+            "day 24: no throw hits every hailstone",
         );
     }
 }
@@ -1,17 +1,9 @@
 //use std::cmp::{max, min};
-use std::io::{BufRead, BufReader, Write};
+use std::io::{BufRead, Write};
 use std::collections::HashSet;
 use std::hash::{Hash, Hasher};
-
-#[allow(unused_macros)]
-macro_rules! dprintln {
-    ( $( $x:expr ),* ) => {
-        {
-	    #[cfg(test)]
-            println!($($x), *);
-        }
-    };
-}
+use aoc::debug;
+use aoc::parse::{Line, Lines, Parser, Result};
 
 #[derive(Debug, PartialEq, PartialOrd, Copy, Clone)]
 struct XYZ {
@@ -48,13 +40,13 @@ impl XYZ {
         other.x * self.y - self.x * other.y
     }
 
-    fn from_str(st: &str) -> XYZ {
-        let parts: Vec<_>  = st.split(", ").collect();
-        XYZ {
-            x: parts[0].trim().parse::<f64>().unwrap(),
-            y: parts[1].trim().parse::<f64>().unwrap(),
-            z: parts[2].trim().parse::<f64>().unwrap(),
-        }
+    fn parse(parser: &mut Parser) -> Result<XYZ> {
+        let x = parser.number()?;
+        parser.expect(",")?;
+        let y = parser.number()?;
+        parser.expect(",")?;
+        let z = parser.number()?;
+        Ok(XYZ { x, y, z })
     }
 }
 
@@ -73,12 +65,16 @@ struct Ray {
 }
 
 impl Ray {
-    fn from_line(line: &str) -> Ray {
-        let mut parts = line.split(" @ ");
-        Ray {
-            start: XYZ::from_str(parts.next().unwrap()),
-            velocity: XYZ::from_str(parts.next().unwrap()),
-        }
+    fn parse(line: &Line) -> Result<Ray> {
+        let mut parser = line.parser();
+        let start = XYZ::parse(&mut parser)?;
+        parser.expect("@")?;
+        let velocity = XYZ::parse(&mut parser)?;
+        parser.end()?;
+        Ok(Ray {
+            start,
+            velocity,
+        })
     }
 
     fn at_time(&self, t: f64) -> XYZ {
@@ -150,8 +146,8 @@ impl CutThroughRec {
             }
         }
         let cuts: Vec<XYZ> = Self::cut_points(ray, &rect).into_iter().collect();
-        println!("Cuts for {:?}, are: {:?}", ray, cuts);
-        println!("with rect: {:?}", rect);
+        debug!("Cuts for {:?}, are: {:?}", ray, cuts);
+        debug!("with rect: {:?}", rect);
 
         if cuts.len() == 2 {
             Some(CutThroughRec {
@@ -170,21 +166,21 @@ impl CutThroughRec {
             ray.at_y(rect.min_y),
             ray.at_y(rect.max_y),
         ];
-        println!("pot points: {:?}", potential_points);
+        debug!("pot points: {:?}", potential_points);
         potential_points.into_iter()
-            .filter(|o| o.is_some()).map(|o| o.unwrap()).filter(|p| p.in_rect_2d(rect)).collect()
+            .flatten().filter(|p| p.in_rect_2d(rect)).collect()
     }
 
 
 
     fn crosses_the_cut(&self, ray: &Ray) -> bool {
-        println!("Checking cross: {:?}, for ray: {:?}", self, ray);
+        debug!("Checking cross: {:?}, for ray: {:?}", self, ray);
         let mut other_cuts = Self::cut_points(ray, &self.rect);
-        println!(" - potential cuts: {:?}", other_cuts);
+        debug!(" - potential cuts: {:?}", other_cuts);
         if ray.start.in_rect_2d(&self.rect) {
             other_cuts.insert(ray.start);
         }
-        println!(" - potential cuts: {:?}", other_cuts);
+        debug!(" - potential cuts: {:?}", other_cuts);
         if other_cuts.len() < 2 {
             return false;
         }
@@ -196,7 +192,7 @@ impl CutThroughRec {
         let dir1 = self.cut_points[1].sub(&base_point).vector_product_2d(other_cuts[0].sub(&base_point));
         let dir2 = self.cut_points[1].sub(&base_point).vector_product_2d(other_cuts[1].sub(&base_point));
 
-        println!(" - did we find a cut: {:?}", dir1 * dir2 < 0.);
+        debug!(" - did we find a cut: {:?}", dir1 * dir2 < 0.);
         dir1 * dir2 < 0.0
     }
 }
@@ -212,13 +208,13 @@ impl Solution {
 
         let cuts: Vec<_> =
             self.rays.iter().map(|r| CutThroughRec::try_cutting(r, min_coord, max_coord)).collect();
-        println!("Cuts: {:?}", cuts);
+        debug!("Cuts: {:?}", cuts);
         for i in 0..(cuts.len() - 1) {
             if let Some(base_cut) = &cuts[i] {
                 for j in (i + 1)..cuts.len() {
-                    println!("Checking {} with {}", i, j);
+                    debug!("Checking {} with {}", i, j);
                     if base_cut.crosses_the_cut(&self.rays[j]) {
-                        println!("no {} crosses {}", i, j);
+                        debug!("no {} crosses {}", i, j);
                         collisions += 1;
                     }
                 }
@@ -229,27 +225,22 @@ impl Solution {
 }
 
 
-fn parse_input<R: BufRead>(input: R) -> Solution {
+fn parse_input<R: BufRead>(input: R) -> Result<Solution> {
     let mut rays = Vec::new();
-    for line in BufReader::new(input).lines().map(|l| l.unwrap()) {
-        rays.push(Ray::from_line(line.trim()));
+    for line in Lines::new(input, crate::DAY) {
+        rays.push(Ray::parse(&line?)?);
     }
-    dprintln!("Rays: {:?}", rays);
-    Solution {
+    debug!("Rays: {:?}", rays);
+    Ok(Solution {
         rays,
-    }
+    })
 }
 
-fn solve<R: BufRead, W: Write>(input: R, mut output: W) {
-    let sol = parse_input(input);
-
-    writeln!(output, "{}", sol.collisions_inside(200000000000000., 400000000000000.)).unwrap();
-}
+pub fn solve<R: BufRead, W: Write>(input: R, mut output: W) -> Result<()> {
+    let sol = parse_input(input)?;
 
-pub fn main() {
-    let stdin = std::io::stdin();
-    let stdout = std::io::stdout();
-    solve(stdin.lock(), stdout.lock());
+    writeln!(output, "{}", sol.collisions_inside(200000000000000., 400000000000000.))?;
+    Ok(())
 }
 
 #[cfg(test)]
@@ -257,7 +248,7 @@ mod tests {
     use super::*;
 
     fn test_solution(input: &str, min: f64, max: f64, expected_out: i64) {
-        let sol = parse_input(input.as_bytes());
+        let sol = parse_input(input.as_bytes()).unwrap();
         assert_eq!(sol.collisions_inside(min, max), expected_out);
     }
 
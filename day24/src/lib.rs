@@ -0,0 +1,6 @@
+pub const DAY: u32 = 24;
+
+pub mod hail;
+pub mod hail_v0_not_working;
+
+pub type Puzzle = hail::Hailstorm;
@@ -1,6 +1,3 @@
-mod hail;
-
-
 fn main() {
-    hail::main();
+    aoc::solution::main::<day24::Puzzle>();
 }
@@ -0,0 +1,5 @@
+pub const DAY: u32 = 25;
+
+pub mod snowerload;
+
+pub type Puzzle = snowerload::Graph;
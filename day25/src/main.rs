@@ -1,6 +1,3 @@
-mod snowerload;
-
-
 fn main() {
-    snowerload::main();
+    aoc::solution::main::<day25::Puzzle>();
 }
@@ -1,19 +1,13 @@
 //use std::cmp::{max, min};
-use std::io::{BufRead, BufReader, Write};
+use std::fmt::Display;
+use std::io::BufRead;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::collections::VecDeque;
 use std::mem::swap;
-
-#[allow(unused_macros)]
-macro_rules! dprintln {
-    ( $( $x:expr ),* ) => {
-        {
-	    #[cfg(test)]
-            println!($($x), *);
-        }
-    };
-}
+use aoc::debug;
+use aoc::parse::{Line, Lines, Result};
+use aoc::solution::Solution;
 
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
 struct Node {
@@ -22,17 +16,18 @@ struct Node {
 }
 
 impl Node {
-    fn parse(line: &str) -> Node {
-        let first_parts: Vec<_> = line.split(": ").collect();
-        let name = first_parts[0].to_string();
+    fn parse(line: &Line) -> Result<Node> {
+        let mut parser = line.parser();
+        let name = parser.word()?.to_string();
+        parser.expect(":")?;
         let mut adjs = Vec::new();
-        for a in first_parts[1].split_whitespace() {
-            adjs.push(a.trim().to_string());
+        while !parser.is_done() {
+            adjs.push(parser.word()?.to_string());
         }
-        Node {
+        Ok(Node {
             name,
             adjs,
-        }
+        })
     }
 
     fn empty(name: &str) -> Node {
@@ -53,34 +48,89 @@ impl Node {
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
-struct Graph {
+pub struct Graph {
     nodes: HashMap<String, Node>,
 }
 
-impl Graph {
-    fn parse<I>(lines: I) -> Graph
-        where I: Iterator<Item=String>
-    {
-        let mut nodes_list = Vec::new();
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct Cut {
+    // Each edge with its endpoints in order.
+    edges: Vec<(String, String)>,
+    // Nodes on one side of the cut, the rest of the graph is on the other.
+    side: HashSet<String>,
+}
 
-        for l in lines {
-            let line = l.trim();
-            nodes_list.push(Node::parse(line));
+// Graph with nodes as indices and every undirected edge having capacity 1 in
+// both directions.
+struct FlowNetwork {
+    // (neighbour, edge index)
+    adjs: Vec<Vec<(usize, usize)>>,
+    edges: Vec<(usize, usize)>,
+    // Flow along the edge, positive when it goes from edges[e].0 to edges[e].1.
+    flow: Vec<i64>,
+}
+
+impl FlowNetwork {
+    fn residual(&self, from: usize, edge: usize) -> i64 {
+        if self.edges[edge].0 == from {
+            1 - self.flow[edge]
+        } else {
+            1 + self.flow[edge]
         }
+    }
 
-        let mut nodes: HashMap<_, _> = nodes_list.iter().map(|n| (n.name.clone(), n.clone())).collect();
+    fn push(&mut self, from: usize, edge: usize) {
+        if self.edges[edge].0 == from {
+            self.flow[edge] += 1;
+        } else {
+            self.flow[edge] -= 1;
+        }
+    }
 
-        for node in nodes_list {
-            for a in &node.adjs {
-                nodes.entry(a.to_string()).or_insert(Node::empty(a)).adjs.push(node.name.clone());
+    // BFS over edges with residual capacity. Returns, for each reached node,
+    // the edge it was reached by.
+    fn residual_bfs(&self, source: usize) -> Vec<Option<Option<usize>>> {
+        let mut came_by = vec![None; self.adjs.len()];
+        came_by[source] = Some(None);
+        let mut queue = VecDeque::new();
+        queue.push_back(source);
+
+        while let Some(node) = queue.pop_front() {
+            for &(a, e) in &self.adjs[node] {
+                if came_by[a].is_some() || self.residual(node, e) <= 0 {
+                    continue;
+                }
+                came_by[a] = Some(Some(e));
+                queue.push_back(a);
             }
         }
+        came_by
+    }
 
-        Graph {
-            nodes,
+    // Max flow from source to sink, but stops as soon as it reaches `limit`.
+    fn max_flow(&mut self, source: usize, sink: usize, limit: i64) -> i64 {
+        self.flow.iter_mut().for_each(|f| *f = 0);
+        let mut total = 0;
+
+        while total < limit {
+            let came_by = self.residual_bfs(source);
+            if came_by[sink].is_none() {
+                break;
+            }
+            let mut curr = sink;
+            while let Some(Some(e)) = came_by[curr] {
+                let (a, b) = self.edges[e];
+                let prev = if a == curr { b } else { a };
+                self.push(prev, e);
+                curr = prev;
+            }
+            total += 1;
         }
+        total
     }
+}
 
+impl Graph {
     fn remove_directed_edge(&mut self, start: &str, end: &str) {
         let start_adjs = &mut self.nodes.get_mut(start).unwrap().adjs;
         let start_idx = start_adjs.iter().position(|el| el == end).unwrap();
@@ -100,7 +150,7 @@ impl Graph {
         while let Some(node) = queue.pop_front() {
 
             for a in &self.nodes.get(node).unwrap().adjs {
-                if let Some(_) = labels.get(a) {
+                if labels.get(a).is_some() {
                     continue;
                 }
                 labels.insert(a.to_string(), label);
@@ -109,7 +159,7 @@ impl Graph {
         }
     }
 
-    fn multiply_connected_components(&self) -> i64 {
+    fn multiply_connected_components(&self) -> Result<i64> {
         let mut labels = HashMap::new();
         let mut label = 0;
         for node in self.nodes.keys() {
@@ -119,11 +169,11 @@ impl Graph {
             }
         }
         if label != 2 {
-            panic!("We were supposed to have 2 components but we have {}", label);
+            return Err(Self::invalid(&format!("the cut leaves {} components instead of 2", label)));
         }
         let num_zeros = labels.iter().filter(|(_, &v)| v == 0).count();
         let num_ones = labels.iter().filter(|(_, &v)| v == 1).count();
-        num_zeros as i64 * num_ones as i64
+        Ok(num_zeros as i64 * num_ones as i64)
     }
 
     fn get_all_edges(&self) -> Vec<(String, String)> {
@@ -141,13 +191,73 @@ impl Graph {
         edges.into_iter().collect()
     }
 
+    fn flow_network(&self) -> (Vec<String>, FlowNetwork) {
+        let mut names: Vec<_> = self.nodes.keys().cloned().collect();
+        names.sort();
+        let idx: HashMap<_, _> = names.iter().enumerate().map(|(i, n)| (n.clone(), i)).collect();
+
+        let mut edges = self.get_all_edges();
+        edges.sort();
+
+        let mut adjs = vec![Vec::new(); names.len()];
+        let mut idx_edges = Vec::new();
+        for (e, (a, b)) in edges.iter().enumerate() {
+            let (a, b) = (idx[a], idx[b]);
+            adjs[a].push((b, e));
+            adjs[b].push((a, e));
+            idx_edges.push((a, b));
+        }
+
+        let flow = vec![0; idx_edges.len()];
+        (names, FlowNetwork { adjs, edges: idx_edges, flow })
+    }
+
+    // Global minimum edge cut. Every cut separates the first node from some
+    // other node, so it is the smallest of the s-t max flows from the first
+    // node. Each flow only needs to run until it matches the best cut so far,
+    // which keeps it cheap when the cut is small.
+    fn min_cut(&self) -> Result<Cut> {
+        let (names, mut network) = self.flow_network();
+
+        let source = 0;
+        let mut best: Option<(i64, Vec<bool>)> = None;
+        for sink in 1..names.len() {
+            let limit = best.as_ref().map_or(i64::MAX, |(flow, _)| *flow);
+            let flow = network.max_flow(source, sink, limit);
+            if flow < limit {
+                let reached = network.residual_bfs(source).iter().map(|c| c.is_some()).collect();
+                best = Some((flow, reached));
+            }
+        }
+        let (_, reached) = best.ok_or_else(|| Self::invalid("there are no two nodes to cut apart"))?;
+
+        let edges: Vec<_> = network.edges.iter()
+            .filter(|&&(a, b)| reached[a] != reached[b])
+            .map(|&(a, b)| (names[a].clone(), names[b].clone()))
+            .collect();
+        debug!("Min cut of size {}: {:?}", edges.len(), edges);
+
+        Ok(Cut {
+            edges,
+            side: names.iter().zip(reached).filter(|(_, r)| *r).map(|(n, _)| n.clone()).collect(),
+        })
+    }
+
+    fn split_by_min_cut(&mut self) -> Result<Cut> {
+        let cut = self.min_cut()?;
+        for (a, b) in &cut.edges {
+            self.remove_edge(a, b);
+        }
+        Ok(cut)
+    }
+
     #[allow(dead_code)]
     fn as_graph(&self) -> String {
 
         let mut nodes = String::new();
         let mut edges = String::new();
 
-        for (n, _) in &self.nodes {
+        for n in self.nodes.keys() {
             nodes += &format!("{};\n", n);
         }
 
@@ -169,7 +279,7 @@ impl Graph {
         let styling = String::new();
         let mut graph = String::new();
 
-        for (_, no ) in &self.nodes {
+        for no in self.nodes.values() {
             //styling += &mo.digraph_styling();
             //styling += "\n";
 
@@ -188,71 +298,77 @@ impl Graph {
 		}
 }
 
-fn parse_input<R: BufRead>(input: R) -> Graph {
-    let lines = BufReader::new(input).lines().map(|l| l.unwrap());
-    Graph::parse(lines)
-}
+impl Solution for Graph {
+    const DAY: u32 = crate::DAY;
 
-fn solve<R: BufRead, W: Write>(input: R, mut output: W) {
-    let mut graph = parse_input(input);
-    //println!("{}", graph.as_graph());
-    //return;
+    fn parse<R: BufRead>(lines: Lines<R>) -> Result<Graph> {
+        let mut nodes_list = Vec::new();
 
-    // Found by graphvizing the graph
-    graph.remove_edge("rjs", "mrd");
-    graph.remove_edge("gmr", "ntx");
-    graph.remove_edge("ncg", "gsk");
+        for line in lines {
+            nodes_list.push(Node::parse(&line?)?);
+        }
 
-    writeln!(output, "{}", graph.multiply_connected_components()).unwrap();
-}
+        let mut nodes: HashMap<_, _> = nodes_list.iter().map(|n| (n.name.clone(), n.clone())).collect();
 
-pub fn main() {
-    let stdin = std::io::stdin();
-    let stdout = std::io::stdout();
-    solve(stdin.lock(), stdout.lock());
+        for node in nodes_list {
+            for a in &node.adjs {
+                nodes.entry(a.to_string()).or_insert(Node::empty(a)).adjs.push(node.name.clone());
+            }
+        }
+
+        if nodes.len() < 2 {
+            return Err(Self::invalid("there have to be at least two nodes to cut apart"));
+        }
+        Ok(Graph {
+            nodes,
+        })
+    }
+
+    fn part1(&self) -> Result<impl Display> {
+        let mut graph = self.clone();
+        graph.split_by_min_cut()?;
+        graph.multiply_connected_components()
+    }
+
+    fn part2(&self) -> Result<impl Display> {
+        Err::<i64, _>(Self::invalid("there is no part 2, the last star comes with all the others"))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc::solution::{solve_part1, solve_part2};
+    use aoc::testing::test_error;
 
-    #[allow(dead_code)]
-    fn test_exact(input: &str, output: &str) {
-        let mut actual_out: Vec<u8> = Vec::new();
-        solve(input.as_bytes(), &mut actual_out);
-        assert_eq!(String::from_utf8(actual_out).unwrap(), output);
+    const SAMPLE: &str = include_str!("../examples/snowerload.sample.in");
+
+    include!(concat!(env!("OUT_DIR"), "/examples/snowerload.rs"));
+
+    #[test]
+    fn no_part2() {
+        test_error(|i, o| solve_part2::<Graph, _, _>(i, o), SAMPLE,
+                   "day 25: there is no part 2, the last star comes with all the others");
     }
 
-    #[allow(dead_code)]
-    fn test_ignore_whitespaces(input: &str, output: &str) {
-        let mut actual_out: Vec<u8> = Vec::new();
-        solve(input.as_bytes(), &mut actual_out);
-        let actual_out_str = String::from_utf8(actual_out).unwrap();
-        let actual_outs = actual_out_str.split_whitespace().collect::<Vec<&str>>();
-        let expected_outs = output.split_whitespace().collect::<Vec<&str>>();
-        assert_eq!(actual_outs, expected_outs);
+    #[test]
+    fn min_cut_sample() {
+        let mut graph = Graph::parse(Lines::new(SAMPLE.as_bytes(), crate::DAY)).unwrap();
+        let cut = graph.split_by_min_cut().unwrap();
+        assert_eq!(cut.edges, vec![
+            ("bvb".to_string(), "cmg".to_string()),
+            ("hfx".to_string(), "pzl".to_string()),
+            ("jqt".to_string(), "nvd".to_string()),
+        ]);
+        assert_eq!(cut.side.len() * (graph.nodes.len() - cut.side.len()), 54);
+        assert_eq!(graph.multiply_connected_components().unwrap(), 54);
     }
 
     #[test]
-    fn sample() {
-        let input =
-            "jqt: rhn xhk nvd
-            rsh: frs pzl lsr
-            xhk: hfx
-            cmg: qnr nvd lhk bvb
-            rhn: xhk bvb hfx
-            bvb: xhk hfx
-            pzl: lsr hfx nvd
-            qnr: nvd
-            ntq: jqt hfx bvb xhk
-            nvd: lhk
-            lsr: lhk
-            rzs: qnr cmg lsr rsh
-            frs: qnr lhk lsr";
-        let mut graph = parse_input(input.as_bytes());
-        graph.remove_edge("hfx", "pzl");
-        graph.remove_edge("bvb", "cmg");
-        graph.remove_edge("nvd", "jqt");
-        assert_eq!(graph.multiply_connected_components(), 54);
+    fn too_small() {
+        test_error(|i, o| solve_part1::<Graph, _, _>(i, o), "abc:",
+                   "day 25: there have to be at least two nodes to cut apart");
+        test_error(|i, o| solve_part1::<Graph, _, _>(i, o), "a: b\nc: d\ne: f",
+                   "day 25: the cut leaves 3 components instead of 2");
     }
 }
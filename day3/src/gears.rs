@@ -0,0 +1,150 @@
+use std::cmp::{max, min};
+use std::collections::HashSet;
+use std::fmt::Display;
+use std::io::BufRead;
+use aoc::debug;
+use aoc::parse::{Line, Lines, Result};
+use aoc::solution::Solution;
+
+#[derive(Debug)]
+enum SchematicPart {
+    Dot,
+    Symbol,
+    Num(usize),
+}
+
+#[derive(Debug)]
+pub struct Schematic {
+    nums: Vec<i64>,
+    content: Vec<Vec<SchematicPart>>,
+    symbols: Vec<(usize, usize)>,
+    stars: Vec<(usize, usize)>,
+}
+
+impl Schematic {
+    fn new_empty() -> Schematic {
+        Schematic {
+            nums: Vec::new(),
+            content: Vec::new(),
+            symbols: Vec::new(),
+            stars: Vec::new(),
+        }
+    }
+
+    fn add_line(&mut self, full_line: &Line) -> Result<()> {
+        let line = full_line.text.trim_start();
+        let indent = full_line.text.len() - line.len();
+        let line = line.trim_end();
+        let row = self.content.len();
+        self.content.push(Vec::new());
+        let mut char_it = line.char_indices().peekable();
+        while let Some((i, c)) = char_it.next() {
+            match c {
+                c if c.is_ascii_digit() => {
+                    let num_start = i;
+                    let mut num_end = i + 1;
+                    self.content[row].push(SchematicPart::Num(self.nums.len()));
+                    while let Some((_, nc)) = char_it.peek() {
+                        if !nc.is_ascii_digit() { break; }
+                        let Some((j, _d)) = char_it.next() else { panic!() };
+                        num_end = j + 1;
+                        self.content[row].push(SchematicPart::Num(self.nums.len()));
+                    }
+                    let num = &line[num_start..num_end];
+                    self.nums.push(num.parse::<i64>().map_err(|_|
+                        full_line.error(indent + num_start + 1, "a number", &format!("`{}`", num))
+                    )?);
+                }
+                '.' => self.content[row].push(SchematicPart::Dot),
+                _ => {
+                    if c == '*' {
+                        self.stars.push((row, self.content[row].len()));
+                    }
+                    self.symbols.push((row, self.content[row].len()));
+                    self.content[row].push(SchematicPart::Symbol);
+                },
+            }
+        }
+
+        if row > 0 && self.content[row].len() != self.content[0].len() {
+            let expected = format!("a row of {} characters", self.content[0].len());
+            let found = format!("{} characters", self.content[row].len());
+            return Err(full_line.error(indent + 1, &expected, &found));
+        }
+        Ok(())
+    }
+
+    /// Indices of the numbers next to `(row, col)`.
+    fn adjacent_nums(&self, row: usize, col: usize) -> HashSet<usize> {
+        let min_row = max(0, (row as i32) - 1) as usize;
+        let max_row = min(self.content.len(), row + 2);
+        let min_col = max(0, (col as i32) - 1) as usize;
+        let max_col = min(self.content[0].len(), col + 2);
+
+        let mut nums = HashSet::new();
+        for i in min_row..max_row {
+            for j in min_col..max_col {
+                if let SchematicPart::Num(idx) = self.content[i][j] {
+                    nums.insert(idx);
+                }
+            }
+        }
+        nums
+    }
+
+    fn sum_of_parts(&self) -> i64 {
+        let parts: HashSet<usize> = self.symbols.iter()
+            .flat_map(|(row, col)| self.adjacent_nums(*row, *col))
+            .collect();
+
+        parts.into_iter().map(|idx| self.nums[idx]).sum()
+    }
+
+    fn sum_of_gear_ratios(&self) -> i64 {
+        let mut ratio_sum = 0_i64;
+        for (row, col) in &self.stars {
+            let nums = self.adjacent_nums(*row, *col);
+            if nums.len() == 2 {
+                ratio_sum += nums.into_iter().map(|x| self.nums[x]).reduce(|a, b| a * b).unwrap();
+            }
+        }
+        ratio_sum
+    }
+}
+
+impl Solution for Schematic {
+    const DAY: u32 = crate::DAY;
+
+    fn parse<R: BufRead>(lines: Lines<R>) -> Result<Schematic> {
+        let mut schema = Schematic::new_empty();
+
+        for line in lines {
+            schema.add_line(&line?)?;
+        }
+        debug!("{:?}", schema);
+        Ok(schema)
+    }
+
+    fn part1(&self) -> Result<impl Display> {
+        Ok(self.sum_of_parts())
+    }
+
+    fn part2(&self) -> Result<impl Display> {
+        Ok(self.sum_of_gear_ratios())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Schematic;
+    use aoc::solution::{solve_part1, solve_part2};
+    use aoc::testing::test_exact;
+
+    include!(concat!(env!("OUT_DIR"), "/examples/gears.rs"));
+
+    #[test]
+    fn test_single_line() {
+        test_exact(|i, o| solve_part1::<Schematic, _, _>(i, o), "12*10...3..10#", "32\n");
+        test_exact(|i, o| solve_part2::<Schematic, _, _>(i, o), "12*10...3..10#", "120\n");
+    }
+}
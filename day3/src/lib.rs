@@ -0,0 +1,5 @@
+pub const DAY: u32 = 3;
+
+pub mod gears;
+
+pub type Puzzle = gears::Schematic;
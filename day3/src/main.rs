@@ -0,0 +1,3 @@
+fn main() {
+    aoc::solution::main::<day3::Puzzle>();
+}
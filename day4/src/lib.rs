@@ -0,0 +1,5 @@
+pub const DAY: u32 = 4;
+
+pub mod scratchcards;
+
+pub type Puzzle = scratchcards::Scratchcards;
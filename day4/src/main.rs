@@ -1,5 +1,3 @@
-mod scratchcards2;
-
 fn main() {
-    scratchcards2::main();
+    aoc::solution::main::<day4::Puzzle>();
 }
@@ -1,20 +1,15 @@
-use lazy_static::lazy_static;
-use regex::Regex;
-use std::cmp::{min};
+use std::cmp::min;
 use std::collections::HashMap;
-use std::io::{BufRead, BufReader, Write};
-
-macro_rules! dprintln {
-    ( $( $x:expr ),* ) => {
-        {
-	    #[cfg(test)]
-            println!($($x), *);
-        }
-    };
-}
+use std::fmt::Display;
+use std::io::BufRead;
+use aoc::debug;
+use aoc::intervals::{Interval, IntervalSet};
+use aoc::parse::{Line, Lines, Result};
+use aoc::solution::Solution;
+use crate::piecewise::Piecewise;
 
 #[derive(Debug)]
-struct Range {
+struct RangeMapping {
     source_start: i64,
     dest_start: i64,
     len: i64,
@@ -23,31 +18,35 @@ struct Range {
 #[derive(Debug)]
 struct Map {
     dest: String,
-    ranges: Vec<Range>,
+    ranges: Vec<RangeMapping>,
 }
 
 #[derive(Debug)]
-struct Almanac {
-    seeds: Vec<i64>,
+pub struct Almanac {
+    pub(crate) seeds: Vec<i64>,
     maps: HashMap<String, Map>,
 }
 
-impl Range {
-    fn from_string(line: &str) -> Range {
-        lazy_static! {
-            static ref RANGE_RE : Regex = Regex::new(
-                r"(?P<dest>\d+)\s+(?P<source>\d+)\s+(?P<len>\d+)"
-            ).unwrap();
-        }
-
-        let caps = RANGE_RE.captures(line).unwrap();
+impl RangeMapping {
+    fn parse(line: &Line) -> Result<RangeMapping> {
+        let mut parser = line.parser();
+        let dest_start = parser.number()?;
+        let source_start = parser.number()?;
+        let len = parser.number()?;
+        parser.end()?;
+
+        Ok(RangeMapping {
+            source_start,
+            dest_start,
+            len,
+        })
+    }
 
-        Range {
-            source_start: caps.name("source").unwrap().as_str().parse().unwrap(),
-            dest_start: caps.name("dest").unwrap().as_str().parse().unwrap(),
-            len: caps.name("len").unwrap().as_str().parse().unwrap(),
-        }
+    fn source_range(&self) -> Interval<i64> {
+        Interval::new(self.source_start, self.source_start + self.len)
     }
+
+    fn diff(&self) -> i64 { self.dest_start - self.source_start }
 }
 
 impl Map {
@@ -62,164 +61,209 @@ impl Map {
             let diff = source - range.source_start;
             return range.dest_start + diff;
         }
-        return source;
+        source
+    }
+
+    fn function(&self) -> Piecewise {
+        Piecewise::new(self.ranges.iter().map(|r| (r.source_range(), r.diff())).collect())
     }
 }
 
 impl Almanac {
 
-    fn follow_source_to_destination(&self, orig_val: i64, orig_source: &str, dest: &str) -> i64 {
+    pub(crate) fn follow_source_to_destination(&self, orig_val: i64, orig_source: &str, dest: &str) -> i64 {
         let mut val = orig_val;
         let mut source = orig_source;
         while source != dest {
-            dprintln!("source: {}, val: {}", source, val);
+            debug!("source: {}, val: {}", source, val);
             val = self.maps[source].destination_for(val);
             source = &self.maps[source].dest;
         }
-        return val;
+        val
     }
 
     fn lowest_seed_destination(&self, dest: &str) -> i64 {
-        let mut res = std::i64::MAX;
+        let mut res = i64::MAX;
         for seed in &self.seeds {
             res = min(self.follow_source_to_destination(*seed, "seed", dest), res);
         }
         res
     }
 
-    fn parse_seeds(line: &str) -> Vec<i64> {
-        let mut seeds = Vec::<i64>::new();
-        for seed in line.split_whitespace() {
-            seeds.push(seed.parse::<i64>().unwrap());
+    pub(crate) fn seeds_ranges(&self) -> Result<IntervalSet<i64>> {
+        if !self.seeds.len().is_multiple_of(2) {
+            return Err(Self::invalid("the seeds do not come in pairs of start and length"));
         }
-        return seeds;
-    }
-
-    fn build<I>(mut lines: I) -> Almanac
-        where I: Iterator<Item = String>
-    {
-        lazy_static! {
-            static ref SEEDS_RE : Regex = Regex::new(
-                r"seeds: (?P<seeds>.*)"
-            ).unwrap();
-            static ref MAP_RE : Regex = Regex::new(
-                r"(?P<source>[a-z]+)-to-(?P<dest>[a-z]+) map:"
-            ).unwrap();
+        Ok(self.seeds.chunks(2)
+            .map(|pair| Interval::new(pair[0], pair[0] + pair[1]))
+            .collect())
+    }
+
+    /// The maps from `source` to `dest` as one function.
+    pub fn composed(&self, source: &str, dest: &str) -> Piecewise {
+        let mut function = Piecewise::default();
+        let mut curr_from = source;
+
+        while curr_from != dest {
+            function = function.then(&self.maps[curr_from].function());
+            curr_from = &self.maps[curr_from].dest;
         }
+        function
+    }
 
-        let seeds;
-        {
-            let seeds_line = lines.next().unwrap();
-            let seeds_caps = SEEDS_RE.captures(&seeds_line).unwrap();
-            seeds = Self::parse_seeds(seeds_caps.name("seeds").unwrap().as_str());
+    /// Where the `source` values of `set` end up, taking them through one map
+    /// at a time.
+    pub(crate) fn image_map_by_map(&self, source: &str, dest: &str, set: IntervalSet<i64>) -> IntervalSet<i64> {
+        let mut set = set;
+        let mut curr_from = source;
+
+        while curr_from != dest {
+            set = self.maps[curr_from].function().image(&set);
+            curr_from = &self.maps[curr_from].dest;
         }
+        set
+    }
+
+    /// The function taking `dest` values back to the `source` ones.
+    pub fn inverse(&self, source: &str, dest: &str) -> Result<Piecewise> {
+        self.composed(source, dest).inverse().ok_or_else(|| Self::invalid(&format!(
+            "some {}s end up at the same {}, the maps can not be inverted", source, dest)))
+    }
+
+    /// The seed ranges whose seeds end up at a `dest` below `limit`.
+    pub fn seeds_landing_below(&self, dest: &str, limit: i64) -> Result<IntervalSet<i64>> {
+        let seeds = self.seeds_ranges()?;
+        let below = IntervalSet::from(Interval::new(i64::MIN, limit));
+        Ok(self.composed("seed", dest).preimage(&below).intersection(&seeds))
+    }
+
+    fn parse_map_header(line: &Line) -> Result<(String, String)> {
+        let mut parser = line.parser();
+        let source = parser.word()?;
+        parser.expect("-to-")?;
+        let dest = parser.word()?;
+        parser.expect("map:")?;
+        parser.end()?;
+        Ok((source.to_string(), dest.to_string()))
+    }
+}
+
+impl Solution for Almanac {
+    const DAY: u32 = crate::DAY;
+
+    fn parse<R: BufRead>(mut lines: Lines<R>) -> Result<Almanac> {
+        let seeds_line = lines.expect_line("seeds")?;
+        let mut parser = seeds_line.parser();
+        parser.expect("seeds:")?;
+        let seeds = parser.numbers()?;
 
-        // empty line
-        let _ = lines.next();
+        lines.expect_blank()?;
 
         let mut maps = HashMap::new();
 
-        while let Some(map) = lines.next() {
-            let map_caps = MAP_RE.captures(&map).unwrap();
-            let source = map_caps.name("source").unwrap().as_str();
-            let dest = map_caps.name("dest").unwrap().as_str();
+        while let Some(header) = lines.next() {
+            let header = header?;
+            if header.is_blank() {
+                continue;
+            }
+            let (source, dest) = Self::parse_map_header(&header)?;
 
             let mut ranges = Vec::new();
-            while let Some(range) = lines.next() {
-                if range.trim() == "" {
-                    break;
-                }
-                ranges.push(Range::from_string(&range));
+            for range in lines.block()? {
+                ranges.push(RangeMapping::parse(&range)?);
             }
 
             ranges.sort_by_key(|r| r.source_start);
+            if ranges.windows(2).any(|pair| pair[0].source_range().end > pair[1].source_start) {
+                return Err(lines.invalid(&format!("the {}-to-{} map has overlapping ranges", source, dest)));
+            }
             maps.insert(
-                source.to_string(),
+                source,
                 Map {
-                    dest: dest.to_string(),
-                    ranges: ranges,
+                    dest,
+                    ranges,
                 }
             );
         }
 
-        Almanac {
-            seeds: seeds,
-            maps: maps,
+        // Both parts follow the maps from seeds all the way to locations.
+        let mut source = "seed";
+        for _ in 0..=maps.len() {
+            if source == "location" {
+                return Ok(Almanac {
+                    seeds,
+                    maps,
+                });
+            }
+            match maps.get(source) {
+                Some(map) => source = &map.dest,
+                None => return Err(lines.invalid(&format!("there is no map from {} on the way to location", source))),
+            }
         }
+        Err(lines.invalid("the maps go round in a circle before reaching location"))
     }
 
-}
+    fn part1(&self) -> Result<impl Display> {
+        debug!("Almanac: {:?}", self);
+        Ok(self.lowest_seed_destination("location"))
+    }
 
-fn solve<R: BufRead, W: Write>(input: R, mut output: W) {
-    let almanac = Almanac::build(BufReader::new(input).lines().map(|l| l.unwrap()));
-    dprintln!("Almanac: {:?}", almanac);
+    fn part2(&self) -> Result<impl Display> {
+        let seeds = self.seeds_ranges()?;
+        let locations = self.composed("seed", "location").image(&seeds);
+        locations.min().ok_or_else(|| Self::invalid("there are no seeds"))
+    }
+}
 
-    writeln!(output, "{}", almanac.lowest_seed_destination("location")).unwrap();
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::Almanac;
+    use aoc::parse::Lines;
+    use aoc::solution::{solve_part1, solve_part2, Solution};
+    use aoc::testing::test_error;
 
-}
+    pub(crate) const SAMPLE: &str = include_str!("../examples/almanac.sample.in");
 
-pub fn main() {
-    let stdin = std::io::stdin();
-    let stdout = std::io::stdout();
-    solve(stdin.lock(), stdout.lock());
-}
+    include!(concat!(env!("OUT_DIR"), "/examples/almanac.rs"));
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn composed() {
+        let almanac = Almanac::parse(Lines::new(SAMPLE.as_bytes(), crate::DAY)).unwrap();
+        let to_location = almanac.composed("seed", "location");
+        let to_seed = almanac.inverse("seed", "location").unwrap();
+        for seed in 0..120 {
+            let location = almanac.follow_source_to_destination(seed, "seed", "location");
+            assert_eq!(to_location.apply(seed), location);
+            assert_eq!(to_seed.apply(location), seed);
+        }
+        assert_eq!(almanac.composed("soil", "water").apply(53), 27);
+
+        let landing: Vec<i64> = almanac.seeds_landing_below("location", 60).unwrap()
+            .intervals().iter().flat_map(|i| i.range()).collect();
+        let brute: Vec<i64> = (55..68).chain(79..93)
+            .filter(|s| almanac.follow_source_to_destination(*s, "seed", "location") < 60)
+            .collect();
+        assert_eq!(landing, brute);
+    }
 
-    fn test_exact(input: &str, output: &str) {
-        let mut actual_out: Vec<u8> = Vec::new();
-        solve(input.as_bytes(), &mut actual_out);
-        assert_eq!(String::from_utf8(actual_out).unwrap(), output);
+    #[test]
+    fn overlapping_ranges() {
+        test_error(|i, o| solve_part1::<Almanac, _, _>(i, o), "seeds: 79 14\n\nseed-to-soil map:\n50 98 2\n52 97 3\n",
+                   "day 5: the seed-to-soil map has overlapping ranges");
     }
 
-    fn test_ignore_whitespaces(input: &str, output: &str) {
-        let mut actual_out: Vec<u8> = Vec::new();
-        solve(input.as_bytes(), &mut actual_out);
-        let actual_out_str = String::from_utf8(actual_out).unwrap();
-        let actual_outs = actual_out_str.split_whitespace().collect::<Vec<&str>>();
-        let expected_outs = output.split_whitespace().collect::<Vec<&str>>();
-        assert_eq!(actual_outs, expected_outs);
+    #[test]
+    fn broken_chain() {
+        test_error(|i, o| solve_part1::<Almanac, _, _>(i, o), "seeds: 79 14\n\nseed-to-soil map:\n50 98 2\n",
+                   "day 5: there is no map from soil on the way to location");
+        test_error(|i, o| solve_part2::<Almanac, _, _>(i, o),
+                   "seeds: 79 14\n\nseed-to-soil map:\n50 98 2\n\nsoil-to-seed map:\n1 2 3\n",
+                   "day 5: the maps go round in a circle before reaching location");
     }
 
     #[test]
-    fn sample() {
-        test_ignore_whitespaces(
-            "seeds: 79 14 55 13
-
-            seed-to-soil map:
-            50 98 2
-            52 50 48
-
-            soil-to-fertilizer map:
-            0 15 37
-            37 52 2
-            39 0 15
-
-            fertilizer-to-water map:
-            49 53 8
-            0 11 42
-            42 0 7
-            57 7 4
-
-            water-to-light map:
-            88 18 7
-            18 25 70
-
-            light-to-temperature map:
-            45 77 23
-            81 45 19
-            68 64 13
-
-            temperature-to-humidity map:
-            0 69 1
-            1 0 69
-
-            humidity-to-location map:
-            60 56 37
-            56 93 4",
-            "35",
-        );
+    fn odd_seeds() {
+        test_error(|i, o| solve_part2::<Almanac, _, _>(i, o), "seeds: 79 14 55\n\nseed-to-location map:\n1 2 3\n",
+                   "day 5: the seeds do not come in pairs of start and length");
     }
 }
@@ -0,0 +1,8 @@
+pub const DAY: u32 = 5;
+
+pub mod almanac;
+pub mod almanac2;
+pub mod almanac2faster;
+pub mod piecewise;
+
+pub type Puzzle = almanac::Almanac;
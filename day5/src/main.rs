@@ -1,5 +1,3 @@
-mod almanac2faster;
-
 fn main() {
-    almanac2faster::main();
+    aoc::solution::main::<day5::Puzzle>();
 }
@@ -0,0 +1,166 @@
+//! Functions that shift each of a few ranges of values by its own constant and
+//! leave all other values as they are. One almanac map is such a function, and
+//! so is any chain of them, composed ahead of time.
+
+use aoc::intervals::{Interval, IntervalSet};
+
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Piecewise {
+    /// Sorted, disjoint, and each with a non-zero shift.
+    pieces: Vec<(Interval<i64>, i64)>,
+}
+
+impl Piecewise {
+    /// The function shifting each of `pieces` by its amount, which must not overlap.
+    pub fn new(mut pieces: Vec<(Interval<i64>, i64)>) -> Piecewise {
+        pieces.sort();
+        for pair in pieces.windows(2) {
+            assert!(pair[0].0.end <= pair[1].0.start, "Overlapping pieces: {:?}", pair);
+        }
+        let mut function = Piecewise::default();
+        for (interval, shift) in pieces {
+            function.push(interval, shift);
+        }
+        function
+    }
+
+    /// Appends a piece after all the others, merged with the last one if they
+    /// touch and shift alike.
+    fn push(&mut self, interval: Interval<i64>, shift: i64) {
+        if interval.is_empty() || shift == 0 {
+            return;
+        }
+        match self.pieces.last_mut() {
+            Some((last, last_shift)) if last.end == interval.start && *last_shift == shift => {
+                last.end = interval.end
+            },
+            _ => self.pieces.push((interval, shift)),
+        }
+    }
+
+    /// All values split into the pieces and the gaps between them, which
+    /// shift by zero.
+    fn segments(&self) -> Vec<(Interval<i64>, i64)> {
+        let mut segments = Vec::new();
+        let mut start = i64::MIN;
+        for &(interval, shift) in &self.pieces {
+            segments.push((Interval::new(start, interval.start), 0));
+            segments.push((interval, shift));
+            start = interval.end;
+        }
+        segments.push((Interval::new(start, i64::MAX), 0));
+        segments.retain(|(interval, _)| !interval.is_empty());
+        segments
+    }
+
+    pub fn apply(&self, x: i64) -> i64 {
+        let idx = self.pieces.partition_point(|(interval, _)| interval.end <= x);
+        match self.pieces.get(idx) {
+            Some((interval, shift)) if interval.contains(x) => x + shift,
+            _ => x,
+        }
+    }
+
+    /// `other` applied to the results of this one.
+    pub fn then(&self, other: &Piecewise) -> Piecewise {
+        let others = other.segments();
+        let mut composed = Piecewise::default();
+        for (interval, shift) in self.segments() {
+            let image = interval.shifted(shift);
+            let first = others.partition_point(|(o, _)| o.end <= image.start);
+            for (other_interval, other_shift) in &others[first..] {
+                if other_interval.start >= image.end {
+                    break;
+                }
+                let part = other_interval.intersection(&image);
+                composed.push(part.shifted(-shift), shift + other_shift);
+            }
+        }
+        composed
+    }
+
+    /// The function taking every result back to its argument, if no two
+    /// arguments give the same result.
+    pub fn inverse(&self) -> Option<Piecewise> {
+        let mut images: Vec<_> = self.segments().into_iter()
+            .map(|(interval, shift)| (interval.shifted(shift), -shift))
+            .collect();
+        images.sort();
+        if images.windows(2).any(|pair| pair[0].0.end > pair[1].0.start) {
+            return None;
+        }
+        Some(Piecewise::new(images))
+    }
+
+    /// Where the values of `set` end up.
+    pub fn image(&self, set: &IntervalSet<i64>) -> IntervalSet<i64> {
+        self.segments().into_iter()
+            .flat_map(|(interval, shift)| {
+                set.intersection(&interval.into()).intervals().iter()
+                    .map(|i| i.shifted(shift))
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    /// The values ending up in `set`.
+    pub fn preimage(&self, set: &IntervalSet<i64>) -> IntervalSet<i64> {
+        self.segments().into_iter()
+            .flat_map(|(interval, shift)| {
+                set.intersection(&interval.shifted(shift).into()).intervals().iter()
+                    .map(|i| i.shifted(-shift))
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn function(pieces: &[(i64, i64, i64)]) -> Piecewise {
+        Piecewise::new(pieces.iter().map(|&(start, end, shift)| (Interval::new(start, end), shift)).collect())
+    }
+
+    fn values(set: &IntervalSet<i64>) -> Vec<i64> {
+        set.intervals().iter().flat_map(|i| i.range()).collect()
+    }
+
+    #[test]
+    fn compose() {
+        let f = function(&[(0, 10, 5), (10, 15, -10), (20, 22, 1)]);
+        let g = function(&[(3, 8, 100), (12, 14, -2), (21, 23, -1)]);
+        let composed = f.then(&g);
+        for x in -5..30 {
+            assert_eq!(composed.apply(x), g.apply(f.apply(x)), "at {}", x);
+        }
+        assert_eq!(f.then(&Piecewise::default()), f);
+        assert_eq!(function(&[(0, 5, 1), (5, 10, 1)]), function(&[(0, 10, 1)]));
+    }
+
+    #[test]
+    fn invert() {
+        let f = function(&[(0, 10, 5), (10, 15, -10)]);
+        let inverse = f.inverse().unwrap();
+        for x in -5..30 {
+            assert_eq!(inverse.apply(f.apply(x)), x, "at {}", x);
+        }
+        assert_eq!(function(&[(0, 10, 1)]).inverse(), None);
+    }
+
+    #[test]
+    fn images() {
+        let f = function(&[(0, 10, 5), (20, 22, -20)]);
+        let set: IntervalSet<i64> = [Interval::new(8, 21)].into_iter().collect();
+        let mut image: Vec<_> = values(&set).into_iter().map(|x| f.apply(x)).collect();
+        image.sort();
+        image.dedup();
+        assert_eq!(values(&f.image(&set)), image);
+
+        let below: IntervalSet<i64> = Interval::new(i64::MIN, 3).into();
+        let preimage: Vec<_> = (-5..30).filter(|x| f.apply(*x) < 3).collect();
+        let found = f.preimage(&below).intersection(&Interval::new(-5, 30).into());
+        assert_eq!(values(&found), preimage);
+    }
+}
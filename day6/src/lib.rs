@@ -0,0 +1,5 @@
+pub const DAY: u32 = 6;
+
+pub mod races;
+
+pub type Puzzle = races::Races;
@@ -1,5 +1,3 @@
-mod races2;
-
 fn main() {
-    races2::main();
+    aoc::solution::main::<day6::Puzzle>();
 }
@@ -1,14 +1,8 @@
-use std::cmp::{max, min};
-use std::io::{BufRead, BufReader, Write};
-
-macro_rules! dprintln {
-    ( $( $x:expr ),* ) => {
-        {
-	    #[cfg(test)]
-            println!($($x), *);
-        }
-    };
-}
+use std::fmt::Display;
+use std::io::BufRead;
+use aoc::debug;
+use aoc::parse::{Line, Lines, Result};
+use aoc::solution::Solution;
 
 #[derive(Debug)]
 struct Race {
@@ -17,13 +11,6 @@ struct Race {
 }
 
 impl Race {
-    fn from_time(time: i64) -> Race {
-        Race {
-            time: time,
-            distance: -1,
-        }
-    }
-
     fn win_possibilities(&self) -> i64 {
         let mut p = 0;
 
@@ -42,67 +29,81 @@ impl Race {
     }
 }
 
-fn solve<R: BufRead, W: Write>(mut input: R, mut output: W) {
-    let mut solution: i64 = 0;
-
-    let mut lines_iter = BufReader::new(input).lines();
+/// Reads the numbers after `label` as one, ignoring the spaces between them.
+fn concat_to_number(line: &Line, label: &str) -> Result<i64> {
+    let mut parser = line.parser();
+    parser.expect(label)?;
+    let mut digits = String::new();
+    while !parser.is_done() {
+        let error = parser.error("digits");
+        let part = parser.token()?;
+        if !part.chars().all(|c| c.is_ascii_digit()) {
+            return Err(error);
+        }
+        digits.push_str(part);
+    }
+    digits.parse().map_err(|_| parser.error("a number"))
+}
 
-    let mut races = Vec::new();
+#[derive(Debug)]
+pub struct Races {
+    races: Vec<Race>,
+    /// The single race of part 2, read with bad kerning.
+    kerned: Race,
+}
 
-    let times_line = lines_iter.next().unwrap().unwrap();
-    for time in times_line.split_whitespace().skip(1) {
-        races.push(Race::from_time(time.parse().unwrap()));
+impl Solution for Races {
+    const DAY: u32 = crate::DAY;
+
+    fn parse<R: BufRead>(mut lines: Lines<R>) -> Result<Races> {
+        let times_line = lines.expect_line("times")?;
+        let mut parser = times_line.parser();
+        parser.expect("Time:")?;
+        let times = parser.numbers()?;
+
+        let distance_line = lines.expect_line("distances")?;
+        let mut parser = distance_line.parser();
+        parser.expect("Distance:")?;
+        let distances = parser.numbers()?;
+        if distances.len() != times.len() {
+            return Err(distance_line.error(
+                1, &format!("{} distances", times.len()), &format!("{}", distances.len())));
+        }
+        let races = times.into_iter().zip(distances)
+            .map(|(time, distance)| Race { time, distance })
+            .collect();
+
+        let kerned = Race {
+            time: concat_to_number(&times_line, "Time:")?,
+            distance: concat_to_number(&distance_line, "Distance:")?,
+        };
+
+        let races = Races { races, kerned };
+        debug!("Races: {:?}", races);
+        Ok(races)
     }
 
-    let distance_line = lines_iter.next().unwrap().unwrap();
-    for (i, distance) in distance_line.split_whitespace().skip(1).enumerate() {
-        races[i].distance = distance.parse().unwrap();
+    fn part1(&self) -> Result<impl Display> {
+        Ok(self.races.iter().map(|r| r.win_possibilities()).reduce(|a, b| a * b).unwrap())
     }
 
-    dprintln!("Races: {:?}", races);
-
-    solution = races.iter().map(|r| r.win_possibilities()).reduce(|a, b| a * b).unwrap();
-
-    writeln!(output, "{}", solution).unwrap();
-}
-
-pub fn main() {
-    let stdin = std::io::stdin();
-    let stdout = std::io::stdout();
-    solve(stdin.lock(), stdout.lock());
+    fn part2(&self) -> Result<impl Display> {
+        Ok(self.kerned.win_possibilities())
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::*;
-
-    fn test_exact(input: &str, output: &str) {
-        let mut actual_out: Vec<u8> = Vec::new();
-        solve(input.as_bytes(), &mut actual_out);
-        assert_eq!(String::from_utf8(actual_out).unwrap(), output);
-    }
+    use super::Races;
+    use aoc::solution::solve_part1;
+    use aoc::testing::test_ignore_whitespaces;
 
-    fn test_ignore_whitespaces(input: &str, output: &str) {
-        let mut actual_out: Vec<u8> = Vec::new();
-        solve(input.as_bytes(), &mut actual_out);
-        let actual_out_str = String::from_utf8(actual_out).unwrap();
-        let actual_outs = actual_out_str.split_whitespace().collect::<Vec<&str>>();
-        let expected_outs = output.split_whitespace().collect::<Vec<&str>>();
-        assert_eq!(actual_outs, expected_outs);
-    }
-
-    #[test]
-    fn sample() {
-        test_ignore_whitespaces(
-            "Time:      7  15   30
-            Distance:  9  40  200",
-            "288",
-        );
-    }
+    include!(concat!(env!("OUT_DIR"), "/examples/races.rs"));
 
     #[test]
     fn shorter() {
         test_ignore_whitespaces(
+            |i, o| solve_part1::<Races, _, _>(i, o),
             "Time:     15   30
             Distance: 40  200",
             "72",
@@ -1,16 +1,11 @@
 //use std::cmp::{max, min};
-use std::io::{BufRead, BufReader, Write};
 use std::collections::{HashMap};
 use std::cmp::Ordering;
-
-macro_rules! dprintln {
-    ( $( $x:expr ),* ) => {
-        {
-	    #[cfg(test)]
-            println!($($x), *);
-        }
-    };
-}
+use std::fmt::Display;
+use std::io::BufRead;
+use aoc::debug;
+use aoc::parse::{Line, Lines, Result};
+use aoc::solution::Solution;
 
 #[derive(Debug, Hash, PartialEq, Eq, Copy, Clone)]
 enum Card {
@@ -34,24 +29,15 @@ enum HandType {
 }
 
 impl Card {
-    fn from_char(s: char) -> Card {
-        match s.to_string().parse::<i32>() {
-            Ok(int) => {
-                if int > 9 || int < 2 {
-                    panic!("Got incorrect card: {}", int)
-                }
-                Card::Num(int)
-            },
-            Err(_) => {
-                match s {
-                    'A' => Card::A,
-                    'K' => Card::K,
-                    'Q' => Card::Q,
-                    'J' => Card::J,
-                    'T' => Card::T,
-                    _ => panic!("Got incorrect card: {}", s)
-                }
-            }
+    fn from_char(s: char) -> Option<Card> {
+        match s {
+            'A' => Some(Card::A),
+            'K' => Some(Card::K),
+            'Q' => Some(Card::Q),
+            'J' => Some(Card::J),
+            'T' => Some(Card::T),
+            '2'..='9' => Some(Card::Num(s.to_digit(10).unwrap() as i32)),
+            _ => None,
         }
     }
 }
@@ -63,17 +49,19 @@ struct Hand {
 }
 
 impl Hand {
-    fn from_string(line: &str) -> Hand {
-        let els: Vec<&str> = line.split_whitespace().collect();
+    fn parse(line: &Line) -> Result<Hand> {
+        let mut parser = line.parser();
         let mut cards = vec![];
-        for c in els[0].chars() {
-            cards.push(Card::from_char(c));
+        for _ in 0..5 {
+            cards.push(parser.char_as("a card", Card::from_char)?);
         }
+        let bid = parser.number()?;
+        parser.end()?;
 
-        Hand {
-            cards: cards,
-            bid: els[1].parse().unwrap(),
-        }
+        Ok(Hand {
+            cards,
+            bid,
+        })
     }
 
     fn counted_cards(&self) -> HashMap<Card, usize> {
@@ -84,7 +72,7 @@ impl Hand {
         counts
     }
 
-    fn get_type(&self) -> HandType {
+    fn get_simple_type(&self) -> HandType {
         let counts = self.counted_cards();
         let mut nums = counts.values().copied().collect::<Vec<usize>>();
         nums.sort();
@@ -111,21 +99,53 @@ impl Hand {
             return HandType::OnePair;
         }
 
-        return HandType::HighCard;
+        HandType::HighCard
+    }
+
+    /// The type of the hand, with `J`s as jokers if `jokers` is set.
+    fn get_type(&self, jokers: bool) -> HandType {
+        let simple_type = self.get_simple_type();
+        if !jokers {
+            return simple_type;
+        }
+
+        let counts = self.counted_cards();
+        if let Some(count) = counts.get(&Card::J) {
+            return match simple_type {
+                HandType::FiveOfKind => HandType::FiveOfKind,
+                HandType::FourOfKind => HandType::FiveOfKind,
+                HandType::FullHouse => HandType::FiveOfKind,
+                HandType::ThreeOfKind => HandType::FourOfKind,
+                HandType::TwoPairs => {
+                    if *count == 2 {
+                        HandType::FourOfKind
+                    } else {
+                        HandType::FullHouse
+                    }
+                },
+                HandType::OnePair => HandType::ThreeOfKind,
+                HandType::HighCard => HandType::OnePair,
+            }
+        }
+
+        simple_type
     }
 }
 
 #[derive(Debug)]
 struct HandComparator {
+    jokers: bool,
     card_order: HashMap<Card, usize>,
     type_order: HashMap<HandType, usize>,
 }
 
 impl HandComparator {
-    fn build() -> HandComparator {
+    /// With `jokers`, `J`s are the weakest cards but count as any other for
+    /// the type of the hand.
+    fn build(jokers: bool) -> HandComparator {
         let mut card_order = HashMap::<Card, usize>::from([
             (Card::T, 10),
-            (Card::J, 11),
+            (Card::J, if jokers { 1 } else { 11 }),
             (Card::Q, 12),
             (Card::K, 13),
             (Card::A, 14),
@@ -145,14 +165,15 @@ impl HandComparator {
         ]);
 
         HandComparator {
-            card_order: card_order,
-            type_order: type_order,
+            jokers,
+            card_order,
+            type_order,
         }
     }
 
     fn cmp(&self, a: &Hand, b: &Hand) -> Ordering {
-        let type_a = a.get_type();
-        let type_b = b.get_type();
+        let type_a = a.get_type(self.jokers);
+        let type_b = b.get_type(self.jokers);
 
         if type_a != type_b {
             return self.type_order[&type_a].cmp(&self.type_order[&type_b]);
@@ -167,79 +188,101 @@ impl HandComparator {
             return self.card_order[&card_a].cmp(&self.card_order[&card_b]);
         }
 
-        return Ordering::Equal;
+        Ordering::Equal
     }
 }
 
-fn solve<R: BufRead, W: Write>(input: R, mut output: W) {
-    let mut solution: i64 = 0;
+pub struct Hands {
+    hands: Vec<Hand>,
+}
+
+impl Hands {
+    fn winnings(&self, jokers: bool) -> i64 {
+        let comp = HandComparator::build(jokers);
+        let mut hands: Vec<&Hand> = self.hands.iter().collect();
+        hands.sort_by(|a, b| comp.cmp(a, b));
 
-    let comp = HandComparator::build();
-    let mut hands = vec![];
+        debug!("hands: {:?}", hands);
 
-    for line_res in BufReader::new(input).lines() {
-        let line = line_res.unwrap();
-        hands.push(Hand::from_string(&line));
+        let mut solution: i64 = 0;
+        for (i, hand) in hands.iter().enumerate() {
+            solution += (i+1) as i64 * hand.bid;
+        }
+        solution
     }
-    hands.sort_by(|a, b| comp.cmp(a, b));
+}
 
-    dprintln!("hands: {:?}", hands);
+impl Solution for Hands {
+    const DAY: u32 = crate::DAY;
 
-    for (i, hand) in hands.iter().enumerate() {
-        solution += (i+1) as i64 * hand.bid;
+    fn parse<R: BufRead>(lines: Lines<R>) -> Result<Hands> {
+        let mut hands = vec![];
+        for line in lines {
+            hands.push(Hand::parse(&line?)?);
+        }
+        Ok(Hands { hands })
     }
 
-    writeln!(output, "{}", solution).unwrap();
-}
+    fn part1(&self) -> Result<impl Display> {
+        Ok(self.winnings(false))
+    }
 
-pub fn main() {
-    let stdin = std::io::stdin();
-    let stdout = std::io::stdout();
-    solve(stdin.lock(), stdout.lock());
+    fn part2(&self) -> Result<impl Display> {
+        Ok(self.winnings(true))
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use super::Hands;
+    use aoc::solution::{solve_part1, solve_part2};
 
-    fn test_exact(input: &str, output: &str) {
-        let mut actual_out: Vec<u8> = Vec::new();
-        solve(input.as_bytes(), &mut actual_out);
-        assert_eq!(String::from_utf8(actual_out).unwrap(), output);
+    fn test_part1(input: &str, output: &str) {
+        aoc::testing::test_ignore_whitespaces(|i, o| solve_part1::<Hands, _, _>(i, o), input, output);
     }
 
-    fn test_ignore_whitespaces(input: &str, output: &str) {
-        let mut actual_out: Vec<u8> = Vec::new();
-        solve(input.as_bytes(), &mut actual_out);
-        let actual_out_str = String::from_utf8(actual_out).unwrap();
-        let actual_outs = actual_out_str.split_whitespace().collect::<Vec<&str>>();
-        let expected_outs = output.split_whitespace().collect::<Vec<&str>>();
-        assert_eq!(actual_outs, expected_outs);
+    fn test_part2(input: &str, output: &str) {
+        aoc::testing::test_ignore_whitespaces(|i, o| solve_part2::<Hands, _, _>(i, o), input, output);
     }
 
-    #[test]
-    fn sample() {
-        test_ignore_whitespaces(
-            "32T3K 765
-            T55J5 684
-            KK677 28
-            KTJJT 220
-            QQQJA 483",
-            "6440",
-        );
+    fn test_error(input: &str, error: &str) {
+        aoc::testing::test_error(|i, o| solve_part1::<Hands, _, _>(i, o), input, error);
     }
 
+    include!(concat!(env!("OUT_DIR"), "/examples/camel_cards.rs"));
+
     #[test]
     fn test_type_order() {
-        test_ignore_whitespaces(
-            "AAAAA 100000
+        let hands = "AAAAA 100000
             AA8AA 10000
             23332 1000
             TTT98 100
             23432 10
             A23A4 1
-            23456 0",
-            "765432",
+            23456 0";
+        test_part1(hands, "765432");
+        test_part2(hands, "765432");
+    }
+
+    #[test]
+    fn joker_tests() {
+        test_part2(
+            "2J222 10
+            22J22 1",
+            "12",
+        );
+    }
+
+    #[test]
+    fn malformed() {
+        test_error(
+            "32T3K 765
+            T55X5 684",
+            "day 7, line 2, column 16: expected a card, found `X5`",
+        );
+        test_error(
+            "32T3K",
+            "day 7, line 1, column 6: expected a number, found end of line",
         );
     }
 }
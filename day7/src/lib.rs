@@ -0,0 +1,5 @@
+pub const DAY: u32 = 7;
+
+pub mod camel_cards;
+
+pub type Puzzle = camel_cards::Hands;
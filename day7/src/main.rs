@@ -1,5 +1,3 @@
-mod camel_cards2;
-
 fn main() {
-    camel_cards2::main();
+    aoc::solution::main::<day7::Puzzle>();
 }
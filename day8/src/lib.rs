@@ -0,0 +1,5 @@
+pub const DAY: u32 = 8;
+
+pub mod wasteland;
+
+pub type Puzzle = wasteland::Network;
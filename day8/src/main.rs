@@ -1,6 +1,3 @@
-mod wasteland2;
-mod biblioteczka;
-
 fn main() {
-    wasteland2::main();
+    aoc::solution::main::<day8::Puzzle>();
 }
@@ -1,17 +1,12 @@
 //use std::cmp::{max, min};
-use std::io::{BufRead, BufReader, Write};
-use std::collections::HashMap;
-use regex::Regex;
-use lazy_static::lazy_static;
-
-macro_rules! dprintln {
-    ( $( $x:expr ),* ) => {
-        {
-	    #[cfg(test)]
-            println!($($x), *);
-        }
-    };
-}
+use std::fmt::Display;
+use std::io::BufRead;
+use std::collections::{HashMap, HashSet};
+
+use aoc::biblioteczka::crt;
+use aoc::debug;
+use aoc::parse::{Line, Lines, Result};
+use aoc::solution::Solution;
 
 #[derive(Debug)]
 struct Node {
@@ -20,52 +15,108 @@ struct Node {
     right: String,
 }
 
+/// Steps at which a ghost stands on a node ending with Z.
+///
+/// The ones in `prefix_hits` happen once, the ones in `cycle_hits` repeat
+/// every `cycle_len` steps. The cycle starts at step `cycle_start`.
+#[derive(Debug, PartialEq)]
+struct Ghost {
+    prefix_hits: Vec<i64>,
+    cycle_start: i64,
+    cycle_len: i64,
+    cycle_hits: Vec<i64>,
+}
+
+impl Ghost {
+    fn is_on_z(&self, step: i64) -> bool {
+        if step < self.cycle_start {
+            return self.prefix_hits.contains(&step);
+        }
+        let offset = (step - self.cycle_start) % self.cycle_len;
+        self.cycle_hits.contains(&(self.cycle_start + offset))
+    }
+
+    /// All steps before `end` at which the ghost is on Z, in order.
+    fn hits_before(&self, end: i64) -> Vec<i64> {
+        let mut hits: Vec<i64> = self.prefix_hits.iter().copied().filter(|h| *h < end).collect();
+        let mut cycle_offset = 0;
+        while self.cycle_start + cycle_offset < end {
+            hits.extend(self.cycle_hits.iter().map(|h| h + cycle_offset).filter(|h| *h < end));
+            cycle_offset += self.cycle_len;
+        }
+        hits
+    }
+}
+
 #[derive(Debug)]
 struct Tree {
     nodes: HashMap<String, Node>,
+    starting_nodes: Vec<String>,
 }
 
 impl Node {
-    fn from_string(line: &str) -> Node {
-        lazy_static! {
-            static ref NODE_RE : Regex = Regex::new(
-                r"(?P<name>...) = \((?P<left>...), (?P<right>...)\)"
-            ).unwrap();
-        }
+    fn parse(line: &Line) -> Result<Node> {
+        let mut parser = line.parser();
+        let name = parser.word()?.to_string();
+        parser.expect("=")?;
+        parser.expect("(")?;
+        let left = parser.word()?.to_string();
+        parser.expect(",")?;
+        let right = parser.word()?.to_string();
+        parser.expect(")")?;
+        parser.end()?;
 
-        let caps = NODE_RE.captures(line).unwrap();
-
-        Node {
-            name: caps.name("name").unwrap().as_str().to_string(),
-            left: caps.name("left").unwrap().as_str().to_string(),
-            right: caps.name("right").unwrap().as_str().to_string(),
-
-        }
+        Ok(Node {
+            name,
+            left,
+            right,
+        })
     }
 }
 
 impl Tree {
-    fn from_lines<I>(lines: I) -> Tree
-        where I: Iterator<Item = String>
-    {
+    fn parse<R: BufRead>(lines: &mut Lines<R>) -> Result<Tree> {
         let mut nodes = HashMap::new();
+        let mut starting_nodes = vec![];
+
+        let mut order = vec![];
 
-        for line in lines {
-            let node = Node::from_string(&line);
-            nodes.insert((&node.name).to_string(), node);
+        for line in lines.by_ref() {
+            let node = Node::parse(&line?)?;
+            if node.name.ends_with("A") {
+                starting_nodes.push(node.name.to_string());
+            }
+            order.push(node.name.to_string());
+            nodes.insert(node.name.to_string(), node);
         }
 
-        Tree {
-            nodes: nodes,
+        for name in &order {
+            let node = &nodes[name];
+            for next in [&node.left, &node.right] {
+                if !nodes.contains_key(next) {
+                    return Err(lines.invalid(&format!("there is no node {}", next)));
+                }
+            }
         }
+
+        Ok(Tree {
+            nodes,
+            starting_nodes,
+        })
     }
 
-    fn traverse_with_directions(&self, start: &str, dest: &str, dirs: &[Direction]) -> usize {
+    /// Steps from `start` to `dest`, if it gets there before the (node,
+    /// instruction index) state repeats.
+    fn traverse_with_directions(&self, start: &str, dest: &str, dirs: &[Direction]) -> Option<usize> {
+        let mut seen: HashSet<(&str, usize)> = HashSet::new();
         let mut curr = start;
         let mut steps = 0;
 
         while curr != dest {
             let idx = steps % dirs.len();
+            if !seen.insert((curr, idx)) {
+                return None;
+            }
             curr = match dirs[idx] {
                 Direction::Left => &self.nodes[curr].left,
                 Direction::Right => &self.nodes[curr].right,
@@ -73,7 +124,80 @@ impl Tree {
             steps += 1;
         }
 
-        return steps;
+        Some(steps)
+    }
+
+    /// Walks from `start` until the (node, instruction index) state repeats.
+    fn ghost(&self, start: &str, dirs: &[Direction]) -> Ghost {
+        let mut seen: HashMap<(&str, usize), i64> = HashMap::new();
+        let mut hits = Vec::new();
+        let mut curr = start;
+        let mut steps = 0;
+
+        loop {
+            let idx = steps as usize % dirs.len();
+            if let Some(&cycle_start) = seen.get(&(curr, idx)) {
+                let (prefix_hits, cycle_hits) = hits.iter().partition(|h| **h < cycle_start);
+                return Ghost {
+                    prefix_hits,
+                    cycle_start,
+                    cycle_len: steps - cycle_start,
+                    cycle_hits,
+                };
+            }
+            seen.insert((curr, idx), steps);
+
+            if curr.ends_with('Z') {
+                hits.push(steps);
+            }
+            curr = match dirs[idx] {
+                Direction::Left => &self.nodes[curr].left,
+                Direction::Right => &self.nodes[curr].right,
+            };
+            steps += 1;
+        }
+    }
+
+    /// First step at which all ghosts are on Z at once, if there is one.
+    fn ghosthly_traverse(&self, dirs: &[Direction]) -> Option<i64> {
+        let ghosts: Vec<Ghost> = self.starting_nodes.iter()
+            .map(|n| self.ghost(n, dirs))
+            .collect();
+        debug!("ghosts: {:?}", ghosts);
+
+        // Before every ghost is in its cycle, just check the steps one of them
+        // is on Z.
+        let all_cycling = ghosts.iter().map(|g| g.cycle_start).max()?;
+        for step in ghosts[0].hits_before(all_cycling) {
+            if ghosts.iter().all(|g| g.is_on_z(step)) {
+                return Some(step);
+            }
+        }
+
+        // Afterwards, each combination of hits in the cycles is a system of
+        // congruences. Keep the solutions of the ghosts combined so far.
+        let mut combined = vec![(0, 1)];
+        for ghost in &ghosts {
+            let mut next = Vec::new();
+            for &(residue, modulus) in &combined {
+                for hit in &ghost.cycle_hits {
+                    if let Some(solution) = crt(&[(residue, modulus), (*hit, ghost.cycle_len)]) {
+                        if !next.contains(&solution) {
+                            next.push(solution);
+                        }
+                    }
+                }
+            }
+            combined = next;
+        }
+
+        combined.iter().map(|&(residue, modulus)| {
+            if residue >= all_cycling {
+                residue
+            } else {
+                residue + (all_cycling - residue + modulus - 1) / modulus * modulus
+            }
+        }).min()
     }
 }
 
@@ -84,81 +208,212 @@ enum Direction {
 }
 
 impl Direction {
-    fn from_string(line: &str) -> Vec<Direction> {
-        let mut dirs = vec![];
-
-        for c in line.chars() {
-            if c == 'L' {
-                dirs.push(Direction::Left)
-            }
-            if c == 'R' {
-                dirs.push(Direction::Right)
-            }
+    fn parse(line: &Line) -> Result<Vec<Direction>> {
+        let dirs = line.row(|c| match c {
+            'L' => Some(Direction::Left),
+            'R' => Some(Direction::Right),
+            _ => None,
+        }, "`L` or `R`")?;
+        if dirs.is_empty() {
+            return Err(line.parser().error("`L` or `R`"));
         }
-
-        dirs
+        Ok(dirs)
     }
 }
 
-fn solve<R: BufRead, W: Write>(input: R, mut output: W) {
-    let mut lines = BufReader::new(input).lines();
+pub struct Network {
+    dirs: Vec<Direction>,
+    tree: Tree,
+}
 
-    let dirs = Direction::from_string(&lines.next().unwrap().unwrap());
-    dprintln!("dirs: {:?}", dirs);
+impl Solution for Network {
+    const DAY: u32 = crate::DAY;
 
-    // empty line
-    let _ = lines.next();
+    fn parse<R: BufRead>(mut lines: Lines<R>) -> Result<Network> {
+        let dirs = Direction::parse(&lines.expect_line("directions")?)?;
+        debug!("dirs: {:?}", dirs);
 
-    let tree = Tree::from_lines(lines.map(|l| l.unwrap()));
-    dprintln!("tree: {:?}", tree);
+        lines.expect_blank()?;
 
-    writeln!(output, "{}", tree.traverse_with_directions("AAA", "ZZZ", &dirs)).unwrap();
-}
+        let tree = Tree::parse(&mut lines)?;
+        debug!("tree: {:?}", tree);
+        Ok(Network { dirs, tree })
+    }
 
-pub fn main() {
-    let stdin = std::io::stdin();
-    let stdout = std::io::stdout();
-    solve(stdin.lock(), stdout.lock());
+    fn part1(&self) -> Result<impl Display> {
+        for name in ["AAA", "ZZZ"] {
+            if !self.tree.nodes.contains_key(name) {
+                return Err(Self::invalid(&format!("there is no node {}", name)));
+            }
+        }
+        self.tree.traverse_with_directions("AAA", "ZZZ", &self.dirs)
+            .ok_or_else(|| Self::invalid("ZZZ can not be reached from AAA"))
+    }
+
+    fn part2(&self) -> Result<impl Display> {
+        if self.tree.starting_nodes.is_empty() {
+            return Err(Self::invalid("there is no node ending with A"));
+        }
+        self.tree.ghosthly_traverse(&self.dirs)
+            .ok_or_else(|| Self::invalid("the ghosts are never all on Z at once"))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc::solution::{solve_part1, solve_part2};
 
-    fn test_ignore_whitespaces(input: &str, output: &str) {
-        let mut actual_out: Vec<u8> = Vec::new();
-        solve(input.as_bytes(), &mut actual_out);
-        let actual_out_str = String::from_utf8(actual_out).unwrap();
-        let actual_outs = actual_out_str.split_whitespace().collect::<Vec<&str>>();
-        let expected_outs = output.split_whitespace().collect::<Vec<&str>>();
-        assert_eq!(actual_outs, expected_outs);
+    fn test_part1(input: &str, output: &str) {
+        aoc::testing::test_ignore_whitespaces(|i, o| solve_part1::<Network, _, _>(i, o), input, output);
     }
 
-    #[test]
-    fn sample() {
-        test_ignore_whitespaces(
-            "RL
-
-            AAA = (BBB, CCC)
-            BBB = (DDD, EEE)
-            CCC = (ZZZ, GGG)
-            DDD = (DDD, DDD)
-            EEE = (EEE, EEE)
-            GGG = (GGG, GGG)
-            ZZZ = (ZZZ, ZZZ)",
-            "2",
-        );
+    fn test_part2(input: &str, output: &str) {
+        aoc::testing::test_ignore_whitespaces(|i, o| solve_part2::<Network, _, _>(i, o), input, output);
     }
 
+    include!(concat!(env!("OUT_DIR"), "/examples/wasteland.rs"));
+
     #[test]
     fn another() {
-        test_ignore_whitespaces(
-            "LLR
+        let input = "LLR
 
             AAA = (BBB, BBB)
             BBB = (AAA, ZZZ)
-            ZZZ = (ZZZ, ZZZ)",
-            "6",
+            ZZZ = (ZZZ, ZZZ)";
+        test_part1(input, "6");
+        test_part2(input, "6");
+    }
+
+    #[test]
+    fn hit_after_prefix() {
+        // 11Z is visited every 3 steps from step 2, 22Z at every odd step,
+        // so the first hits 2 and 1 don't combine with lcm.
+        test_part2(
+            "L
+
+            11A = (11B, 11B)
+            11B = (11Z, 11Z)
+            11Z = (11C, 11C)
+            11C = (11B, 11B)
+            22A = (22Z, 22Z)
+            22Z = (22A, 22A)",
+            "5",
+        );
+    }
+
+    #[test]
+    fn hit_only_in_prefix() {
+        test_part2(
+            "L
+
+            11A = (11Z, 11Z)
+            11Z = (11B, 11B)
+            11B = (11B, 11B)
+            22A = (22Z, 22Z)
+            22Z = (22A, 22A)",
+            "1",
+        );
+    }
+
+    #[test]
+    fn many_hits_in_cycle() {
+        // 11Z and 12Z are hit at steps 3, 5, 8, 10, 13, ..., 22Z at steps
+        // 2, 6, 10, ...
+        test_part2(
+            "L
+
+            11A = (11B, 11B)
+            11B = (11C, 11C)
+            11C = (11Z, 11Z)
+            11Z = (11D, 11D)
+            11D = (12Z, 12Z)
+            12Z = (11E, 11E)
+            11E = (11F, 11F)
+            11F = (11Z, 11Z)
+            22A = (22B, 22B)
+            22B = (22Z, 22Z)
+            22Z = (22C, 22C)
+            22C = (22D, 22D)
+            22D = (22B, 22B)",
+            "10",
+        );
+    }
+
+    #[test]
+    fn never_together() {
+        aoc::testing::test_error(
+            |i, o| solve_part2::<Network, _, _>(i, o),
+            "L
+
+            11A = (11B, 11B)
+            11B = (11Z, 11Z)
+            11Z = (11B, 11B)
+            22A = (22Z, 22Z)
+            22Z = (22A, 22A)",
+            "day 8: the ghosts are never all on Z at once",
         );
+        aoc::testing::test_error(
+            |i, o| solve_part2::<Network, _, _>(i, o),
+            "L
+
+            11B = (11Z, 11Z)
+            11Z = (11B, 11B)",
+            "day 8: there is no node ending with A",
+        );
+    }
+
+    #[test]
+    fn unreachable() {
+        aoc::testing::test_error(
+            |i, o| solve_part1::<Network, _, _>(i, o),
+            "LR
+
+            AAA = (BBB, AAA)
+            BBB = (BBB, AAA)
+            ZZZ = (AAA, AAA)",
+            "day 8: ZZZ can not be reached from AAA",
+        );
+    }
+
+    #[test]
+    fn missing_node() {
+        aoc::testing::test_error(
+            |i, o| solve_part1::<Network, _, _>(i, o),
+            "L
+
+            AAA = (BBB, BBB)
+            BBB = (AAA, AAA)",
+            "day 8: there is no node ZZZ",
+        );
+    }
+
+    #[test]
+    fn undefined_node() {
+        let input = "L
+
+            AAA = (BBB, BBB)
+            BBB = (ZZZ, CCC)
+            ZZZ = (ZZZ, ZZZ)";
+        aoc::testing::test_error(|i, o| solve_part1::<Network, _, _>(i, o), input, "day 8: there is no node CCC");
+        aoc::testing::test_error(|i, o| solve_part2::<Network, _, _>(i, o), input, "day 8: there is no node CCC");
+    }
+
+    #[test]
+    fn ghost_cycle() {
+        let tree = Tree::parse(&mut Lines::new(
+            "11A = (11B, XXX)
+            11B = (XXX, 11Z)
+            11Z = (11B, XXX)
+            XXX = (XXX, XXX)".as_bytes(),
+            crate::DAY,
+        )).unwrap();
+        let dirs = Direction::parse(&Lines::new("LR".as_bytes(), crate::DAY).expect_line("").unwrap()).unwrap();
+        assert_eq!(tree.ghost("11A", &dirs), Ghost {
+            prefix_hits: vec![],
+            cycle_start: 1,
+            cycle_len: 2,
+            cycle_hits: vec![2],
+        });
     }
 }
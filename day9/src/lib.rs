@@ -0,0 +1,5 @@
+pub const DAY: u32 = 9;
+
+pub mod oasis;
+
+pub type Puzzle = oasis::Report;
@@ -1,6 +1,3 @@
-mod oasis2;
-
-
 fn main() {
-    oasis2::main();
+    aoc::solution::main::<day9::Puzzle>();
 }
@@ -1,14 +1,9 @@
 //use std::cmp::{max, min};
-use std::io::{BufRead, BufReader, Write};
-
-macro_rules! dprintln {
-    ( $( $x:expr ),* ) => {
-        {
-	    #[cfg(test)]
-            println!($($x), *);
-        }
-    };
-}
+use std::fmt::Display;
+use std::io::BufRead;
+use aoc::debug;
+use aoc::parse::{Line, Lines, Result};
+use aoc::solution::Solution;
 
 #[derive(Debug)]
 struct History {
@@ -17,12 +12,16 @@ struct History {
 
 impl History {
 
-    fn from_string(line: &str) -> History {
-        History {
-            sequences: vec![
-                line.split_whitespace().map(|x| x.parse().unwrap()).collect(),
-            ],
+    fn parse(line: &Line) -> Result<History> {
+        let mut parser = line.parser();
+        let values = parser.numbers()?;
+        if values.is_empty() {
+            return Err(parser.error("a number"));
         }
+
+        Ok(History {
+            sequences: vec![values],
+        })
     }
 
     fn compute_diffs(base: &[i64]) -> Vec<i64> {
@@ -46,60 +45,50 @@ impl History {
     }
 
     fn compute_next(&self) -> i64 {
-        let mut nexts = vec![0 as i64; self.sequences.len()];
+        let mut nexts = vec![0_i64; self.sequences.len()];
         for i in (0..nexts.len()-1).rev() {
             nexts[i] = self.sequences[i].last().unwrap() + nexts[i+1];
         }
-        return nexts[0];
+        nexts[0]
     }
-}
-
-fn solve<R: BufRead, W: Write>(input: R, mut output: W) {
-    let mut solution: i64 = 0;
 
-    for line in BufReader::new(input).lines().map(|l| l.unwrap()) {
-        let mut history = History::from_string(&line);
-        history.build_diffs_sequence();
-        dprintln!("History: {:?}", history);
-        solution += history.compute_next();
+    fn compute_prev(&self) -> i64 {
+        let mut prevs = vec![0_i64; self.sequences.len()];
+        for i in (0..prevs.len()-1).rev() {
+            prevs[i] = self.sequences[i][0] - prevs[i+1];
+        }
+        prevs[0]
     }
-
-
-    writeln!(output, "{}", solution).unwrap();
 }
 
-pub fn main() {
-    let stdin = std::io::stdin();
-    let stdout = std::io::stdout();
-    solve(stdin.lock(), stdout.lock());
+pub struct Report {
+    histories: Vec<History>,
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+impl Solution for Report {
+    const DAY: u32 = crate::DAY;
 
-    fn test_exact(input: &str, output: &str) {
-        let mut actual_out: Vec<u8> = Vec::new();
-        solve(input.as_bytes(), &mut actual_out);
-        assert_eq!(String::from_utf8(actual_out).unwrap(), output);
+    fn parse<R: BufRead>(lines: Lines<R>) -> Result<Report> {
+        let mut histories = Vec::new();
+        for line in lines {
+            let mut history = History::parse(&line?)?;
+            history.build_diffs_sequence();
+            debug!("History: {:?}", history);
+            histories.push(history);
+        }
+        Ok(Report { histories })
     }
 
-    fn test_ignore_whitespaces(input: &str, output: &str) {
-        let mut actual_out: Vec<u8> = Vec::new();
-        solve(input.as_bytes(), &mut actual_out);
-        let actual_out_str = String::from_utf8(actual_out).unwrap();
-        let actual_outs = actual_out_str.split_whitespace().collect::<Vec<&str>>();
-        let expected_outs = output.split_whitespace().collect::<Vec<&str>>();
-        assert_eq!(actual_outs, expected_outs);
+    fn part1(&self) -> Result<impl Display> {
+        Ok(self.histories.iter().map(History::compute_next).sum::<i64>())
     }
 
-    #[test]
-    fn sample() {
-        test_ignore_whitespaces(
-            "0 3 6 9 12 15
-            1 3 6 10 15 21
-            10 13 16 21 30 45",
-            "114",
-        );
+    fn part2(&self) -> Result<impl Display> {
+        Ok(self.histories.iter().map(History::compute_prev).sum::<i64>())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    include!(concat!(env!("OUT_DIR"), "/examples/oasis.rs"));
+}
@@ -0,0 +1,98 @@
+//! Accepted answers for our own puzzle inputs, kept in a plain text file.
+//!
+//! Every line is `<day> <part> <answer>` separated by single spaces, the rest
+//! of the line being the answer. Blank lines and lines starting with `#` are
+//! ignored:
+//!
+//! ```text
+//! # day part answer
+//! 1 1 54304
+//! 1 2 54418
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Answers {
+    answers: BTreeMap<(u32, u32), String>,
+}
+
+impl Answers {
+    pub fn parse(text: &str) -> Result<Answers, String> {
+        let mut answers = BTreeMap::new();
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let error = |what: &str| format!("answers, line {}: {}", i + 1, what);
+
+            let mut fields = line.splitn(3, ' ');
+            let mut number = |name: &str| {
+                fields.next()
+                    .and_then(|f| f.parse::<u32>().ok())
+                    .ok_or_else(|| error(&format!("expected a {} number", name)))
+            };
+            let day = number("day")?;
+            let part = number("part")?;
+            let answer = fields.next().map(str::trim).unwrap_or("");
+            if answer.is_empty() {
+                return Err(error("expected an answer"));
+            }
+            if answers.insert((day, part), answer.to_string()).is_some() {
+                return Err(error(&format!("day {} part {} is listed twice", day, part)));
+            }
+        }
+        Ok(Answers { answers })
+    }
+
+    pub fn get(&self, day: u32, part: u32) -> Option<&str> {
+        self.answers.get(&(day, part)).map(|a| a.as_str())
+    }
+
+    pub fn insert(&mut self, day: u32, part: u32, answer: &str) {
+        self.answers.insert((day, part), answer.to_string());
+    }
+
+    /// The file contents, sorted by day and part.
+    pub fn to_text(&self) -> String {
+        let mut text = String::from("# day part answer\n");
+        for ((day, part), answer) in &self.answers {
+            writeln!(text, "{} {} {}", day, part, answer).unwrap();
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let answers = Answers::parse("# day part answer\n\n1 1 142\n 1 2  281 \n24 2 47\n").unwrap();
+        assert_eq!(answers.get(1, 1), Some("142"));
+        assert_eq!(answers.get(1, 2), Some("281"));
+        assert_eq!(answers.get(24, 2), Some("47"));
+        assert_eq!(answers.get(2, 1), None);
+        assert_eq!(Answers::parse(&answers.to_text()), Ok(answers));
+        assert_eq!(Answers::parse("17 1 two words").unwrap().get(17, 1), Some("two words"));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(Answers::parse("1 1 142\nx 1 2").unwrap_err(),
+                   "answers, line 2: expected a day number");
+        assert_eq!(Answers::parse("1").unwrap_err(),
+                   "answers, line 1: expected a part number");
+        assert_eq!(Answers::parse("1 1 142\n1\t2 281").unwrap_err(),
+                   "answers, line 2: expected a day number");
+        assert_eq!(Answers::parse("1  2 281").unwrap_err(),
+                   "answers, line 1: expected a part number");
+        assert_eq!(Answers::parse("1 2").unwrap_err(),
+                   "answers, line 1: expected an answer");
+        assert_eq!(Answers::parse("1 2 3\n1 2 3").unwrap_err(),
+                   "answers, line 2: day 1 part 2 is listed twice");
+    }
+}
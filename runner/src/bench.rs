@@ -0,0 +1,148 @@
+//! Timing of solvers over repeated runs, and baselines to compare them with.
+//!
+//! A baseline is a text file with one `<day> <part> <solver> <median ns> <min ns>`
+//! line per solver, written by `aoc bench --save`.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::io::{BufRead, Write};
+use std::time::{Duration, Instant};
+
+use aoc::parse::Result;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stats {
+    pub median: Duration,
+    pub min: Duration,
+}
+
+impl Stats {
+    /// Panics on no samples.
+    pub fn from_samples(mut samples: Vec<Duration>) -> Stats {
+        samples.sort();
+        let mid = samples.len() / 2;
+        let median = if samples.len().is_multiple_of(2) {
+            (samples[mid - 1] + samples[mid]) / 2
+        } else {
+            samples[mid]
+        };
+        Stats { median, min: samples[0] }
+    }
+}
+
+/// Runs `solve` `warmup` times without measuring, then `runs` times measured.
+pub fn measure<F>(mut solve: F, input: &[u8], warmup: u32, runs: u32) -> Result<Stats>
+    where F: FnMut(&mut dyn BufRead, &mut dyn Write) -> Result<()>
+{
+    for _ in 0..warmup {
+        solve(&mut &input[..], &mut std::io::sink())?;
+    }
+    let mut samples = Vec::new();
+    for _ in 0..runs.max(1) {
+        let start = Instant::now();
+        solve(&mut &input[..], &mut std::io::sink())?;
+        samples.push(start.elapsed());
+    }
+    Ok(Stats::from_samples(samples))
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Baseline {
+    stats: BTreeMap<(u32, u32, String), Stats>,
+}
+
+impl Baseline {
+    pub fn parse(text: &str) -> std::result::Result<Baseline, String> {
+        let mut stats = BTreeMap::new();
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let number = |i: usize| fields.get(i).and_then(|f| f.parse::<u64>().ok());
+            match (number(0), number(1), fields.get(2), number(3), number(4), fields.len()) {
+                (Some(day), Some(part), Some(name), Some(median), Some(min), 5) => {
+                    let entry = Stats {
+                        median: Duration::from_nanos(median),
+                        min: Duration::from_nanos(min),
+                    };
+                    stats.insert((day as u32, part as u32, name.to_string()), entry);
+                },
+                _ => return Err(format!(
+                    "baseline, line {}: expected `<day> <part> <solver> <median ns> <min ns>`",
+                    i + 1)),
+            }
+        }
+        Ok(Baseline { stats })
+    }
+
+    pub fn get(&self, day: u32, part: u32, name: &str) -> Option<Stats> {
+        self.stats.get(&(day, part, name.to_string())).copied()
+    }
+
+    pub fn insert(&mut self, day: u32, part: u32, name: &str, stats: Stats) {
+        self.stats.insert((day, part, name.to_string()), stats);
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text = String::from("# day part solver median_ns min_ns\n");
+        for ((day, part, name), stats) in &self.stats {
+            writeln!(text, "{} {} {} {} {}",
+                     day, part, name, stats.median.as_nanos(), stats.min.as_nanos()).unwrap();
+        }
+        text
+    }
+}
+
+/// How many times faster `now` is than `before`, as in `2.50x`.
+pub fn speedup(before: Duration, now: Duration) -> String {
+    if now.is_zero() {
+        return "-".to_string();
+    }
+    format!("{:.2}x", before.as_secs_f64() / now.as_secs_f64())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(n: u64) -> Duration {
+        Duration::from_millis(n)
+    }
+
+    #[test]
+    fn test_stats() {
+        assert_eq!(Stats::from_samples(vec![ms(5), ms(1), ms(3)]),
+                   Stats { median: ms(3), min: ms(1) });
+        assert_eq!(Stats::from_samples(vec![ms(8), ms(2), ms(4), ms(6)]),
+                   Stats { median: ms(5), min: ms(2) });
+    }
+
+    #[test]
+    fn test_measure() {
+        let mut calls = 0;
+        let stats = measure(|_, _| { calls += 1; Ok(()) }, b"", 2, 3).unwrap();
+        assert_eq!(calls, 5);
+        assert!(stats.min <= stats.median);
+    }
+
+    #[test]
+    fn test_baseline() {
+        let mut baseline = Baseline::parse("# comment\n5 2 almanac2 3000000 2000000\n").unwrap();
+        assert_eq!(baseline.get(5, 2, "almanac2"), Some(Stats { median: ms(3), min: ms(2) }));
+        assert_eq!(baseline.get(5, 2, "almanac2faster"), None);
+
+        baseline.insert(12, 1, "springs_brut", Stats { median: ms(7), min: ms(6) });
+        assert_eq!(Baseline::parse(&baseline.to_text()), Ok(baseline));
+
+        assert_eq!(Baseline::parse("5 2 almanac2 3000000").unwrap_err(),
+                   "baseline, line 1: expected `<day> <part> <solver> <median ns> <min ns>`");
+    }
+
+    #[test]
+    fn test_speedup() {
+        assert_eq!(speedup(ms(10), ms(4)), "2.50x");
+        assert_eq!(speedup(ms(10), Duration::ZERO), "-");
+    }
+}
@@ -0,0 +1,219 @@
+//! Puzzle inputs: downloading, a local cache checked with SHA-256, and
+//! validation before a solver gets to see them.
+//!
+//! The cache keeps `dayN.input` with its hash in `dayN.sha256`. A download only
+//! gets there after it passed `validate`, so an error page never replaces a
+//! good input.
+
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use sha2::{Digest, Sha256};
+
+pub const INPUT_URL: &str = "https://adventofcode.com/2023/day/{day}/input";
+
+const USER_AGENT: &str = "https://github.com/anula/advent_of_code_2023 - auto-retrieve input";
+
+/// Checks that `input` looks like a puzzle input: not empty and not a web page.
+pub fn validate(input: &[u8]) -> Result<(), String> {
+    if input.iter().all(|b| b.is_ascii_whitespace()) {
+        return Err("the input is empty".to_string());
+    }
+    let start = String::from_utf8_lossy(&input[..input.len().min(512)]).to_lowercase();
+    if start.trim_start().starts_with("<!doctype") || start.contains("<html") {
+        return Err("the input is an HTML page".to_string());
+    }
+    Ok(())
+}
+
+pub fn sha256(input: &[u8]) -> String {
+    Sha256::digest(input).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+pub struct InputCache {
+    dir: PathBuf,
+}
+
+impl InputCache {
+    pub fn new(dir: PathBuf) -> InputCache {
+        InputCache { dir }
+    }
+
+    fn input_path(&self, day: u32) -> PathBuf {
+        self.dir.join(format!("day{}.input", day))
+    }
+
+    fn hash_path(&self, day: u32) -> PathBuf {
+        self.dir.join(format!("day{}.sha256", day))
+    }
+
+    /// The cached input of `day`, if there is one and it matches its hash.
+    pub fn get(&self, day: u32) -> Result<Option<Vec<u8>>, String> {
+        let (Ok(input), Ok(hash)) =
+            (fs::read(self.input_path(day)), fs::read_to_string(self.hash_path(day))) else {
+            return Ok(None);
+        };
+        if sha256(&input) != hash.trim() {
+            return Err(format!("the cached input of day {} does not match its hash, remove {}",
+                               day, self.input_path(day).display()));
+        }
+        Ok(Some(input))
+    }
+
+    /// Stores `input` if it is valid, leaving the cache as it was otherwise.
+    pub fn store(&self, day: u32, input: &[u8]) -> Result<(), String> {
+        validate(input).map_err(|e| format!("Not caching the input of day {}: {}", day, e))?;
+        fs::create_dir_all(&self.dir)
+            .map_err(|e| format!("Cannot create {}: {}", self.dir.display(), e))?;
+
+        // Written aside and renamed, an interrupted write doesn't leave half an
+        // input, and the hash is only recorded for an input that is in place.
+        let tmp = self.dir.join(format!("day{}.input.tmp", day));
+        let write = |path: &Path, contents: &[u8]| {
+            fs::write(path, contents).map_err(|e| format!("Cannot write {}: {}", path.display(), e))
+        };
+        write(&tmp, input)?;
+        fs::rename(&tmp, self.input_path(day))
+            .map_err(|e| format!("Cannot write {}: {}", self.input_path(day).display(), e))?;
+        write(&self.hash_path(day), sha256(input).as_bytes())
+    }
+
+    /// The input of `day` from the cache or, unless `offline`, from `download`.
+    pub fn fetch<F>(&self, day: u32, offline: bool, download: F) -> Result<Vec<u8>, String>
+        where F: FnOnce() -> Result<Vec<u8>, String>
+    {
+        if let Some(input) = self.get(day)? {
+            return Ok(input);
+        }
+        if offline {
+            return Err(format!("There is no cached input for day {} and we are offline", day));
+        }
+        let input = download()?;
+        self.store(day, &input)?;
+        Ok(input)
+    }
+}
+
+/// Reads `aoc_session` from a JSON file like `{"aoc_session": "..."}`.
+pub fn read_session(secrets: &Path) -> Result<String, String> {
+    let text = fs::read_to_string(secrets)
+        .map_err(|e| format!("Cannot read {}: {}", secrets.display(), e))?;
+    let json: serde_json::Value = serde_json::from_str(&text)
+        .map_err(|e| format!("{}: {}", secrets.display(), e))?;
+    json["aoc_session"].as_str()
+        .map(|s| s.to_string())
+        .ok_or(format!("{}: there is no `aoc_session` string", secrets.display()))
+}
+
+/// Downloads the input of `day` from `url_template`, where `{day}` is replaced
+/// by the day number. Responses other than 2xx are errors.
+pub fn download_input(url_template: &str, day: u32, session: &str) -> Result<Vec<u8>, String> {
+    let url = url_template.replace("{day}", &day.to_string());
+    let response = ureq::get(&url)
+        .set("Cookie", &format!("session={}", session))
+        .set("User-Agent", USER_AGENT)
+        .call()
+        .map_err(|e| format!("Failed to download {}: {}", url, e))?;
+    let mut input = Vec::new();
+    response.into_reader().read_to_end(&mut input)
+        .map_err(|e| format!("Failed to download {}: {}", url, e))?;
+    Ok(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+
+    fn temp_cache(name: &str) -> InputCache {
+        let dir = std::env::temp_dir().join(format!("aoc_inputs_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        InputCache::new(dir)
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(validate(b"1abc2\npqr3stu8vwx\n").is_ok());
+        assert_eq!(validate(b"").unwrap_err(), "the input is empty");
+        assert_eq!(validate(b" \n\n").unwrap_err(), "the input is empty");
+        assert_eq!(validate(b"<!DOCTYPE html>\n<html lang=\"en-us\">").unwrap_err(),
+                   "the input is an HTML page");
+        assert!(validate(b"<head></head>\n<html>").is_err());
+        // Day 19 inputs start with a `{`, Day 20 ones can contain `>`.
+        assert!(validate(b"px{a<2006:qkq,m>2090:A,rfg}\n").is_ok());
+        assert!(validate(b"broadcaster -> a, b, c\n").is_ok());
+    }
+
+    #[test]
+    fn test_sha256() {
+        assert_eq!(sha256(b"abc"), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+    }
+
+    #[test]
+    fn test_cache() {
+        let cache = temp_cache("cache");
+        assert_eq!(cache.get(3), Ok(None));
+        assert!(cache.fetch(3, true, || panic!("offline")).unwrap_err().contains("offline"));
+
+        assert_eq!(cache.fetch(3, false, || Ok(b"467..114..\n".to_vec())), Ok(b"467..114..\n".to_vec()));
+        assert_eq!(cache.get(3), Ok(Some(b"467..114..\n".to_vec())));
+        assert_eq!(cache.fetch(3, true, || panic!("cached")), Ok(b"467..114..\n".to_vec()));
+
+        // A bad download is an error and the good input stays.
+        assert!(cache.store(3, b"<html>Please log in</html>").is_err());
+        assert!(cache.fetch(4, false, || Ok(Vec::new())).unwrap_err().contains("the input is empty"));
+        assert_eq!(cache.get(3), Ok(Some(b"467..114..\n".to_vec())));
+        assert_eq!(cache.get(4), Ok(None));
+
+        fs::write(cache.input_path(3), b"edited\n").unwrap();
+        assert!(cache.get(3).unwrap_err().contains("does not match its hash"));
+
+        // An input that can not be put in place gets no hash.
+        fs::create_dir(cache.input_path(5)).unwrap();
+        assert!(cache.store(5, b"0 3 6 9 12 15\n").is_err());
+        assert!(!cache.hash_path(5).exists());
+        fs::remove_dir_all(&cache.dir).unwrap();
+    }
+
+    /// Serves `status` and `body` to one request and returns the request head.
+    fn serve_once(status: &'static str, body: &'static str) -> (String, std::thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/2023/day/{{day}}/input", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut head = String::new();
+            while reader.read_line(&mut head).unwrap() > 2 {}
+            write!(reader.get_mut(), "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                   status, body.len(), body).unwrap();
+            head
+        });
+        (url, server)
+    }
+
+    #[test]
+    fn test_download_input() {
+        let (url, server) = serve_once("200 OK", "1abc2\npqr3stu8vwx\n");
+        assert_eq!(download_input(&url, 7, "cookie").unwrap(), b"1abc2\npqr3stu8vwx\n");
+        let head = server.join().unwrap();
+        assert!(head.starts_with("GET /2023/day/7/input HTTP/1.1\r\n"));
+        assert!(head.contains("session=cookie"));
+
+        let (url, server) = serve_once("404 Not Found", "Please don't repeatedly request this endpoint");
+        assert!(download_input(&url, 7, "cookie").unwrap_err().contains("404"));
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn test_error_page_is_not_cached() {
+        let cache = temp_cache("error_page");
+        let (url, server) = serve_once("200 OK", "<!DOCTYPE html><html>Puzzle inputs differ by user.</html>");
+        let fetched = cache.fetch(5, false, || download_input(&url, 5, "expired"));
+        assert_eq!(fetched.unwrap_err(), "Not caching the input of day 5: the input is an HTML page");
+        server.join().unwrap();
+        assert_eq!(cache.get(5), Ok(None));
+    }
+}
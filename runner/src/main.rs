@@ -0,0 +1,885 @@
+mod answers;
+mod bench;
+mod inputs;
+mod new_day;
+mod solvers;
+
+use std::io::{BufRead, Write};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+use answers::Answers;
+use bench::Baseline;
+use inputs::InputCache;
+
+const USAGE: &str = "Usage:
+    aoc [--trace <filter>] <command> ...
+
+    aoc run --day <day> --part <part> [--input <path>] [--variant <name>]
+    aoc crucible [--min-run <n>] [--max-run <n>] [--reversing] [--path]
+                 [--input <path>]
+    aoc workflows [--dot | --accepted] [--categories <list>] [--min <n>]
+                  [--max <n>] [--input <path>]
+    aoc verify [--day <day>] [--answers <path>] [--record]
+    aoc bench [--day <day>] [--part <part>] [--warmup <n>] [--runs <n>]
+              [--baseline <path>] [--save]
+    aoc new <day> [<name>] [--template <path>] [--input-url <url>] [--secrets <path>]
+            [--no-download] [--offline]
+    aoc input <day> [--input-url <url>] [--secrets <path>] [--offline]
+    aoc list
+
+`--trace` turns on the diagnostics of the solvers, written to stderr, and
+overrides AOC_TRACE. The filter is like `debug` or `warn,day12::springs_brut=trace`.
+
+`crucible` solves day 17 with custom movement rules, by default the ones of
+part 1. `--path` draws the way the crucible goes on the city before the heat
+loss. The input defaults to dayN/input in the repo, use `--input -` for stdin.
+
+`workflows` analyzes the workflows of day 19: the rules no part ever matches,
+the workflows no part ever gets to, the cycles between them, and the same
+workflows simplified. `--dot` writes their decision tree as Graphviz DOT
+instead, `--accepted` lists all ranges of parts they accept and how many parts
+that is. The categories, like `x,m,a,s`, default to those of the first part in
+the input, the ratings to 1 up to 4000. A first line in the input like
+`ratings from 0 to 99 in p,q` sets them instead, for `run` too.
+
+`verify` runs every solver on its dayN/input and compares the result with the
+answers file, answers.txt in the repo by default. `--record` adds the answers
+of parts that are not in the file yet.
+
+`bench` times every solver, variants included, on its dayN/input: `--warmup`
+unmeasured runs (1 by default) and then `--runs` measured ones (10 by default).
+It compares the median with the default solver of the same part and with the
+baseline, bench_baseline.txt in the repo by default. `--save` stores the new
+timings in the baseline.
+
+`new` creates the dayN crate with <name>.rs (dayN by default) copied from
+rust_template.rs, registers it in the workspace and the runner, and downloads
+the input like `input` does.
+
+`input` puts the input in dayN/input, from the cache in .cache/inputs or
+downloaded with the `aoc_session` cookie from secrets.json. `{day}` in the
+input URL is replaced by the day number. Downloads are checked and cached with
+their SHA-256, an empty or HTML one is an error and replaces nothing.
+`--offline` uses only the cache. Inputs are checked the same way before any
+solver reads them.";
+
+#[derive(Debug, PartialEq)]
+struct RunArgs {
+    day: u32,
+    part: u32,
+    input: Option<PathBuf>,
+    variant: Option<String>,
+}
+
+impl RunArgs {
+    fn parse(args: &[String]) -> Result<RunArgs, String> {
+        let mut day = None;
+        let mut part = None;
+        let mut input = None;
+        let mut variant = None;
+
+        let mut args = args.iter();
+        while let Some(flag) = args.next() {
+            let value = args.next().ok_or(format!("Missing value for {}", flag))?;
+            match flag.as_str() {
+                "--day" => day = Some(parse_number(flag, value)?),
+                "--part" => part = Some(parse_number(flag, value)?),
+                "--input" => input = Some(PathBuf::from(value)),
+                "--variant" => variant = Some(value.to_string()),
+                _ => return Err(format!("Unknown flag: {}", flag)),
+            }
+        }
+
+        Ok(RunArgs {
+            day: day.ok_or("Missing --day")?,
+            part: part.ok_or("Missing --part")?,
+            input,
+            variant,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct CrucibleArgs {
+    rules: day17::crucible::Rules,
+    path: bool,
+    input: Option<PathBuf>,
+}
+
+impl CrucibleArgs {
+    fn parse(args: &[String]) -> Result<CrucibleArgs, String> {
+        let mut rules = day17::crucible::Rules::CRUCIBLE;
+        let mut path = false;
+        let mut input = None;
+
+        let mut args = args.iter();
+        while let Some(flag) = args.next() {
+            if flag == "--reversing" {
+                rules.reversing = true;
+                continue;
+            }
+            if flag == "--path" {
+                path = true;
+                continue;
+            }
+            let value = args.next().ok_or(format!("Missing value for {}", flag))?;
+            match flag.as_str() {
+                "--min-run" => rules.min_run = parse_number(flag, value)? as i64,
+                "--max-run" => rules.max_run = parse_number(flag, value)? as i64,
+                "--input" => input = Some(PathBuf::from(value)),
+                _ => return Err(format!("Unknown flag: {}", flag)),
+            }
+        }
+
+        if rules.min_run > rules.max_run {
+            return Err(format!("--min-run {} is larger than --max-run {}",
+                               rules.min_run, rules.max_run));
+        }
+        Ok(CrucibleArgs { rules, path, input })
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum WorkflowsReport {
+    Analysis,
+    Dot,
+    Accepted,
+}
+
+#[derive(Debug, PartialEq)]
+struct WorkflowsArgs {
+    report: WorkflowsReport,
+    ratings: day19::aplenty::Ratings,
+    input: Option<PathBuf>,
+}
+
+impl WorkflowsArgs {
+    fn parse(args: &[String]) -> Result<WorkflowsArgs, String> {
+        let mut report = WorkflowsReport::Analysis;
+        let mut ratings = day19::aplenty::Ratings::default();
+        let mut input = None;
+
+        let mut args = args.iter();
+        while let Some(flag) = args.next() {
+            let chosen = match flag.as_str() {
+                "--dot" => Some(WorkflowsReport::Dot),
+                "--accepted" => Some(WorkflowsReport::Accepted),
+                _ => None,
+            };
+            if let Some(chosen) = chosen {
+                if report != WorkflowsReport::Analysis {
+                    return Err("Only one of --dot and --accepted can be given".to_string());
+                }
+                report = chosen;
+                continue;
+            }
+            let value = args.next().ok_or(format!("Missing value for {}", flag))?;
+            match flag.as_str() {
+                "--categories" => ratings.categories = value.split(',').map(|c| c.to_string()).collect(),
+                "--min" => ratings.min = parse_number(flag, value)? as i64,
+                "--max" => ratings.max = parse_number(flag, value)? as i64,
+                "--input" => input = Some(PathBuf::from(value)),
+                _ => return Err(format!("Unknown flag: {}", flag)),
+            }
+        }
+
+        if ratings.min > ratings.max {
+            return Err(format!("--min {} is larger than --max {}", ratings.min, ratings.max));
+        }
+        Ok(WorkflowsArgs { report, ratings, input })
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct VerifyArgs {
+    day: Option<u32>,
+    answers: Option<PathBuf>,
+    record: bool,
+}
+
+impl VerifyArgs {
+    fn parse(args: &[String]) -> Result<VerifyArgs, String> {
+        let mut day = None;
+        let mut answers = None;
+        let mut record = false;
+
+        let mut args = args.iter();
+        while let Some(flag) = args.next() {
+            if flag == "--record" {
+                record = true;
+                continue;
+            }
+            let value = args.next().ok_or(format!("Missing value for {}", flag))?;
+            match flag.as_str() {
+                "--day" => day = Some(parse_number(flag, value)?),
+                "--answers" => answers = Some(PathBuf::from(value)),
+                _ => return Err(format!("Unknown flag: {}", flag)),
+            }
+        }
+
+        Ok(VerifyArgs { day, answers, record })
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct BenchArgs {
+    day: Option<u32>,
+    part: Option<u32>,
+    warmup: u32,
+    runs: u32,
+    baseline: Option<PathBuf>,
+    save: bool,
+}
+
+impl BenchArgs {
+    fn parse(args: &[String]) -> Result<BenchArgs, String> {
+        let mut bench = BenchArgs {
+            day: None,
+            part: None,
+            warmup: 1,
+            runs: 10,
+            baseline: None,
+            save: false,
+        };
+
+        let mut args = args.iter();
+        while let Some(flag) = args.next() {
+            if flag == "--save" {
+                bench.save = true;
+                continue;
+            }
+            let value = args.next().ok_or(format!("Missing value for {}", flag))?;
+            match flag.as_str() {
+                "--day" => bench.day = Some(parse_number(flag, value)?),
+                "--part" => bench.part = Some(parse_number(flag, value)?),
+                "--warmup" => bench.warmup = parse_number(flag, value)?,
+                "--runs" => bench.runs = parse_number(flag, value)?,
+                "--baseline" => bench.baseline = Some(PathBuf::from(value)),
+                _ => return Err(format!("Unknown flag: {}", flag)),
+            }
+        }
+
+        if bench.runs == 0 {
+            return Err("--runs has to be at least 1".to_string());
+        }
+        Ok(bench)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct NewArgs {
+    day: u32,
+    name: String,
+    template: Option<PathBuf>,
+    input_url: String,
+    secrets: Option<PathBuf>,
+    download: bool,
+    offline: bool,
+}
+
+impl NewArgs {
+    fn parse(args: &[String]) -> Result<NewArgs, String> {
+        let mut positional = Vec::new();
+        let mut template = None;
+        let mut input_url = inputs::INPUT_URL.to_string();
+        let mut secrets = None;
+        let mut download = true;
+        let mut offline = false;
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            if arg == "--no-download" {
+                download = false;
+                continue;
+            }
+            if arg == "--offline" {
+                offline = true;
+                continue;
+            }
+            if !arg.starts_with("--") {
+                positional.push(arg);
+                continue;
+            }
+            let value = args.next().ok_or(format!("Missing value for {}", arg))?;
+            match arg.as_str() {
+                "--template" => template = Some(PathBuf::from(value)),
+                "--input-url" => input_url = value.to_string(),
+                "--secrets" => secrets = Some(PathBuf::from(value)),
+                _ => return Err(format!("Unknown flag: {}", arg)),
+            }
+        }
+
+        let (day, name) = match positional[..] {
+            [day] => (parse_number("<day>", day)?, None),
+            [day, name] => (parse_number("<day>", day)?, Some(name.to_string())),
+            [] => return Err("Missing <day>".to_string()),
+            _ => return Err(format!("Too many arguments: {:?}", positional)),
+        };
+        Ok(NewArgs {
+            day,
+            name: name.unwrap_or(format!("day{}", day)),
+            template,
+            input_url,
+            secrets,
+            download,
+            offline,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct InputArgs {
+    day: u32,
+    input_url: String,
+    secrets: Option<PathBuf>,
+    offline: bool,
+}
+
+impl InputArgs {
+    fn parse(args: &[String]) -> Result<InputArgs, String> {
+        let mut day = None;
+        let mut input_url = inputs::INPUT_URL.to_string();
+        let mut secrets = None;
+        let mut offline = false;
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            if arg == "--offline" {
+                offline = true;
+                continue;
+            }
+            if !arg.starts_with("--") && day.is_none() {
+                day = Some(parse_number("<day>", arg)?);
+                continue;
+            }
+            let value = args.next().ok_or(format!("Missing value for {}", arg))?;
+            match arg.as_str() {
+                "--input-url" => input_url = value.to_string(),
+                "--secrets" => secrets = Some(PathBuf::from(value)),
+                _ => return Err(format!("Unknown flag: {}", arg)),
+            }
+        }
+
+        Ok(InputArgs { day: day.ok_or("Missing <day>")?, input_url, secrets, offline })
+    }
+}
+
+fn parse_number(flag: &str, value: &str) -> Result<u32, String> {
+    value.parse().map_err(|_| format!("{} expects a number, got: {}", flag, value))
+}
+
+fn repo_root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("..")
+}
+
+fn default_input(day: u32) -> PathBuf {
+    repo_root().join(format!("day{}", day)).join("input")
+}
+
+/// Reads the input file, which has to pass `inputs::validate`.
+fn read_input(path: &PathBuf) -> Result<Vec<u8>, String> {
+    let input = std::fs::read(path)
+        .map_err(|e| format!("Cannot open {}: {}", path.display(), e))?;
+    inputs::validate(&input).map_err(|e| format!("{}: {}", path.display(), e))?;
+    Ok(input)
+}
+
+fn open_input(path: Option<&PathBuf>, day: u32) -> Result<Box<dyn BufRead>, String> {
+    let path = path.cloned().unwrap_or_else(|| default_input(day));
+    if path.as_os_str() == "-" {
+        return Ok(Box::new(std::io::stdin().lock()));
+    }
+    Ok(Box::new(std::io::Cursor::new(read_input(&path)?)))
+}
+
+fn run(args: &RunArgs) -> Result<(), String> {
+    let solver = solvers::find(args.day, args.part, args.variant.as_deref())
+        .ok_or(format!("No solver for day {}, part {}{}", args.day, args.part,
+                       args.variant.as_ref().map_or(String::new(), |v| format!(" named {}", v))))?;
+
+    let mut input = open_input(args.input.as_ref(), args.day)?;
+
+    let mut answer = Vec::new();
+    let start = Instant::now();
+    (solver.solve)(&mut input, &mut answer).map_err(|e| e.to_string())?;
+    let elapsed = start.elapsed();
+
+    std::io::stdout().write_all(&answer).unwrap();
+    eprintln!("Day {}, part {} ({}) took {:?}", solver.day, solver.part, solver.name, elapsed);
+    Ok(())
+}
+
+fn crucible(args: &CrucibleArgs) -> Result<(), String> {
+    let input = open_input(args.input.as_ref(), 17)?;
+
+    let mut answer = Vec::new();
+    let start = Instant::now();
+    let solve = if args.path {
+        day17::crucible::draw_with_rules
+    } else {
+        day17::crucible::solve_with_rules
+    };
+    solve(input, &mut answer, &args.rules).map_err(|e| e.to_string())?;
+    std::io::stdout().write_all(&answer).unwrap();
+    eprintln!("Day 17 with {:?} took {:?}", args.rules, start.elapsed());
+    Ok(())
+}
+
+fn workflows(args: &WorkflowsArgs) -> Result<(), String> {
+    let input = open_input(args.input.as_ref(), 19)?;
+
+    let mut report = Vec::new();
+    let write = match args.report {
+        WorkflowsReport::Analysis => day19::analysis::analyze,
+        WorkflowsReport::Dot => day19::tree::export_dot,
+        WorkflowsReport::Accepted => day19::tree::list_accepted,
+    };
+    write(input, &mut report, &args.ratings).map_err(|e| e.to_string())?;
+    std::io::stdout().write_all(&report).unwrap();
+    Ok(())
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Outcome {
+    Pass,
+    Fail { expected: String },
+    /// There is no accepted answer to compare with.
+    New,
+    Error(String),
+    NoInput,
+}
+
+impl Outcome {
+    fn is_failure(&self) -> bool {
+        matches!(self, Outcome::Fail { .. } | Outcome::Error(_))
+    }
+
+    fn status(&self) -> &'static str {
+        match self {
+            Outcome::Pass => "ok",
+            Outcome::Fail { .. } => "FAIL",
+            Outcome::New => "new",
+            Outcome::Error(_) => "ERROR",
+            Outcome::NoInput => "no input",
+        }
+    }
+}
+
+fn check(answer: &str, expected: Option<&str>) -> Outcome {
+    match expected {
+        None => Outcome::New,
+        Some(expected) if expected == answer => Outcome::Pass,
+        Some(expected) => Outcome::Fail { expected: expected.to_string() },
+    }
+}
+
+fn verify(args: &VerifyArgs) -> Result<(), String> {
+    let answers_path = args.answers.clone().unwrap_or_else(|| {
+        repo_root().join("answers.txt")
+    });
+    let mut answers = match std::fs::read_to_string(&answers_path) {
+        Ok(text) => Answers::parse(&text)?,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound && args.answers.is_none() => {
+            Answers::default()
+        },
+        Err(e) => return Err(format!("Cannot read {}: {}", answers_path.display(), e)),
+    };
+
+    println!("{:>3} {:>4}  {:<20} {:<8} {:>10}  answer", "day", "part", "solver", "status", "time");
+    let mut checked = 0;
+    let mut failures = 0;
+    let mut recorded = 0;
+    let mut total = Duration::ZERO;
+    let mut input: Option<(u32, Result<Vec<u8>, Outcome>)> = None;
+    for solver in solvers::SOLVERS.iter().filter(|s| args.day.is_none_or(|d| d == s.day)) {
+        if input.as_ref().is_none_or(|(day, _)| *day != solver.day) {
+            let path = default_input(solver.day);
+            let bytes = if path.exists() {
+                read_input(&path).map_err(Outcome::Error)
+            } else {
+                Err(Outcome::NoInput)
+            };
+            input = Some((solver.day, bytes));
+        }
+        let (outcome, answer, elapsed) = match &input {
+            Some((_, Ok(bytes))) => {
+                let mut output = Vec::new();
+                let start = Instant::now();
+                let result = (solver.solve)(&mut bytes.as_slice(), &mut output);
+                let elapsed = start.elapsed();
+                total += elapsed;
+                let answer = String::from_utf8_lossy(&output).trim().to_string();
+                let outcome = match result {
+                    Ok(()) => check(&answer, answers.get(solver.day, solver.part)),
+                    Err(e) => Outcome::Error(e.to_string()),
+                };
+                (outcome, answer, Some(elapsed))
+            },
+            Some((_, Err(outcome))) => (outcome.clone(), String::new(), None),
+            None => unreachable!(),
+        };
+
+        if outcome == Outcome::New && args.record {
+            answers.insert(solver.day, solver.part, &answer);
+            recorded += 1;
+        }
+        if outcome.is_failure() {
+            failures += 1;
+        }
+
+        let details = match &outcome {
+            Outcome::Fail { expected } => format!("{} (expected {})", answer, expected),
+            Outcome::Error(e) => e.clone(),
+            _ => answer,
+        };
+        let row = format!("{:>3} {:>4}  {:<20} {:<8} {:>10}  {}",
+                          solver.day, solver.part, solver.name, outcome.status(),
+                          elapsed.map_or(String::new(), |e| format!("{:.2?}", e)), details);
+        println!("{}", row.trim_end());
+        checked += 1;
+    }
+    println!("total time {:.2?}", total);
+
+    if recorded > 0 {
+        std::fs::write(&answers_path, answers.to_text())
+            .map_err(|e| format!("Cannot write {}: {}", answers_path.display(), e))?;
+        println!("recorded {} new answers in {}", recorded, answers_path.display());
+    }
+    if failures > 0 {
+        return Err(format!("{} of {} solvers failed", failures, checked));
+    }
+    Ok(())
+}
+
+fn bench(args: &BenchArgs) -> Result<(), String> {
+    let baseline_path = args.baseline.clone().unwrap_or_else(|| {
+        repo_root().join("bench_baseline.txt")
+    });
+    let mut baseline = match std::fs::read_to_string(&baseline_path) {
+        Ok(text) => Baseline::parse(&text)?,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Baseline::default(),
+        Err(e) => return Err(format!("Cannot read {}: {}", baseline_path.display(), e)),
+    };
+
+    println!("{:>3} {:>4}  {:<20} {:>10} {:>10} {:>10} {:>11}",
+             "day", "part", "solver", "median", "min", "vs default", "vs baseline");
+    let mut input: Option<(u32, Result<Vec<u8>, String>)> = None;
+    let mut default_median = None;
+    for solver in solvers::SOLVERS.iter()
+        .filter(|s| args.day.is_none_or(|d| d == s.day))
+        .filter(|s| args.part.is_none_or(|p| p == s.part))
+    {
+        if input.as_ref().is_none_or(|(day, _)| *day != solver.day) {
+            input = Some((solver.day, read_input(&default_input(solver.day))));
+        }
+        let bytes = match &input {
+            Some((_, Ok(bytes))) => bytes,
+            Some((_, Err(e))) => {
+                println!("{:>3} {:>4}  {:<20} {}", solver.day, solver.part, solver.name, e);
+                continue;
+            },
+            None => unreachable!(),
+        };
+        let stats = match bench::measure(solver.solve, bytes, args.warmup, args.runs) {
+            Ok(stats) => stats,
+            Err(e) => {
+                println!("{:>3} {:>4}  {:<20} {}", solver.day, solver.part, solver.name, e);
+                continue;
+            },
+        };
+
+        let is_default = std::ptr::eq(solvers::find(solver.day, solver.part, None).unwrap(), solver);
+        if is_default {
+            default_median = Some((solver.day, solver.part, stats.median));
+        }
+        let vs_default = match default_median {
+            Some((day, part, median)) if !is_default && (day, part) == (solver.day, solver.part) => {
+                bench::speedup(median, stats.median)
+            },
+            _ => String::new(),
+        };
+        let vs_baseline = baseline.get(solver.day, solver.part, solver.name)
+            .map_or(String::new(), |before| bench::speedup(before.median, stats.median));
+        let row = format!("{:>3} {:>4}  {:<20} {:>10} {:>10} {:>10} {:>11}",
+                          solver.day, solver.part, solver.name,
+                          format!("{:.2?}", stats.median), format!("{:.2?}", stats.min),
+                          vs_default, vs_baseline);
+        println!("{}", row.trim_end());
+
+        if args.save {
+            baseline.insert(solver.day, solver.part, solver.name, stats);
+        }
+    }
+
+    if args.save {
+        std::fs::write(&baseline_path, baseline.to_text())
+            .map_err(|e| format!("Cannot write {}: {}", baseline_path.display(), e))?;
+        println!("saved the baseline in {}", baseline_path.display());
+    }
+    Ok(())
+}
+
+fn new(args: &NewArgs) -> Result<(), String> {
+    let root = repo_root();
+    let template_path = args.template.clone().unwrap_or_else(|| root.join("rust_template.rs"));
+    let template = std::fs::read_to_string(&template_path)
+        .map_err(|e| format!("Cannot read {}: {}", template_path.display(), e))?;
+
+    new_day::create_day(&root, args.day, &args.name, &template)?;
+    println!("Created day{} with {}.rs", args.day, args.name);
+
+    if args.download {
+        let input_args = InputArgs {
+            day: args.day,
+            input_url: args.input_url.clone(),
+            secrets: args.secrets.clone(),
+            offline: args.offline,
+        };
+        // The crate is there already, the input can be fetched later with `aoc input`.
+        if let Err(e) = input(&input_args) {
+            eprintln!("{}", e);
+        }
+    }
+    Ok(())
+}
+
+fn input(args: &InputArgs) -> Result<(), String> {
+    let path = default_input(args.day);
+    let cache = InputCache::new(repo_root().join(".cache").join("inputs"));
+
+    if let Ok(existing) = read_input(&path) {
+        if cache.get(args.day)?.is_none() {
+            cache.store(args.day, &existing)?;
+        }
+        println!("{} is already there", path.display());
+        return Ok(());
+    }
+
+    let secrets = args.secrets.clone().unwrap_or_else(|| repo_root().join("secrets.json"));
+    let input = cache.fetch(args.day, args.offline, || {
+        let session = inputs::read_session(&secrets)?;
+        inputs::download_input(&args.input_url, args.day, &session)
+    })?;
+    std::fs::write(&path, input).map_err(|e| format!("Cannot write {}: {}", path.display(), e))?;
+    println!("Wrote the input to {}", path.display());
+    Ok(())
+}
+
+fn list() {
+    for s in solvers::SOLVERS {
+        println!("day {:>2} part {} {}", s.day, s.part, s.name);
+    }
+}
+
+fn main() -> ExitCode {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+
+    if args.first().is_some_and(|a| a == "--trace") {
+        let filter = args.get(1).ok_or("Missing value for --trace".to_string())
+            .and_then(|f| aoc::trace::init(f));
+        if let Err(e) = filter {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+        args.drain(..2);
+    }
+
+    let result = match args.first().map(|a| a.as_str()) {
+        Some("run") => RunArgs::parse(&args[1..]).and_then(|a| run(&a)),
+        Some("crucible") => CrucibleArgs::parse(&args[1..]).and_then(|a| crucible(&a)),
+        Some("workflows") => WorkflowsArgs::parse(&args[1..]).and_then(|a| workflows(&a)),
+        Some("verify") => VerifyArgs::parse(&args[1..]).and_then(|a| verify(&a)),
+        Some("bench") => BenchArgs::parse(&args[1..]).and_then(|a| bench(&a)),
+        Some("new") => NewArgs::parse(&args[1..]).and_then(|a| new(&a)),
+        Some("input") => InputArgs::parse(&args[1..]).and_then(|a| input(&a)),
+        Some("list") => {
+            list();
+            Ok(())
+        },
+        _ => Err(USAGE.to_string()),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_run_args() {
+        assert_eq!(
+            RunArgs::parse(&to_args("--day 8 --part 1")),
+            Ok(RunArgs { day: 8, part: 1, input: None, variant: None }),
+        );
+        assert_eq!(
+            RunArgs::parse(&to_args("--part 2 --day 10 --input x/y --variant maze2")),
+            Ok(RunArgs {
+                day: 10,
+                part: 2,
+                input: Some(PathBuf::from("x/y")),
+                variant: Some("maze2".to_string()),
+            }),
+        );
+        assert!(RunArgs::parse(&to_args("--day 8")).is_err());
+        assert!(RunArgs::parse(&to_args("--day eight --part 1")).is_err());
+        assert!(RunArgs::parse(&to_args("--day 8 --part")).is_err());
+        assert!(RunArgs::parse(&to_args("--day 8 --part 1 --verbose yes")).is_err());
+    }
+
+    #[test]
+    fn test_parse_crucible_args() {
+        use day17::crucible::Rules;
+
+        assert_eq!(
+            CrucibleArgs::parse(&[]),
+            Ok(CrucibleArgs { rules: Rules::CRUCIBLE, path: false, input: None }),
+        );
+        assert_eq!(
+            CrucibleArgs::parse(&to_args("--min-run 4 --max-run 10")),
+            Ok(CrucibleArgs { rules: Rules::ULTRA_CRUCIBLE, path: false, input: None }),
+        );
+        assert_eq!(
+            CrucibleArgs::parse(&to_args("--reversing --max-run 1 --path --input -")),
+            Ok(CrucibleArgs {
+                rules: Rules { min_run: 1, max_run: 1, reversing: true },
+                path: true,
+                input: Some(PathBuf::from("-")),
+            }),
+        );
+        assert!(CrucibleArgs::parse(&to_args("--min-run 5 --max-run 4")).is_err());
+        assert!(CrucibleArgs::parse(&to_args("--min-run")).is_err());
+        assert!(CrucibleArgs::parse(&to_args("--day 17")).is_err());
+    }
+
+    #[test]
+    fn test_parse_workflows_args() {
+        use day19::aplenty::Ratings;
+
+        assert_eq!(
+            WorkflowsArgs::parse(&[]),
+            Ok(WorkflowsArgs { report: WorkflowsReport::Analysis, ratings: Ratings::default(), input: None }),
+        );
+        assert_eq!(
+            WorkflowsArgs::parse(&to_args("--input - --dot --categories a,b,c --max 9")),
+            Ok(WorkflowsArgs {
+                report: WorkflowsReport::Dot,
+                ratings: Ratings { categories: vec!["a".into(), "b".into(), "c".into()], min: 1, max: 9 },
+                input: Some(PathBuf::from("-")),
+            }),
+        );
+        assert!(WorkflowsArgs::parse(&to_args("--input")).is_err());
+        assert!(WorkflowsArgs::parse(&to_args("--min 10 --max 9")).is_err());
+        assert!(WorkflowsArgs::parse(&to_args("--dot --accepted")).is_err());
+        assert!(WorkflowsArgs::parse(&to_args("--day 19")).is_err());
+    }
+
+    #[test]
+    fn test_parse_verify_args() {
+        assert_eq!(
+            VerifyArgs::parse(&[]),
+            Ok(VerifyArgs { day: None, answers: None, record: false }),
+        );
+        assert_eq!(
+            VerifyArgs::parse(&to_args("--record --day 5 --answers a.txt")),
+            Ok(VerifyArgs { day: Some(5), answers: Some(PathBuf::from("a.txt")), record: true }),
+        );
+        assert!(VerifyArgs::parse(&to_args("--day")).is_err());
+        assert!(VerifyArgs::parse(&to_args("--part 1")).is_err());
+    }
+
+    #[test]
+    fn test_parse_bench_args() {
+        assert_eq!(
+            BenchArgs::parse(&[]),
+            Ok(BenchArgs { day: None, part: None, warmup: 1, runs: 10, baseline: None, save: false }),
+        );
+        assert_eq!(
+            BenchArgs::parse(&to_args("--day 12 --part 1 --warmup 0 --runs 3 --baseline b --save")),
+            Ok(BenchArgs {
+                day: Some(12),
+                part: Some(1),
+                warmup: 0,
+                runs: 3,
+                baseline: Some(PathBuf::from("b")),
+                save: true,
+            }),
+        );
+        assert!(BenchArgs::parse(&to_args("--runs 0")).is_err());
+        assert!(BenchArgs::parse(&to_args("--runs")).is_err());
+        assert!(BenchArgs::parse(&to_args("--input x")).is_err());
+    }
+
+    #[test]
+    fn test_parse_new_args() {
+        assert_eq!(
+            NewArgs::parse(&to_args("26")),
+            Ok(NewArgs {
+                day: 26,
+                name: "day26".to_string(),
+                template: None,
+                input_url: inputs::INPUT_URL.to_string(),
+                secrets: None,
+                download: true,
+                offline: false,
+            }),
+        );
+        assert_eq!(
+            NewArgs::parse(&to_args("26 sleigh --input-url http://localhost:8000/{day} --no-download --offline")),
+            Ok(NewArgs {
+                day: 26,
+                name: "sleigh".to_string(),
+                template: None,
+                input_url: "http://localhost:8000/{day}".to_string(),
+                secrets: None,
+                download: false,
+                offline: true,
+            }),
+        );
+        assert!(NewArgs::parse(&[]).is_err());
+        assert!(NewArgs::parse(&to_args("twenty")).is_err());
+        assert!(NewArgs::parse(&to_args("26 sleigh more")).is_err());
+        assert!(NewArgs::parse(&to_args("26 --secrets")).is_err());
+    }
+
+    #[test]
+    fn test_parse_input_args() {
+        assert_eq!(
+            InputArgs::parse(&to_args("5 --offline")),
+            Ok(InputArgs { day: 5, input_url: inputs::INPUT_URL.to_string(), secrets: None, offline: true }),
+        );
+        assert_eq!(
+            InputArgs::parse(&to_args("--secrets s.json 5 --input-url http://localhost/{day}")),
+            Ok(InputArgs {
+                day: 5,
+                input_url: "http://localhost/{day}".to_string(),
+                secrets: Some(PathBuf::from("s.json")),
+                offline: false,
+            }),
+        );
+        assert!(InputArgs::parse(&[]).is_err());
+        assert!(InputArgs::parse(&to_args("5 6")).is_err());
+        assert!(InputArgs::parse(&to_args("five")).is_err());
+    }
+
+    #[test]
+    fn test_check() {
+        assert_eq!(check("142", Some("142")), Outcome::Pass);
+        assert_eq!(check("143", Some("142")), Outcome::Fail { expected: "142".to_string() });
+        assert_eq!(check("142", None), Outcome::New);
+        assert!(check("143", Some("142")).is_failure());
+        assert!(!Outcome::NoInput.is_failure());
+    }
+}
@@ -0,0 +1,173 @@
+//! Scaffolding of a new day crate: sources from the template and registration
+//! in the workspace and the runner.
+
+use std::fs;
+use std::path::Path;
+
+fn cargo_toml(day_name: &str) -> String {
+    format!("[package]
+name = \"{day_name}\"
+version = \"0.1.0\"
+edition = \"2021\"
+
+# See more keys and their definitions at https://doc.rust-lang.org/cargo/reference/manifest.html
+
+[dependencies]
+aoc = {{ path = \"../aoc\" }}
+
+[build-dependencies]
+aoc = {{ path = \"../aoc\" }}
+
+[lints]
+workspace = true
+")
+}
+
+fn lib_rs(day: u32, problem_name: &str) -> String {
+    format!("pub const DAY: u32 = {day};\n\npub mod {problem_name};\n\npub type Puzzle = {problem_name}::Input;\n")
+}
+
+const BUILD_RS: &str = "fn main() {\n    aoc::examples::generate_tests();\n}\n";
+
+fn main_rs(day_name: &str) -> String {
+    format!("fn main() {{\n    aoc::solution::main::<{day_name}::Puzzle>();\n}}\n")
+}
+
+/// Adds `day_name` at the end of the `members` of the workspace `Cargo.toml`.
+pub fn register_member(workspace_toml: &str, day_name: &str) -> Result<String, String> {
+    let members = workspace_toml.find("members = [")
+        .ok_or("Cargo.toml: there is no `members = [` list")?;
+    let end = members + workspace_toml[members..].find(']')
+        .ok_or("Cargo.toml: the `members` list is not closed")?;
+    let end = workspace_toml[..end].rfind('\n').map_or(end, |i| i + 1);
+    Ok(format!("{}    \"{}\",\n{}", &workspace_toml[..end], day_name, &workspace_toml[end..]))
+}
+
+/// Adds `day_name` as a path dependency after the last day of the runner.
+pub fn register_dependency(runner_toml: &str, day_name: &str) -> Result<String, String> {
+    let last_day = runner_toml.lines()
+        .rfind(|l| l.starts_with("day") && l.contains("path = \"../day"))
+        .ok_or("runner/Cargo.toml: there are no day dependencies")?;
+    let end = runner_toml.rfind(last_day).unwrap() + last_day.len();
+    Ok(format!("{}\n{} = {{ path = \"../{}\" }}{}",
+               &runner_toml[..end], day_name, day_name, &runner_toml[end..]))
+}
+
+/// Adds both parts of the new day at the end of `SOLVERS`.
+pub fn register_solver(solvers_rs: &str, day: u32, problem_name: &str) -> Result<String, String> {
+    let solvers = solvers_rs.find("pub static SOLVERS")
+        .ok_or("solvers.rs: there is no `SOLVERS` list")?;
+    let end = solvers + solvers_rs[solvers..].find("\n];")
+        .ok_or("solvers.rs: the `SOLVERS` list is not closed")? + 1;
+    let solutions = (1..=2)
+        .map(|part| format!("    solution!({}, {}, day{}::{}),\n", day, part, day, problem_name))
+        .collect::<String>();
+    Ok(format!("{}{}{}", &solvers_rs[..end], solutions, &solvers_rs[end..]))
+}
+
+fn update(path: &Path, f: impl FnOnce(&str) -> Result<String, String>) -> Result<(), String> {
+    let text = fs::read_to_string(path)
+        .map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+    fs::write(path, f(&text)?)
+        .map_err(|e| format!("Cannot write {}: {}", path.display(), e))
+}
+
+/// Creates `root/dayN` with `template` as `problem_name.rs`, with a sample in
+/// `examples` to fill in, and registers it.
+pub fn create_day(root: &Path, day: u32, problem_name: &str, template: &str) -> Result<(), String> {
+    let day_name = format!("day{}", day);
+    let day_dir = root.join(&day_name);
+    if day_dir.exists() {
+        return Err(format!("Directory {} already exists, aborting...", day_dir.display()));
+    }
+    if problem_name.is_empty() || !problem_name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        || problem_name.starts_with(|c: char| c.is_ascii_digit()) {
+        return Err(format!("{} is not a valid module name", problem_name));
+    }
+
+    let write = |path: &Path, contents: &str| {
+        fs::write(path, contents).map_err(|e| format!("Cannot write {}: {}", path.display(), e))
+    };
+    let src = day_dir.join("src");
+    fs::create_dir_all(&src).map_err(|e| format!("Cannot create {}: {}", src.display(), e))?;
+    write(&day_dir.join("Cargo.toml"), &cargo_toml(&day_name))?;
+    write(&src.join("lib.rs"), &lib_rs(day, problem_name))?;
+    write(&src.join("main.rs"), &main_rs(&day_name))?;
+    let template = template.replace("/examples/rust_template.rs", &format!("/examples/{}.rs", problem_name));
+    write(&src.join(format!("{}.rs", problem_name)), &template)?;
+    write(&day_dir.join("build.rs"), BUILD_RS)?;
+
+    let examples = day_dir.join("examples");
+    fs::create_dir_all(&examples).map_err(|e| format!("Cannot create {}: {}", examples.display(), e))?;
+    write(&examples.join(format!("{}.sample.in", problem_name)), "1\n")?;
+    write(&examples.join(format!("{}.sample.part1.out", problem_name)), "0\n")?;
+    write(&examples.join(format!("{}.sample.part2.out", problem_name)), "0\n")?;
+
+    update(&root.join("Cargo.toml"), |t| register_member(t, &day_name))?;
+    update(&root.join("runner").join("Cargo.toml"), |t| register_dependency(t, &day_name))?;
+    update(&root.join("runner").join("src").join("solvers.rs"),
+           |t| register_solver(t, day, problem_name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_register_member() {
+        let toml = "[workspace]\nmembers = [\n    \"aoc\",\n    \"day1\",\n]\n\n[workspace.lints.clippy]\n";
+        assert_eq!(
+            register_member(toml, "day2").unwrap(),
+            "[workspace]\nmembers = [\n    \"aoc\",\n    \"day1\",\n    \"day2\",\n]\n\n[workspace.lints.clippy]\n",
+        );
+        assert!(register_member("[workspace]\n", "day2").is_err());
+    }
+
+    #[test]
+    fn test_register_dependency() {
+        let toml = "[dependencies]\naoc = { path = \"../aoc\" }\nday1 = { path = \"../day1\" }\n\n[lints]\n";
+        assert_eq!(
+            register_dependency(toml, "day2").unwrap(),
+            "[dependencies]\naoc = { path = \"../aoc\" }\nday1 = { path = \"../day1\" }\nday2 = { path = \"../day2\" }\n\n[lints]\n",
+        );
+    }
+
+    #[test]
+    fn test_register_solver() {
+        let solvers = "pub static SOLVERS: &[Solver] = &[\n    solution!(1, 1, day1::trebuchet),\n];\n";
+        assert_eq!(
+            register_solver(solvers, 2, "cubes").unwrap(),
+            "pub static SOLVERS: &[Solver] = &[\n    solution!(1, 1, day1::trebuchet),\n    \
+             solution!(2, 1, day2::cubes),\n    solution!(2, 2, day2::cubes),\n];\n",
+        );
+    }
+
+    #[test]
+    fn test_create_day() {
+        let root = std::env::temp_dir().join(format!("aoc_new_day_{}", std::process::id()));
+        fs::create_dir_all(root.join("runner").join("src")).unwrap();
+        fs::write(root.join("Cargo.toml"), "members = [\n    \"day1\",\n]\n").unwrap();
+        fs::write(root.join("runner").join("Cargo.toml"), "day1 = { path = \"../day1\" }\n").unwrap();
+        fs::write(root.join("runner").join("src").join("solvers.rs"),
+                  "pub static SOLVERS: &[Solver] = &[\n];\n").unwrap();
+
+        create_day(&root, 26, "sleigh", "// \"/examples/rust_template.rs\"\n").unwrap();
+        let read = |path: &str| fs::read_to_string(root.join(path)).unwrap();
+        assert_eq!(read("day26/src/sleigh.rs"), "// \"/examples/sleigh.rs\"\n");
+        assert_eq!(read("day26/build.rs"), BUILD_RS);
+        assert_eq!(read("day26/examples/sleigh.sample.in"), "1\n");
+        assert_eq!(read("day26/examples/sleigh.sample.part2.out"), "0\n");
+        assert_eq!(read("day26/src/lib.rs"),
+                   "pub const DAY: u32 = 26;\n\npub mod sleigh;\n\npub type Puzzle = sleigh::Input;\n");
+        assert_eq!(read("day26/src/main.rs"),
+                   "fn main() {\n    aoc::solution::main::<day26::Puzzle>();\n}\n");
+        assert!(read("day26/Cargo.toml").contains("name = \"day26\""));
+        assert!(read("Cargo.toml").contains("\"day26\","));
+        assert!(read("runner/Cargo.toml").contains("day26 = { path = \"../day26\" }"));
+        assert!(read("runner/src/solvers.rs").contains("solution!(26, 2, day26::sleigh),"));
+
+        assert!(create_day(&root, 26, "sleigh", "").unwrap_err().contains("already exists"));
+        assert!(create_day(&root, 27, "sleigh-2", "").is_err());
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
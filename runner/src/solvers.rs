@@ -0,0 +1,136 @@
+//! Every solver in the repo, by day and part: the `Solution` of each day and
+//! the `solve` functions of alternative implementations.
+
+use std::io::{BufRead, Write};
+
+use aoc::parse::Result;
+
+pub type SolveFn = fn(&mut dyn BufRead, &mut dyn Write) -> Result<()>;
+
+pub struct Solver {
+    pub day: u32,
+    pub part: u32,
+    /// Module with the `Solution` or the `solve` function.
+    pub name: &'static str,
+    pub solve: SolveFn,
+}
+
+macro_rules! solver {
+    ( $day:expr, $part:expr, $krate:ident :: $module:ident ) => {
+        Solver {
+            day: $day,
+            part: $part,
+            name: stringify!($module),
+            solve: |input, output| $krate::$module::solve(input, output),
+        }
+    };
+}
+
+/// One part of the `Puzzle` of a day crate, implemented in `module`.
+macro_rules! solution {
+    ( $day:expr, 1, $krate:ident :: $module:ident ) => {
+        Solver {
+            day: $day,
+            part: 1,
+            name: stringify!($module),
+            solve: |input, output| aoc::solution::solve_part1::<$krate::Puzzle, _, _>(input, output),
+        }
+    };
+    ( $day:expr, 2, $krate:ident :: $module:ident ) => {
+        Solver {
+            day: $day,
+            part: 2,
+            name: stringify!($module),
+            solve: |input, output| aoc::solution::solve_part2::<$krate::Puzzle, _, _>(input, output),
+        }
+    };
+}
+
+/// When a part has more than one solver, the first one is the default.
+pub static SOLVERS: &[Solver] = &[
+    solution!(1, 1, day1::trebuchet),
+    solution!(1, 2, day1::trebuchet),
+    solution!(2, 1, day2::cubes),
+    solution!(2, 2, day2::cubes),
+    solution!(3, 1, day3::gears),
+    solution!(3, 2, day3::gears),
+    solution!(4, 1, day4::scratchcards),
+    solution!(4, 2, day4::scratchcards),
+    solution!(5, 1, day5::almanac),
+    solution!(5, 2, day5::almanac),
+    solver!(5, 2, day5::almanac2faster),
+    solver!(5, 2, day5::almanac2),
+    solution!(6, 1, day6::races),
+    solution!(6, 2, day6::races),
+    solution!(7, 1, day7::camel_cards),
+    solution!(7, 2, day7::camel_cards),
+    solution!(8, 1, day8::wasteland),
+    solution!(8, 2, day8::wasteland),
+    solution!(9, 1, day9::oasis),
+    solution!(9, 2, day9::oasis),
+    solution!(10, 1, day10::maze),
+    solution!(10, 2, day10::maze),
+    solver!(10, 2, day10::maze2),
+    solution!(11, 1, day11::galaxies),
+    solution!(11, 2, day11::galaxies),
+    solution!(12, 1, day12::springs),
+    solver!(12, 1, day12::springs_brut),
+    solution!(12, 2, day12::springs),
+    solution!(13, 1, day13::palindrome),
+    solution!(13, 2, day13::palindrome),
+    solution!(14, 1, day14::dish),
+    solution!(14, 2, day14::dish),
+    solution!(15, 1, day15::lens),
+    solution!(15, 2, day15::lens),
+    solution!(16, 1, day16::lava_floor),
+    solution!(16, 2, day16::lava_floor),
+    solution!(17, 1, day17::crucible),
+    solution!(17, 2, day17::crucible),
+    solution!(18, 1, day18::lagoon),
+    solution!(18, 2, day18::lagoon),
+    solution!(19, 1, day19::aplenty),
+    solution!(19, 2, day19::aplenty),
+    solution!(20, 1, day20::pulse),
+    solution!(20, 2, day20::pulse),
+    solution!(21, 1, day21::steps),
+    solution!(21, 2, day21::steps),
+    solution!(22, 1, day22::slabs),
+    solution!(22, 2, day22::slabs),
+    solution!(23, 1, day23::walk),
+    solution!(23, 2, day23::walk),
+    solution!(24, 1, day24::hail),
+    solution!(24, 2, day24::hail),
+    solution!(25, 1, day25::snowerload),
+];
+
+pub fn find(day: u32, part: u32, name: Option<&str>) -> Option<&'static Solver> {
+    SOLVERS.iter().find(|s| s.day == day && s.part == part && name.is_none_or(|n| n == s.name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find() {
+        assert_eq!(find(10, 2, None).unwrap().name, "maze");
+        assert_eq!(find(10, 2, Some("maze2")).unwrap().name, "maze2");
+        assert!(find(10, 2, Some("maze2easier")).is_none());
+        assert!(find(25, 2, None).is_none());
+    }
+
+    #[test]
+    fn test_solve_error() {
+        let solver = find(7, 1, None).unwrap();
+        let mut answer = Vec::new();
+        let error = (solver.solve)(&mut "32T3K 765\nT55J5".as_bytes(), &mut answer).unwrap_err();
+        assert_eq!(error.to_string(), "day 7, line 2, column 6: expected a number, found end of line");
+    }
+
+    #[test]
+    fn test_every_solver_is_reachable() {
+        for s in SOLVERS {
+            assert!(std::ptr::eq(find(s.day, s.part, Some(s.name)).unwrap(), s));
+        }
+    }
+}
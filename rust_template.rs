@@ -1,109 +1,70 @@
-use std::cmp::{max, min};
-use std::io::{BufRead, BufReader, Write};
-
-/// UnsafeScanner is from https://github.com/EbTech/rust-algorithms/blob/master/src/scanner.rs
-pub struct UnsafeScanner<R> {
-    reader: R,
-    buf_str: Vec<u8>,
-    buf_iter: std::str::SplitAsciiWhitespace<'static>,
+use std::fmt::Display;
+use std::io::BufRead;
+use aoc::debug;
+use aoc::parse::{Lines, Result};
+use aoc::solution::Solution;
+
+pub struct Input {
+    numbers: Vec<i64>,
 }
 
-impl<R: BufRead> UnsafeScanner<R> {
-    pub fn new(reader: R) -> Self {
-        Self {
-            reader,
-            buf_str: vec![],
-            buf_iter: "".split_ascii_whitespace(),
-        }
-    }
+impl Solution for Input {
+    const DAY: u32 = crate::DAY;
 
-    pub fn token<T: std::str::FromStr>(&mut self) -> T {
-        loop {
-            if let Some(token) = self.buf_iter.next() {
-                return token.parse().ok().expect("Failed parse");
-            }
-            self.buf_str.clear();
-            self.reader
-                .read_until(b'\n', &mut self.buf_str)
-                .expect("Failed read");
-            self.buf_iter = unsafe {
-                let slice = std::str::from_utf8_unchecked(&self.buf_str);
-                std::mem::transmute(slice.split_ascii_whitespace())
-            }
-        }
-    }
-}
+    fn parse<R: BufRead>(lines: Lines<R>) -> Result<Input> {
+        // AoC typical IO.
+        let mut numbers = Vec::new();
 
-macro_rules! dprintln {
-    ( $( $x:expr ),* ) => {
-        {
-	    #[cfg(test)]
-            println!($($x), *);
+        for line in lines {
+            let line = line?;
+            let mut parser = line.parser();
+            numbers.push(parser.number()?);
         }
-    };
-}
 
-fn solve<R: BufRead, W: Write>(mut input: R, mut output: W) {
-    // AoC typical IO.
-    let mut solution: i64 = 0;
 
-    for line in BufReader::new(input).lines().map(|l| l.unwrap()) {
-    }
+        // Codeforces typical IO, or AoC input in blocks, `key: values` lines, comma
+        // lists and digit grids.
+        //let mut scan = aoc::scanner::Scanner::from_lines(lines);
+        //let zet = scan.token::<usize>()?;
 
-    writeln!(output, "{}", solution).unwrap();
+        //for _ in 0..zet {
+        //    numbers.push(scan.token()?);
+        //}
 
+        Ok(Input {
+            numbers,
+        })
+    }
 
-    // Codeforces typical IO.
-    //let mut scan = UnsafeScanner::new(input);
-    //let zet = scan.token::<usize>();
+    fn part1(&self) -> Result<impl Display> {
+        debug!("{} numbers", self.numbers.len());
+        let solution: i64 = 0;
 
-    //for _ in 0..zet {
-    //    writeln!(output, "10 11 20").unwrap();
-    //}
-}
+        Ok(solution)
+    }
 
-pub fn main() {
-    let stdin = std::io::stdin();
-    let stdout = std::io::stdout();
-    solve(stdin.lock(), stdout.lock());
+    fn part2(&self) -> Result<impl Display> {
+        let solution: i64 = 0;
+
+        Ok(solution)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc::solution::{solve_part1, solve_part2};
 
-    fn test_exact(input: &str, output: &str) {
-        let mut actual_out: Vec<u8> = Vec::new();
-        solve(input.as_bytes(), &mut actual_out);
-        assert_eq!(String::from_utf8(actual_out).unwrap(), output);
+    #[allow(dead_code)]
+    fn test_part1(input: &str, output: &str) {
+        aoc::testing::test_ignore_whitespaces(|i, o| solve_part1::<Input, _, _>(i, o), input, output);
     }
 
-    fn test_ignore_whitespaces(input: &str, output: &str) {
-        let mut actual_out: Vec<u8> = Vec::new();
-        solve(input.as_bytes(), &mut actual_out);
-        let actual_out_str = String::from_utf8(actual_out).unwrap();
-        let actual_outs = actual_out_str.split_whitespace().collect::<Vec<&str>>();
-        let expected_outs = output.split_whitespace().collect::<Vec<&str>>();
-        assert_eq!(actual_outs, expected_outs);
+    #[allow(dead_code)]
+    fn test_part2(input: &str, output: &str) {
+        aoc::testing::test_ignore_whitespaces(|i, o| solve_part2::<Input, _, _>(i, o), input, output);
     }
 
-    #[test]
-    fn sample() {
-        test_ignore_whitespaces(
-            "1",
-            "0",
-        );
-    }
-
-    #[test]
-    fn test_test_functions() {
-        test_ignore_whitespaces(
-            "1",
-            "0",
-        );
-        test_exact(
-            "1",
-            "0\n",
-        );
-    }
+    // One test per examples/<module>.<case>.in and .part1.out or .part2.out.
+    include!(concat!(env!("OUT_DIR"), "/examples/rust_template.rs"));
 }